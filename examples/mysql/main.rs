@@ -16,6 +16,14 @@ struct Todo {
     done: bool,
 }
 
+#[derive(Debug, Table)]
+struct NewTodo {
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    description: String,
+    done: bool,
+}
+
 #[tokio::main(flavor = "current_thread")]
 async fn main() {
     let m = Migrator::new(std::path::Path::new("examples/mysql/migrations"))
@@ -59,6 +67,22 @@ async fn main() {
         None => println!("Todo item does not exist for the id {todo_id}"),
     }
 
+    // Without a primary key, the record created is read back by its `last_insert_id()`
+    // on the same connection, which can also be a single connection checked out of the pool
+    let mut conn = pool.acquire().await.unwrap();
+    let other_todo = NewTodo {
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        description: "My second item".to_string(),
+        done: false,
+    }
+    .create(&mut *conn)
+    .await
+    .expect("Todo item should be created");
+    println!("My second todo created {:?}", other_todo);
+    other_todo.delete(&mut *conn).await.unwrap();
+    drop(conn);
+
     check_updated_item.unwrap().delete(&pool).await.unwrap();
     let deleted_todo = Todo::get_by_id(&pool, &todo_id).await.unwrap();
     match deleted_todo {
@@ -113,6 +113,12 @@ async fn main() {
         None => println!("Todo item does not exist for the id {0}", todo.id),
     }
 
+    // The methods also accept a single connection checked out of the pool
+    let mut conn = pool.acquire().await.unwrap();
+    let todos = Todo::list_all(&mut *conn).await.unwrap();
+    println!("{} todo items", todos.len());
+    drop(conn);
+
     check_updated_item.unwrap().delete(&pool).await.unwrap();
     let deleted_todo = Todo::get_by_id(&pool, &todo.id).await.unwrap();
     match deleted_todo {
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS account (
+    id            NUMERIC(20, 0) PRIMARY KEY NOT NULL,
+    balance       NUMERIC(12, 2)             NOT NULL,
+    credit_limit  NUMERIC(12, 2)             NULL
+);
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS settings (
+    id        BIGINT PRIMARY KEY NOT NULL,
+    metadata  JSONB              NOT NULL DEFAULT '{}',
+    extra     JSONB              NULL
+);
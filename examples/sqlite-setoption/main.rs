@@ -56,11 +56,7 @@ async fn main() {
         Some(ref item) => println!("First todo item is {:?}", item),
         None => println!("Todo item does not exist for the id {}", todo.id),
     }
-    let update_todo = UpdateTodo {
-        id: todo.id,
-        description: "My first item updated".to_string().into(),
-        ..Default::default()
-    };
+    let update_todo = UpdateTodo::new(todo.id).with_description("My first item updated");
     update_todo
         .update(&pool)
         .await
@@ -16,26 +16,161 @@
 //! The code above would generate the following methods on the Todo object
 //! ```rust,ignore
 //! impl Todo {
-//!     pub fn get_by_id(pool: &DbPool, id: &i32) -> sqlx::Result<Self> {
-//!         // Get a specific record for a given ID
+//!     pub const TABLE_NAME: &str = "todo";
+//!     pub const COLUMNS: &[&str] = &["id", "created_at", "updated_at", "description", "done"];
+//!     pub const OPERATIONS: &[&str] = &["get", "list", "create", "update", "delete"];
+//!     pub const PRIMARY_KEY_NAME: Option<&str> = Some("id");
+//!     pub fn table_name() -> &'static str {
+//!         // Returns `TABLE_NAME`
+//!     }
+//!     pub fn columns() -> &'static [&'static str] {
+//!         // Returns `COLUMNS`, the names of the columns in the database
+//!     }
+//!     pub fn operations() -> &'static [&'static str] {
+//!         // Returns `OPERATIONS`, the operations generated for the struct (eg: for an admin UI)
+//!     }
+//!     pub fn primary_key_name() -> Option<&'static str> {
+//!         // Returns `PRIMARY_KEY_NAME`, the name of the primary key column if any
+//!     }
+//!     pub fn table_exists(pool: &DbPool) -> sqlx::Result<bool> {
+//!         // Whether the table exists, from the catalog of the database
+//!         // (`information_schema.tables` for Postgres and MySQL, `sqlite_master` for SQLite)
+//!     }
+//!     pub fn get_by_id(pool: &DbPool, id: impl Borrow<i32>) -> sqlx::Result<Option<Self>> {
+//!         // Get a specific record for a given ID, either owned (`id`) or borrowed (`&id`)
 //!         // Use the `id` column by default
 //!     }
+//!     pub fn get_by_id_or_err(pool: &DbPool, id: impl Borrow<i32>) -> sqlx::Result<Self> {
+//!         // Same as `get_by_id` but returns `sqlx::Error::RowNotFound`
+//!         // when no record matches the given ID
+//!     }
+//!     pub fn get_by(pool: &DbPool, column: &str, value: V) -> sqlx::Result<Vec<Self>> {
+//!         // Get the records where the column is equal to the value.
+//!         // The column must be part of `Todo::COLUMNS`, otherwise `sqlx::Error::ColumnNotFound` is returned
+//!     }
+//!     pub fn get_by_ids_ordered(pool: &DbPool, ids: &[i32]) -> sqlx::Result<Vec<Self>> {
+//!         // Get the records in the same order as the given IDs. The IDs without a record are skipped.
+//!         // Postgres sorts them with `array_position()`, the other databases in memory.
+//!         // Only when the `return_object` is `Self`
+//!     }
+//!     pub fn existing_ids(pool: &DbPool, ids: &[i32]) -> sqlx::Result<Vec<i32>> {
+//!         // The subset of the IDs which have a record (eg: to know which records to create when syncing)
+//!     }
+//!     pub fn stream_by_ids(pool: &DbPool, ids: Vec<i32>, chunk_size: NonZeroUsize) -> impl Stream<Item = sqlx::Result<Self>> {
+//!         // Stream the records with one `IN (...)` query per chunk of `chunk_size` IDs,
+//!         // which keeps each query under the bind parameter limit of the database.
+//!         // The executor must be `Copy` (eg: `&DbPool`)
+//!     }
+//!     pub fn find_one_by_<field>(pool: &DbPool, value: &T) -> sqlx::Result<Option<Self>> {
+//!         // Only for the `#[tiny_orm(unique)]` fields.
+//!         // Get the record where the column is equal to the value (`LIMIT 1`)
+//!     }
 //!     pub fn list_all(pool: &DbPool) -> sqlx::Result<Vec<Self>> {
-//!         // Get all the records
+//!         // Get all the records, in the `order_by` order when it is set
+//!     }
+//!     pub fn list_all_ordered(pool: &DbPool, order: &[(&str, tiny_orm::Order)]) -> sqlx::Result<Vec<Self>> {
+//!         // Get all the records sorted by the columns (eg: `&[("done", Order::Asc), ("id", Order::Desc)]`).
+//!         // A name which is not one of `COLUMNS` returns `sqlx::Error::ColumnNotFound` without running the query.
+//!         // The `order_by` order is only used when `order` is empty.
+//!     }
+//!     pub fn list_all_summary(pool: &DbPool) -> sqlx::Result<Vec<TodoSummary>> {
+//!         // Only with `projections(TodoSummary = "id, description")`, one per projection.
+//!         // Get all the records reading only the columns of the projection (`SELECT id, description FROM todo`)
+//!         // into the struct, which derives `sqlx::FromRow`.
+//!     }
+//!     pub fn list_page(pool: &DbPool, limit: i64, offset: i64) -> sqlx::Result<tiny_orm::Page<Self>> {
+//!         // Get a page of records along with the total number of records
+//!     }
+//!     pub fn list_after(pool: &DbPool, after: &i64, limit: i64) -> sqlx::Result<Vec<Self>> {
+//!         // Get the next `limit` records after a value of the primary key (`WHERE id > ? ORDER BY id LIMIT ?`),
+//!         // the last one of a page being the `after` of the next page.
+//!         // The `cursor` option orders them by another column instead.
+//!     }
+//!     pub fn list_matching(pool: &DbPool, filter: &TodoFilter) -> sqlx::Result<Vec<Self>> {
+//!         // Get the records matching every field set in the filter (`AND <column> = ?`), all the records when none is set.
+//!         // `TodoFilter` is generated along with the struct with an `Option<T>` per field (except the `json` ones) and implements `Default`.
+//!         // A `NULL` cannot be matched.
+//!     }
+//!     pub fn upsert_with_outcome(&self, pool: &DbPool) -> sqlx::Result<tiny_orm::UpsertOutcome<Self>> {
+//!         // With the `create` and `update` operations and a primary key that is not `auto`.
+//!         // Create the record or update the existing one with the same primary key (`ON CONFLICT DO UPDATE`)
+//!         // and returns whether it has been `Inserted` or `Updated`. See `conflict_target` to use another unique index.
+//!         // Postgres tells it within the same query. SQLite and MySQL look the record up first within a transaction
+//!         // (`FOR UPDATE` with MySQL), so they take an `Acquire` (eg: a pool or a connection) instead of an executor.
+//!     }
+//!     pub fn upsert_many(pool: &DbPool, items: &[Self]) -> sqlx::Result<u64> {
+//!         // With the `create` and `update` operations and a primary key that is not `auto` (or a `conflict_target`).
+//!         // Create or update the records with a multi-row `INSERT ... ON CONFLICT DO UPDATE` (`ON DUPLICATE KEY UPDATE` with MySQL),
+//!         // in chunks under the bind parameter limit of the database and within a transaction.
+//!         // Returns the number of rows affected as reported by the database (MySQL counts an updated row twice).
+//!     }
+//!     pub fn save(&mut self, pool: &DbPool) -> sqlx::Result<bool> {
+//!         // Create the record when the primary key is not set yet (`0` for `primary_key(auto)`, `None` for an `Option`)
+//!         // and stores the new primary key, otherwise update it. Returns whether the record has been created.
+//!         // Only with an auto increment or an `Option` primary key.
+//!     }
+//!     pub fn touch(&self, pool: &DbPool) -> sqlx::Result<()> {
+//!         // Set the `updated_at` column to the current timestamp of the database without changing the other columns.
+//!         // Only with the `update` operation and an `updated_at` timestamp field.
 //!     }
 //!     pub fn delete(&self, pool: &DbPool) -> sqlx::Result<()> {
 //!         // Delete the record in the database
 //!     }
+//!     pub fn delete_all(pool: &DbPool) -> sqlx::Result<u64> {
+//!         // Dangerous: delete ALL the records of the table (or soft delete them with `soft_deletion`)
+//!         // and returns the number of records deleted
+//!     }
+//!     pub fn delete_by_done(pool: &DbPool, value: &bool) -> sqlx::Result<u64> {
+//!         // Delete (or soft delete) the records matching the value and returns the number of records deleted.
+//!         // Only for the `#[tiny_orm(filterable)]` fields.
+//!     }
 //!     pub fn create(&self, pool: &DbPool) -> sqlx::Result<i32> {
 //!         // Create the Todo object as a record in
 //!         // the database and returns the primary key of the record created.
+//!         // The SQL query is a constant string so it is prepared and cached once by sqlx,
+//!         // unless a column is a `SetOption` or an `Option` primary key.
+//!         // Every field is bound by reference, so borrowing the struct costs no clone and consuming it would not save any.
+//!         // With `returning_generated`, it returns a tuple of the primary key and the columns filled in by the database,
+//!         // eg: `sqlx::Result<(i32, DateTime<Utc>, DateTime<Utc>)>`.
+//!     }
+//!     pub fn create_for<T>(&self, tenant: &T, pool: &DbPool) -> sqlx::Result<i32> {
+//!         // Only with `inject`, same as `create` with the injected column bound from `tenant`
+//!         // instead of a field of the struct.
+//!     }
+//!     pub fn create_if_not_exists(&self, pool: &DbPool) -> sqlx::Result<bool> {
+//!         // Create the record unless it conflicts with an existing one
+//!         // (`ON CONFLICT DO NOTHING` or `INSERT IGNORE` with MySQL).
+//!         // Returns whether the record has been inserted.
+//!     }
+//!     pub fn create_returning_id(&self, pool: &DbPool) -> sqlx::Result<i32> {
+//!         // Same as `create` but always returns the primary key, whatever the backend or the `return_object`
+//!         // (`RETURNING` with Postgres and SQLite, `last_insert_id()` or the bound key with MySQL).
+//!         // Only when the struct has a primary key.
+//!     }
+//!     pub fn create_many(pool: &DbPool, items: &[Self]) -> sqlx::Result<Vec<i32>> {
+//!         // With a `primary_key(auto)`, create the records with a multi-row `INSERT` in chunks under the bind parameter limit
+//!         // of the database and within a transaction, and returns their primary keys in the order of `items`.
+//!         // MySQL only gives the key of the first record of each `INSERT`, so the next ones are computed as `auto_increment_increment` apart.
+//!         // It holds with the `innodb_autoinc_lock_mode` of a plain multi-row `INSERT`, but not when another process hands out the keys
+//!         // (eg: a trigger or a proxy allocating them).
 //!     }
 //!     pub fn update(&self, pool: &DbPool) -> sqlx::Result<()> {
 //!         // Update the record in the database with the values
 //!         // currently part of the Todo object
 //!     }
+//!     pub fn get_by_id_sql() -> String {
+//!         // The SQL built by `get_by_id` (eg: "SELECT * FROM todo WHERE id = $1")
+//!         // Same for `list_all_sql`, `create_sql`, `update_sql` and `delete_sql`
+//!         // where all the columns are considered set.
+//!     }
 //! }
 //! ```
+//! `DbPool` is exported as `tiny_orm::DbPool`, an alias of the `sqlx::Pool` of the backend selected
+//! with the `postgres`, `mysql` or `sqlite` feature (`tiny_orm::Db` being the `sqlx::Database` itself).
+//! The methods actually accept any `sqlx::Executor` of the backend (`list_page` any `sqlx::Acquire`), so a transaction is used with `&mut *tx`
+//! and can be passed to several methods in a row. The same goes for a single connection checked out of the pool with `&mut *conn`,
+//! except for `stream_by_ids()` which needs a `Copy` executor.
+//! The `create`, `update` and `delete` methods are `#[must_use]` so that an ignored result warns.
 //!
 //! # Examples
 //! More examples can be found in the [examples](./examples) directory.
@@ -44,20 +179,113 @@
 //! The Table macro comes with a few options to give flexibility to the user
 //!
 //! ### At the Struct level
+//! The options can be split across several `#[tiny_orm(...)]` attributes (eg: when one of them is generated by another macro).
+//! The operations of `only`, `exclude` and `add` are merged while the other options keep the last value given.
+//!
 //! - **table_name**: The name of the table in the database.
 //!   Default being a snake_case version of the Struct name. So `MyStruct` would have `my_struct` as a default `table_name`.
+//!   It can also be read from an environment variable at compile time with `table_name = env!("TABLE_NAME")`.
+//!   A qualified name (eg: `table_name = "billing.invoice"`) is snake cased segment by segment and each segment is quoted in the queries (`"billing"."invoice"`, or `` `billing`.`invoice` `` with MySQL).
+//!   The `table_prefix` goes before the table and `table_exists()` looks it up in that schema (the attached database for SQLite).
+//! - **table_name_fn**: The name of the generated `table_name()` function (eg: `#[tiny_orm(table_name_fn = "orm_table")]`) when the struct already has a `table_name` method.
+//!   The `TABLE_NAME` constant keeps its name.
+//!   Default `table_name`.
+//! - **table_prefix**: A prefix added to the `table_name` (eg: `tenant_`), a string or an `env!("TABLE_PREFIX")` read at compile time.
+//!   The generated code reads the environment variable again with `env!`, so that a change of its value recompiles the crate.
+//!   Default none.
 //! - **only**: The methods that will only be available to that struct. Multiple values are comma separated.
 //!   Default is dependent on the struct name (see below).
 //! - **exclude**: The methods that will be excluded for that struct. Multiple values are comma separated
 //!   Default empty vec.
 //! - **add**: The methods that will be added for that struct. Multiple values are comma separated
 //!   Default empty vec.
+//! - **view**: The struct targets a read-only database view (eg: `#[tiny_orm(table_name = "todo_stats", view)]`). The operations default to `get` and `list`,
+//!   and requesting `create`, `update` or `delete` (eg: with `all` or `add`) is a compile error.
+//!   Default false.
 //! - **all**: All the methods will be available to the struct. This will override the default values when none are provided.
+//!   It can be combined with `exclude` to remove some of them (eg: `#[tiny_orm(all, exclude = "delete")]`) but not with `only`.
 //!   Default none.
 //! - **soft_deletion**: When set to true, it will add a `deleted_at` filter when querying the data. When using the `delete` method, it will set the `deleted_at` column to the current timestamp.
+//!   A custom column can be used instead of `deleted_at` with `soft_deletion = "archived_at"`.
+//!   A boolean column can be used instead of a timestamp with `soft_deletion(bool = "is_deleted")`: the filter becomes `is_deleted = false` and `delete` sets `is_deleted = true`. `is_deleted()` is then generated for a `bool` field, and `soft_delete_now` cannot be used.
+//!   `get_by_id_with_deleted()` and `list_all_with_deleted()` are also generated to read the soft deleted records as well (the `scope` still applies).
+//!   An `is_deleted()` method, returning whether the soft deletion column is set, is generated when that column is an `Option` field of the struct (eg: `deleted_at: Option<DateTime<Utc>>`).
+//!   The current timestamp of the backend (eg: `NOW()` with Postgres) can be replaced by a function call with `soft_delete_now = "clock_timestamp()"`.
+//!   If the struct has an `updated_at` timestamp column (eg: `DateTime<Utc>`, `NaiveDateTime`, `OffsetDateTime`), it will also be set to the current timestamp when deleting.
 //!   Default false.
 //! - **return_object**: A custom object that would be returned instead of `Self`. Useful when creating or updating records with partial information.
+//!   With `return_object = "()"`, `create()` returns nothing and runs a plain `execute()` without `RETURNING` (`create_returning_id()` and `save()` are not generated).
 //!   Default is `Self` which corresponds to the current Strut.
+//! - **like**: The name of another struct used to derive the default `table_name` and `return_object` (eg: `#[tiny_orm(like = "Todo")]` uses the `todo` table and returns a `Todo`).
+//!   Only the name is used since the macro cannot read the attributes of the other struct, so a custom `table_name` of `Todo` must be repeated.
+//!   Explicit `table_name` and `return_object` take precedence.
+//!   Default none.
+//! - **primary_key_field**: The field used as the primary key when none is marked with `#[tiny_orm(primary_key)]`. The field must be part of the struct.
+//! - **lookup_key**: The field used by `get_by_id()`, `get_by_id_or_err()` and `get_by_id_sql()` instead of the primary key (eg: `#[tiny_orm(lookup_key = "slug")]`). The `update` and `delete` methods still use the primary key.
+//!   The `TinyTable` trait is not implemented since its `get_by_id()` takes the primary key.
+//!   Default is `id`.
+//! - **discriminator**: The column holding the variant of an enum mapped to a single table (see [Single table inheritance](#single-table-inheritance)).
+//!   Default `kind`.
+//! - **cursor**: The field used by `list_after()` instead of the primary key (eg: `#[tiny_orm(cursor = "created_at")]`).
+//!   The records sharing the same value are ordered arbitrarily, so a column that is not unique may skip some of them between two pages.
+//!   Default is the primary key.
+//! - **delete_returning**: The `delete` method returns the deleted record (`Option<return_object>`) using `RETURNING`. It also works with `soft_deletion`.
+//!   Ignored with MySQL which does not support `RETURNING`.
+//!   Default false.
+//! - **returning_generated**: `create()` returns the primary key followed by the columns it does not insert (`db_default` and `readonly`), in the order of the fields,
+//!   eg: `(i64, DateTime<Utc>, DateTime<Utc>)` using `RETURNING id, created_at, updated_at`. `create_returning_id()` and `save()` keep only the primary key.
+//!   Not supported by MySQL and `sqlite-no-returning`, nor with `return_object = "()"`.
+//!   Default false.
+//! - **returning**: The columns returned by the `RETURNING` statement when the entire record is returned (eg: when a custom `return_object` is set). Multiple values are comma separated.
+//!   Useful when the table has more columns than the `return_object`.
+//!   Since the macro cannot read the fields of the `return_object`, they must be listed explicitly (eg: `returning = "id, description"`).
+//!   It applies to `create()` (without a primary key), `update()` and `delete()` (with `delete_returning`), not to the `SELECT` queries.
+//!   Default is `*`.
+//! - **scope**: A static condition added to the `WHERE` clause of every `SELECT` query (eg: `#[tiny_orm(scope = "archived = false")]`), along with the `soft_deletion` condition.
+//! - **projections**: Lighter structs read from a few columns only (eg: `#[tiny_orm(projections(TodoSummary = "id, description"))]`), each one generating a `list_all_<name>()` method named after the projection without the name of the struct (`list_all_summary()`).
+//!   The projection is defined by the user and derives `sqlx::FromRow`. The `soft_deletion`, `scope` and `order_by` of the struct still apply.
+//! - **inject**: A column set by the caller rather than by the struct, eg: a tenant (`#[tiny_orm(inject = "tenant_id")]`). It generates `create_for(&self, tenant, db)` which binds `tenant` for that column, so the struct does not need to carry it.
+//!   A field of the same name is ignored by `create_for()` and still used by `create()`.
+//! - **order_by**: The default order of `list_all()`, `list_all_with_deleted()`, `list_page()` and `list_matching()` (eg: `#[tiny_orm(order_by = "created_at DESC, id")]`). The direction is `ASC` unless `DESC` is given. `list_all_ordered()` falls back to it only when no order is given.
+//!   The condition is trusted and pushed as is in the queries, only a `;`, a comment or an unbalanced quote are rejected.
+//!   It does not apply to the `UPDATE` and `DELETE` queries nor to the `has_many` accessors.
+//!   Default none.
+//! - **conflict_target** and **conflict_where**: The columns of the unique index used by `upsert_with_outcome()` and `upsert_many()` instead of the primary key (eg: `#[tiny_orm(conflict_target = "email")]`).
+//!   `conflict_where` targets a partial unique index (eg: `conflict_where = "deleted_at IS NULL"` emits `ON CONFLICT (email) WHERE deleted_at IS NULL`), the condition must match the one of the index.
+//!   The primary key is then not updated by the upsert and it can be `auto`.
+//!   Ignored with MySQL since `ON DUPLICATE KEY UPDATE` applies to any unique index.
+//!   Default is the primary key.
+//! - **from_row**: Implements `sqlx::FromRow` for the struct, reading every field from the same column name as the queries (eg: with `column` or `#[sqlx(rename_all = "...")]`) so both always agree.
+//!   A `json` field is decoded from `sqlx::types::Json`, a `transform` field with the `decode` function of its module and a `#[sqlx(skip)]` field is set to its `Default::default()` value.
+//!   Do not derive `FromRow` along with it. Generic structs are not supported.
+//!   Default false.
+//! - **skip_defaults**: The `create` methods omit the fields equal to their `Default::default()` value so the column defaults of the database are used instead.
+//!   Useful for wide tables with mostly default values. The types of the fields must implement `PartialEq` and `Default`. The primary key is always inserted.
+//!   Default false.
+//! - **error**: A custom error type returned by the generated methods instead of `sqlx::Error` (eg: `#[tiny_orm(error = "crate::AppError")]`).
+//!   The type must implement `From<sqlx::Error>`. The `TinyTable` trait is not implemented since its methods return a `sqlx::Result`.
+//! - **belongs_to** and **foreign_key**: Generates an accessor to the record referenced by a foreign key field (eg: `#[tiny_orm(belongs_to = "User", foreign_key = "user_id")]` generates `user(db)`).
+//!   It calls `User::get_by_id(db, &self.user_id)` so the target must have the `get` operation. An `Option` foreign key returns `None` when it is not set.
+//!   With the `get` operation, `get_by_id_with_user(db, id)` also returns the record along with the one it refers to (`Option<(Self, Option<User>)>`) using two queries.
+//!   The attribute can be repeated for multiple targets.
+//! - **has_many** and **foreign_key**: Generates an accessor to the records referencing the struct with a foreign key column (eg: `#[tiny_orm(has_many = "Todo", foreign_key = "user_id")]` generates `todos(db)`).
+//!   It runs `SELECT * FROM todo WHERE user_id = <primary key>` so a primary key is mandatory. The table defaults to the snake case of the target and can be set with `child_table = "..."`.
+//!   Add `child_soft_deletion` (or `child_soft_deletion = "archived_at"`) when the target uses `soft_deletion` since the macro cannot read the attributes of the target.
+//!   The accessor only adds an `s` to the target, so use `child_fn` for another plural (eg: `#[tiny_orm(has_many = "Category", foreign_key = "user_id", child_fn = "categories")]`).
+//! - **cascade_delete**: The `has_many` records deleted by `delete()` before the struct itself, by the name of their accessor (eg: `#[tiny_orm(cascade_delete = "todos, notes")]`).
+//!   They are soft deleted with `child_soft_deletion`, deleted otherwise. `delete()` then takes a connection or a transaction (`Acquire`) to run the queries in a transaction,
+//!   nested in the transaction of the caller if any, and the struct no longer implements `TinyTable`. `delete_all()` and the `delete_by_<field>()` methods do not cascade.
+//! - **database**: The backend targeted by that struct (`postgres`, `mysql` or `sqlite`), which allows using multiple databases in the same crate.
+//!   The sqlx feature of that backend must be enabled as well. `tiny_orm::DbPool` still refers to the backend of the enabled feature.
+//!   Default is the backend of the enabled feature.
+//!
+//! The `#[sqlx(rename_all = "...")]` attribute used by `FromRow` is also respected, so the generated queries use the same column names.
+//! Supported values are `lowercase`, `UPPERCASE`, `snake_case`, `SCREAMING_SNAKE_CASE`, `kebab-case`, `camelCase` and `PascalCase`.
+//! A column name which is not a plain lowercase identifier (eg: `firstName`, `first-name` or `FIRST_NAME`) is quoted in the queries (`"firstName"`, or `` `firstName` `` with MySQL),
+//! so the table must be created with the same quoted names.
+//!
+//! The struct can have lifetime and generic parameters (eg: `struct NewTodo<'a> { description: &'a str }`), they are carried to the generated `impl` blocks.
+//! Borrowed fields only work with the `create` and `update` operations since `FromRow` cannot decode a borrowed record, and the `return_object` cannot have generic parameters.
 //!
 //! _Note: `only` cannot be used with `exclude` nor `add` cannot be used together._
 //!
@@ -107,9 +335,11 @@
 //!         // Use the NewTodo object to create a record
 //!         // in the database and return the record created.
 //!
-//!         // This would not be supported for MySQL since
-//!         // there is a need to know the primary key (auto increment or not)
-//!         // to either return the value or use `last_insert_id()`
+//!         // For MySQL, which does not support `RETURNING`, the record created
+//!         // is read back by a second query on the same connection
+//!         // (`SELECT * FROM todo WHERE id = <last_insert_id()>`, `id` being renamed with `rename_all`),
+//!         // so the table must have an `id` auto increment primary key, otherwise `create()` returns an error,
+//!         // and `create()` takes any `sqlx::Acquire` (eg: a pool or a connection).
 //!     }
 //! }
 //!
@@ -151,11 +381,60 @@
 //! ```
 //!
 //! ### At the field level
-//! - **primary_key**: The field that would be used as a primary key for the queries. For some methods the primary key is mandatory (eg: `get_by_id()`). If not specified, it will default to `id` if part of the struct.
+//! - **primary_key**: The field that would be used as a primary key for the queries. For some methods the primary key is mandatory (eg: `get_by_id()`). If not specified, it will default to `id` (or the `primary_key_field` of the struct) if part of the struct.
 //! - **primary_key(auto)**: The field that would be used as a primary key for the queries. When "auto" is set, it will skip the column and returns it during the `create()` method since it will be auto generated by the database itself.
+//! - **primary_key(uuid)**: The `Uuid` primary key is generated client side with `Uuid::new_v4()` during the `create()` methods when it is nil, then returned by `create()` without a `RETURNING` statement.
+//! - **primary_key(sequence = "my_seq")**: Postgres only. The primary key is drawn from the named sequence with `nextval('my_seq')` during the `create()` methods, then returned like a `primary_key(auto)`. A schema qualified name such as "public.my_seq" is accepted.
+//!   A key which is already set is kept. It requires the `uuid` feature of tiny-orm.
+//! - **db_default**: The column is never part of the `create()` query so the database applies its `DEFAULT` value (eg: a `created_at` timestamp).
+//!   Unlike `SetOption`, the column is always skipped and does not need a wrapper type.
+//! - **readonly**: The column is read (eg: by `get_by_id()`) but never part of the `create()` nor the `update()` queries (eg: a `GENERATED ALWAYS AS` column).
+//! - **unique**: Generates a `find_one_by_<field>(db, &value)` method which returns the record matching the value, if any (eg: a user by its email).
+//!   The uniqueness itself must be enforced by the database.
+//! - **filterable**: Generates a `delete_by_<field>(db, &value)` method which deletes all the records matching the value (eg: the done todos) and returns the number of records deleted.
+//!   With `soft_deletion`, the records are soft deleted instead. It requires the `delete` operation.
+//! - **json**: The value is bound as `sqlx::types::Json` during `create()` and `update()` so any `serde::Serialize` type can be stored in a JSON column (eg: a `jsonb` column with Postgres).
+//!   It composes with `SetOption`, and a `None` (eg: `SetOption::Set(None)`) is still stored as `NULL` instead of a JSON `null`. The `json` feature of sqlx is required.
+//!   Decoding is left to `FromRow` (eg: with `#[sqlx(json)]` or a `serde_json::Value` field).
+//! - **transform**: The module whose `encode(&T)` function encodes the value before it is bound by `create()` and `update()` (eg: `#[tiny_orm(transform = "crate::crypto")]` to encrypt a PII column).
+//!   The returned type must be supported by the database (eg: `Vec<u8>`), and a `None` is still stored as `NULL`. It cannot be combined with `json`.
+//!   The values used by the lookups (eg: `find_one_by_<field>()`) are not encoded.
+//!   Decoding is left to `FromRow`: with the `from_row` attribute of the struct, the `decode(Bytes) -> T` function of the module is called on the value read.
+//!   Otherwise, the field must decode itself (eg: a newtype implementing `sqlx::Decode`) or `FromRow` must be implemented manually.
+//! - **bulk_updatable**: Generates an `update_<field>_by_ids(db, &value, &ids)` method which sets the column to the same value for all the records matching the primary keys in a single `UPDATE ... WHERE <primary_key> IN (...)` query.
+//!   It returns the number of records updated. A primary key is mandatory.
+//! - **column**: The name of the column in the database when it differs from the field name. It takes precedence over `#[sqlx(rename_all = "...")]` and `#[sqlx(rename = "...")]`,
+//!   which are otherwise used as is, so a field renamed for `FromRow` does not need a `column`.
 //!
 //! _Note: MySQL only supports "auto increment" in that case. It does not support returning the default value of a primary key like a UUID._
 //!
+//! A field with `#[sqlx(skip)]` is not a column: it is never part of the queries, as for `FromRow` which sets it to its `Default::default()` value.
+//! It cannot have any `tiny_orm` attribute. A field with `#[sqlx(default)]` is still a column since `FromRow` reads it when it is present.
+//!
+//! The primary key can also be an `Option<T>` (eg: `id: Option<Uuid>`). `create()` only inserts it when it is set, so the database
+//! can generate it otherwise, and returns the `T` of the record created. `get_by_id()` takes a `&T`, while `update()` and `delete()`
+//! return `sqlx::Error::RowNotFound` when it is not set.
+//!
+//! The primary key can also be a newtype (eg: `struct TodoId(Uuid)`) as long as it implements the sqlx `Type`, `Encode` and `Decode` traits,
+//! which `#[derive(sqlx::Type)]` with `#[sqlx(transparent)]` provides. The generated code never clones the primary key,
+//! except with MySQL where `create()` returns a clone of it since the inserted key cannot be read back.
+//! _Note: With MySQL, an auto increment primary key must be a primitive integer (eg: `i64`) since it comes from `last_insert_id()`._
+//! ```rust
+//! # use tiny_orm::Table;
+//! # use uuid::Uuid;
+//! # use sqlx::FromRow;
+//! #[derive(Debug, Clone, PartialEq, sqlx::Type)]
+//! #[sqlx(transparent)]
+//! struct TodoId(Uuid);
+//!
+//! #[derive(Debug, FromRow, Table, Clone)]
+//! struct Todo {
+//!     id: TodoId,
+//!     description: String,
+//! }
+//! // Todo::get_by_id(&pool, &TodoId(uuid))
+//! ```
+//!
 //! Example
 //! ```rust
 //! # use tiny_orm::Table;
@@ -191,6 +470,141 @@
 //!     }
 //! }
 //! ```
+//!
+//! ### Time
+//! The `time` types (`OffsetDateTime` and `PrimitiveDateTime`) can be used instead of the `chrono` ones, eg: for the `updated_at` column.
+//! The `time` feature enables the `time` feature of sqlx.
+//!
+//! ### Decimal
+//! The `decimal` feature enables the `rust_decimal` feature of sqlx so `Decimal` columns (eg: money amounts), including a `Decimal` primary key, can be used with Postgres and MySQL.
+//! SQLx does not support `Decimal` with SQLite, store the amount as an integer or as a `TEXT` column instead.
+//! `BigDecimal` works the same way with the `bigdecimal` feature, which enables the `bigdecimal` feature of sqlx.
+//!
+//! ### SQLite without RETURNING
+//! SQLite only supports the `RETURNING` statement since 3.35. The `sqlite-no-returning` feature generates the same queries as with MySQL for older versions:
+//! `create()` returns the `auto` primary key with `last_insert_rowid()` (or the primary key of the struct otherwise), `update()` and `delete()` return nothing,
+//! and the record created by a struct without primary key is read back by its `rowid` with a second query.
+//!
+//! ### Tracing
+//! With the `tracing` feature, every generated `async` method runs in a `tiny_orm.<method>` debug span (eg: `tiny_orm.create`) which records the `table` name.
+//! Nothing is generated without the feature.
+//!
+//! ### Generic code
+//! When a struct has the `get`, `create`, `update` and `delete` operations, the macro also implements the `TinyTable` trait.
+//! This allows writing generic code over multiple models.
+//!
+//! ```rust
+//! # use tiny_orm::{Table, TinyTable};
+//! # use sqlx::{FromRow, Row};
+//! #[derive(Debug, FromRow, Table, Clone)]
+//! #[tiny_orm(all)]
+//! struct Todo {
+//!     id: i64,
+//!     description: String,
+//! }
+//!
+//! async fn delete_all<T>(items: &[T], pool: &sqlx::SqlitePool) -> sqlx::Result<()>
+//! where
+//!     T: TinyTable<Database = sqlx::Sqlite>,
+//! {
+//!     for item in items {
+//!         item.delete(pool).await?;
+//!     }
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ### Single table inheritance
+//! The macro can also be derived on an enum whose variants have named fields, all stored in the same table.
+//! A `kind` column (or the one of `#[tiny_orm(discriminator = "shape_type")]`) holds the snake_case name of the variant of each record.
+//! Every variant must have the same primary key, and only `get_by_id()` is generated for now
+//! (along with `table_name()`, `COLUMNS` and `PRIMARY_KEY_NAME`), the `scope` and `soft_deletion` options still applying.
+//! A record whose `kind` matches no variant returns a `sqlx::Error::ColumnDecode`.
+//!
+//! ```rust
+//! # use tiny_orm::Table;
+//! #[derive(Debug, Table)]
+//! #[tiny_orm(table_name = "shape")]
+//! enum Shape {
+//!     // Read from the records where `kind = 'circle'`
+//!     Circle { id: i64, radius: f64 },
+//!     // Read from the records where `kind = 'right_square'`
+//!     RightSquare { id: i64, side: f64 },
+//! }
+//! ```
+//!
+//! ### Custom column types
+//! Any type implementing the SQLx `Type`, `Encode` and `Decode` traits can be used as a column. For example an enum deriving `sqlx::Type`, whether it is stored as a string or as an integer.
+//! The macro does not make any assumption on the type of the columns and will simply bind them as they are.
+//!
+//! When such a type is wrapped in a `SetOption`, it behaves like any other `SetOption` column. The column is skipped during `create()` and `update()` when it is `NotSet`, and a `NULL` value is decoded as `NotSet`.
+//!
+//! ```rust
+//! use tiny_orm::{Table, SetOption};
+//! # use sqlx::FromRow;
+//! #[derive(Debug, Clone, PartialEq, sqlx::Type)]
+//! #[sqlx(rename_all = "lowercase")]
+//! enum Status {
+//!     Open,
+//!     Closed,
+//! }
+//!
+//! #[derive(Debug, Clone, PartialEq, sqlx::Type)]
+//! #[repr(i32)]
+//! enum Priority {
+//!     Low = 1,
+//!     High = 2,
+//! }
+//!
+//! #[derive(Debug, FromRow, Table, Clone)]
+//! struct Ticket {
+//!     id: i64,
+//!     status: Status,
+//!     priority: Priority,
+//! }
+//!
+//! #[derive(Debug, FromRow, Table, Clone)]
+//! struct UpdateTicket {
+//!     id: i64,
+//!     status: SetOption<Status>,
+//!     priority: SetOption<Priority>,
+//! }
+//!
+//! // The `SetOption` columns get a `with_<field>()` setter. A `new(primary_key)` constructor
+//! // is also generated when all the other columns are `SetOption`.
+//! let update_ticket = UpdateTicket::new(1).with_status(Status::Closed);
+//! assert_eq!(update_ticket.status, SetOption::Set(Status::Closed));
+//! assert_eq!(update_ticket.priority, SetOption::NotSet);
+//! ```
+//!
+//! How a column is written by `create()` and `update()` depends on its type:
+//!
+//! | Type | Value | Written |
+//! |------|-------|---------|
+//! | `Option<T>` | `Some(value)` | `value` |
+//! | `Option<T>` | `None` | `NULL` |
+//! | `SetOption<T>` | `Set(value)` | `value` |
+//! | `SetOption<T>` | `NotSet` | skipped |
+//! | `SetOption<Option<T>>` | `Set(Some(value))` | `value` |
+//! | `SetOption<Option<T>>` | `Set(None)` | `NULL` |
+//! | `SetOption<Option<T>>` | `NotSet` | skipped |
+//!
+//! A skipped column keeps its database default with `create()` and is left unchanged by `update()`.
+//! So a plain `Option<T>` cannot leave a column unchanged, use a `SetOption<Option<T>>` when `None` must be told apart from "not set".
+//! A `NULL` read back into a `SetOption<Option<T>>` is decoded as `NotSet`.
+//! Only a top-level `SetOption` is skipped: an `Option<SetOption<T>>` is always bound like an `Option<T>`, so `Some(NotSet)` writes a `NULL` as well.
+//!
+//! When every column of an `update()` is a `NotSet` `SetOption`, no `UPDATE` query is sent since there is nothing to set.
+//! The method returns `Ok(())`, or fetches the current record when it returns the `return_object`.
 
 pub use tiny_orm_macros::*;
 pub use tiny_orm_model::*;
+
+#[doc(hidden)]
+pub use futures_util;
+#[cfg(feature = "tracing")]
+#[doc(hidden)]
+pub use tracing;
+#[cfg(feature = "uuid")]
+#[doc(hidden)]
+pub use uuid;
@@ -1,9 +1,11 @@
+use futures_util::{StreamExt, TryStreamExt};
 use sqlx::{
     prelude::FromRow,
     types::chrono::{DateTime, Utc},
     SqlitePool,
 };
-use tiny_orm::Table;
+use tiny_orm::{Order, SetOption, Table, TinyTable};
+use uuid::Uuid;
 
 #[derive(Debug, PartialEq, Table, FromRow)]
 #[tiny_orm(exclude = "create", add = "update")]
@@ -12,6 +14,7 @@ struct Todo {
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
     description: String,
+    #[tiny_orm(bulk_updatable, filterable)]
     done: bool,
 }
 impl Todo {
@@ -39,9 +42,386 @@ impl NewTodo {
     }
 }
 
+#[cfg(not(feature = "sqlite-no-returning"))]
+#[derive(Debug, PartialEq, Table)]
+#[tiny_orm(table_name = "todo", return_object = "Todo")]
+struct NewBorrowedTodo<'a> {
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    description: &'a str,
+}
+
+#[cfg(not(feature = "sqlite-no-returning"))]
+#[derive(Debug, PartialEq, Table)]
+#[tiny_orm(table_name = "todo", return_object = "Todo")]
+struct UpdateBorrowedTodo<'a> {
+    id: &'a i32,
+    description: &'a str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, sqlx::Type)]
+#[sqlx(rename_all = "lowercase")]
+enum TaskStatus {
+    Open,
+    Closed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, sqlx::Type)]
+#[repr(i32)]
+enum TaskPriority {
+    Low = 1,
+    High = 2,
+}
+
+#[derive(Debug, PartialEq, Table, FromRow)]
+#[tiny_orm(all, delete_returning)]
+struct Task {
+    id: i32,
+    status: TaskStatus,
+    priority: TaskPriority,
+}
+
+#[derive(Debug, Default, Table)]
+struct UpdateTask {
+    id: i32,
+    status: SetOption<TaskStatus>,
+    priority: SetOption<TaskPriority>,
+}
+
+#[derive(Debug, PartialEq, Table, FromRow)]
+#[tiny_orm(all)]
+#[sqlx(rename_all = "camelCase")]
+struct Contact {
+    id: i32,
+    first_name: String,
+    last_name: String,
+    #[tiny_orm(unique)]
+    #[sqlx(rename = "email_address")]
+    email: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, sqlx::Type)]
+#[sqlx(transparent)]
+struct NoteId(Uuid);
+
+#[derive(Debug, PartialEq, Table, FromRow)]
+#[tiny_orm(all)]
+struct Note {
+    id: NoteId,
+    body: String,
+}
+
+#[cfg(feature = "uuid")]
+#[derive(Debug, PartialEq, Table, FromRow)]
+#[tiny_orm(all, table_name = "note")]
+struct Memo {
+    #[tiny_orm(primary_key(uuid))]
+    id: Uuid,
+    body: String,
+}
+
+#[cfg(feature = "time")]
+#[derive(Debug, PartialEq, Table, FromRow)]
+#[tiny_orm(all, soft_deletion)]
+struct Meeting {
+    id: i64,
+    title: String,
+    starts_at: sqlx::types::time::OffsetDateTime,
+    updated_at: sqlx::types::time::PrimitiveDateTime,
+    deleted_at: Option<sqlx::types::time::PrimitiveDateTime>,
+}
+
+#[derive(Debug, PartialEq, Table, FromRow)]
+#[tiny_orm(all, soft_deletion)]
+struct Subscriber {
+    id: i64,
+    #[tiny_orm(filterable)]
+    email: String,
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, PartialEq, Table)]
+enum Shape {
+    Circle { id: i64, radius: f64 },
+    Square { id: i64, side: f64 },
+}
+
+/// A reversible encoding standing for an encryption
+mod reversed {
+    pub fn encode(value: &str) -> String {
+        value.chars().rev().collect()
+    }
+    pub fn decode(value: String) -> String {
+        value.chars().rev().collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Table)]
+#[tiny_orm(
+    table_name = "subscriber",
+    only = "get, create, update",
+    return_object = "()",
+    from_row
+)]
+struct EncodedSubscriber {
+    id: i64,
+    #[tiny_orm(transform = "reversed")]
+    email: String,
+}
+
+#[derive(Debug, PartialEq, Table)]
+#[tiny_orm(all, table_name = "subscriber", soft_deletion, from_row)]
+struct MailingAddress {
+    id: i64,
+    #[tiny_orm(column = "email")]
+    address: String,
+    deleted_at: Option<DateTime<Utc>>,
+    #[sqlx(skip)]
+    label: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Table, FromRow)]
+#[tiny_orm(all)]
+struct Document {
+    id: Option<Uuid>,
+    title: String,
+}
+
+#[derive(Debug, PartialEq, Table, FromRow)]
+#[tiny_orm(all)]
+struct Event {
+    id: i32,
+    name: String,
+    #[tiny_orm(db_default)]
+    created_at: DateTime<Utc>,
+}
+
+#[cfg(not(feature = "sqlite-no-returning"))]
+#[derive(Debug, PartialEq, Table, FromRow)]
+#[tiny_orm(table_name = "event", only = "create", returning_generated)]
+struct NewEvent {
+    #[tiny_orm(primary_key(auto))]
+    id: i32,
+    name: String,
+    #[tiny_orm(db_default)]
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, PartialEq, Table, FromRow)]
+#[tiny_orm(table_name = "project", only = "create", inject = "tenant_id")]
+struct NewProject {
+    #[tiny_orm(primary_key(auto))]
+    id: i64,
+    name: String,
+}
+
+#[derive(Debug, PartialEq, Table, FromRow)]
+#[tiny_orm(all)]
+struct Person {
+    #[tiny_orm(primary_key(auto))]
+    id: i64,
+    first_name: String,
+    last_name: String,
+    #[tiny_orm(readonly)]
+    full_name: String,
+}
+
+#[derive(Debug, PartialEq, Table, FromRow)]
+#[tiny_orm(all)]
+struct Profile {
+    id: i64,
+    nickname: SetOption<Option<String>>,
+    bio: Option<SetOption<String>>,
+}
+
+#[derive(Debug, Table)]
+#[tiny_orm(table_name = "profile", only = "update")]
+struct UpdateProfile {
+    id: i64,
+    nickname: SetOption<String>,
+    bio: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Table, FromRow)]
+#[tiny_orm(all)]
+#[tiny_orm(has_many = "Book", foreign_key = "author_id")]
+struct Author {
+    id: i64,
+    name: String,
+}
+
+#[derive(Debug, PartialEq, Table, FromRow)]
+#[tiny_orm(all, table_name = "author")]
+struct CachedAuthor {
+    id: i64,
+    name: String,
+    #[sqlx(skip)]
+    display_name: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Table, FromRow)]
+#[tiny_orm(
+    table_name = "author",
+    only = "create, delete",
+    cascade_delete = "books"
+)]
+#[tiny_orm(has_many = "Book", foreign_key = "author_id")]
+struct CascadingAuthor {
+    id: i64,
+    name: String,
+}
+
+#[derive(Debug, PartialEq, Table, FromRow)]
+#[tiny_orm(table_name = "author", table_name_fn = "orm_table", only = "get")]
+struct NamedAuthor {
+    id: i64,
+    name: String,
+}
+impl NamedAuthor {
+    fn table_name(&self) -> String {
+        format!("{}'s table", self.name)
+    }
+}
+
+#[derive(Debug, PartialEq, Table, FromRow)]
+#[tiny_orm(all)]
+#[tiny_orm(belongs_to = "Author", foreign_key = "author_id")]
+#[tiny_orm(projections(BookTitle = "id, title"))]
+struct Book {
+    id: i64,
+    author_id: i64,
+    title: String,
+}
+
+#[derive(Debug, PartialEq, FromRow)]
+struct BookTitle {
+    id: i64,
+    title: String,
+}
+
+#[derive(Debug, Table)]
+#[tiny_orm(return_object = "()")]
+struct NewAuthor {
+    id: i64,
+    name: String,
+}
+
+#[derive(Debug)]
+enum AppError {
+    Database(sqlx::Error),
+}
+impl From<sqlx::Error> for AppError {
+    fn from(error: sqlx::Error) -> Self {
+        AppError::Database(error)
+    }
+}
+
+#[derive(Debug, PartialEq, Table, FromRow)]
+#[tiny_orm(all, table_name = "book", error = "AppError")]
+#[tiny_orm(belongs_to = "Author", foreign_key = "author_id")]
+struct Novel {
+    id: i64,
+    author_id: i64,
+    title: String,
+}
+
+#[derive(Debug, PartialEq, FromRow)]
+struct BookSummary {
+    id: i64,
+    title: String,
+}
+
+#[derive(Debug, PartialEq, Table)]
+#[tiny_orm(
+    table_name = "book",
+    return_object = "BookSummary",
+    returning = "id, title",
+    only = "create"
+)]
+struct NewBookSummary {
+    author_id: i64,
+    title: String,
+}
+
+// Deliberately not `Clone` to check that the generated code never clones the primary key.
+// Without `RETURNING`, `create()` returns a clone of the key it has bound.
+#[cfg(not(feature = "sqlite-no-returning"))]
+#[derive(Debug, PartialEq, sqlx::Type)]
+#[sqlx(transparent)]
+struct TagName(String);
+
+#[cfg(not(feature = "sqlite-no-returning"))]
+#[derive(Debug, PartialEq, Table, FromRow)]
+#[tiny_orm(all)]
+struct Tag {
+    #[tiny_orm(primary_key)]
+    name: TagName,
+    label: String,
+}
+
+// Deliberately not `Clone` to check that every column is bound by reference.
+#[derive(Debug, PartialEq, sqlx::Type)]
+#[sqlx(transparent)]
+struct Content(Vec<u8>);
+
+#[derive(Debug, PartialEq, sqlx::Type)]
+#[sqlx(transparent)]
+struct Caption(String);
+
+#[derive(Debug, PartialEq, Table, FromRow)]
+#[tiny_orm(all)]
+struct Attachment {
+    id: i64,
+    content: Content,
+    caption: SetOption<Caption>,
+}
+
+#[derive(Debug, PartialEq, Table, FromRow)]
+#[tiny_orm(all)]
+struct Settings {
+    id: i64,
+    #[tiny_orm(json)]
+    metadata: SetOption<serde_json::Value>,
+    #[tiny_orm(json)]
+    extra: SetOption<Option<serde_json::Value>>,
+}
+
 #[tokio::test]
 async fn test_table_name() {
     assert_eq!(Todo::table_name(), "todo");
+    assert_eq!(Todo::TABLE_NAME, "todo");
+    assert_eq!(NewTodo::TABLE_NAME, "todo");
+}
+
+#[tokio::test]
+async fn test_columns() {
+    assert_eq!(
+        Contact::columns(),
+        &["id", "firstName", "lastName", "email_address"]
+    );
+}
+
+#[tokio::test]
+async fn test_generated_sql() {
+    assert!(Todo::get_by_id_sql().contains("SELECT * FROM"));
+    assert_eq!(Todo::get_by_id_sql(), "SELECT * FROM todo WHERE id = ?");
+    assert_eq!(Todo::list_all_sql(), "SELECT * FROM todo");
+    assert_eq!(
+        Todo::update_sql(),
+        "UPDATE todo SET created_at = ?, updated_at = ?, description = ?, done = ? WHERE id = ?"
+    );
+    assert_eq!(Todo::delete_sql(), "DELETE FROM todo WHERE id = ?");
+    // Without `RETURNING`, the created record is read back by a second query
+    let returning = match cfg!(feature = "sqlite-no-returning") {
+        true => "",
+        false => " RETURNING * ",
+    };
+    assert_eq!(
+        NewTodo::create_sql(),
+        format!(
+            "INSERT INTO todo (created_at, updated_at, description) VALUES (?, ?, ?){returning}"
+        )
+    );
 }
 
 #[sqlx::test(migrations = "examples/sqlite/migrations")]
@@ -62,6 +442,71 @@ async fn test_insert_and_get_by_id(pool: SqlitePool) {
     assert_eq!(inserted_item, checked_item);
 }
 
+#[sqlx::test(migrations = "examples/sqlite/migrations")]
+async fn test_get_by_id_or_err(pool: SqlitePool) {
+    let inserted_item = NewTodo::new("My new item".to_string())
+        .create(&pool)
+        .await
+        .unwrap();
+
+    let checked_item = Todo::get_by_id_or_err(&pool, &inserted_item.id)
+        .await
+        .unwrap();
+    assert_eq!(inserted_item, checked_item);
+
+    let missing_item = Todo::get_by_id_or_err(&pool, &(inserted_item.id + 1)).await;
+    assert!(matches!(missing_item, Err(sqlx::Error::RowNotFound)));
+}
+
+#[sqlx::test(migrations = "examples/sqlite/migrations")]
+async fn test_get_by(pool: SqlitePool) {
+    for description in ["Item 1", "Item 2", "Item 1"] {
+        let _ = NewTodo::new(description.to_string())
+            .create(&pool)
+            .await
+            .unwrap();
+    }
+
+    let items = Todo::get_by(&pool, "description", "Item 1").await.unwrap();
+    assert_eq!(items.len(), 2);
+    assert!(items.iter().all(|item| item.description == "Item 1"));
+
+    let error = Todo::get_by(&pool, "description = '' OR 1 = 1 --", "Item 1")
+        .await
+        .unwrap_err();
+    assert!(matches!(error, sqlx::Error::ColumnNotFound(_)));
+    assert_eq!(
+        Todo::COLUMNS,
+        &["id", "created_at", "updated_at", "description", "done"]
+    );
+    assert_eq!(Todo::primary_key_name(), Some("id"));
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_get_by_ids_ordered(pool: SqlitePool) {
+    for id in 1..=3 {
+        Author {
+            id,
+            name: format!("Author {id}"),
+        }
+        .create(&pool)
+        .await
+        .unwrap();
+    }
+
+    let authors = Author::get_by_ids_ordered(&pool, &[3, 42, 1, 3])
+        .await
+        .unwrap();
+    assert_eq!(
+        authors.iter().map(|author| author.id).collect::<Vec<i64>>(),
+        vec![3, 1]
+    );
+    assert!(Author::get_by_ids_ordered(&pool, &[])
+        .await
+        .unwrap()
+        .is_empty());
+}
+
 #[sqlx::test(migrations = "examples/sqlite/migrations")]
 async fn test_update(pool: SqlitePool) {
     let description = "My new item".to_string();
@@ -114,6 +559,121 @@ async fn test_list_all(pool: SqlitePool) {
     );
 }
 
+#[sqlx::test(migrations = "examples/sqlite/migrations")]
+async fn test_list_page(pool: SqlitePool) {
+    for i in 1..=3 {
+        let _ = NewTodo::new(format!("Item {i}"))
+            .create(&pool)
+            .await
+            .unwrap();
+    }
+
+    let first_page = Todo::list_page(&pool, 2, 0).await.unwrap();
+    assert_eq!(first_page.total, 3);
+    assert_eq!(first_page.items.len(), 2);
+    assert!(first_page.has_next());
+    assert!(!first_page.has_previous());
+
+    let last_page = Todo::list_page(&pool, 2, 2).await.unwrap();
+    assert_eq!(last_page.total, 3);
+    assert_eq!(last_page.items.len(), 1);
+    assert!(!last_page.has_next());
+    assert!(last_page.has_previous());
+}
+
+#[sqlx::test(migrations = "examples/sqlite/migrations")]
+async fn test_list_after(pool: SqlitePool) {
+    for i in 1..=5 {
+        let _ = NewTodo::new(format!("Item {i}"))
+            .create(&pool)
+            .await
+            .unwrap();
+    }
+
+    let first_page = Todo::list_after(&pool, &0, 2).await.unwrap();
+    assert_eq!(
+        first_page.iter().map(|todo| todo.id).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+
+    let second_page = Todo::list_after(&pool, &first_page[1].id, 2).await.unwrap();
+    assert_eq!(
+        second_page.iter().map(|todo| todo.id).collect::<Vec<_>>(),
+        vec![3, 4]
+    );
+
+    let last_page = Todo::list_after(&pool, &second_page[1].id, 2)
+        .await
+        .unwrap();
+    assert_eq!(
+        last_page.iter().map(|todo| todo.id).collect::<Vec<_>>(),
+        vec![5]
+    );
+    assert!(Todo::list_after(&pool, &5, 2).await.unwrap().is_empty());
+}
+
+#[cfg(not(feature = "sqlite-no-returning"))]
+#[sqlx::test(migrations = "examples/sqlite/migrations")]
+async fn test_struct_with_a_lifetime(pool: SqlitePool) {
+    let description = String::from("Borrowed item");
+    let inserted_item = NewBorrowedTodo {
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        description: &description,
+    }
+    .create(&pool)
+    .await
+    .unwrap();
+    assert_eq!(inserted_item.description, description);
+
+    let description = String::from("Updated borrowed item");
+    let updated_item = UpdateBorrowedTodo {
+        id: &inserted_item.id,
+        description: &description,
+    }
+    .update(&pool)
+    .await
+    .unwrap();
+    assert_eq!(updated_item.id, inserted_item.id);
+    assert_eq!(updated_item.description, description);
+}
+
+#[sqlx::test(migrations = "examples/sqlite/migrations")]
+async fn test_bulk_update_by_ids(pool: SqlitePool) {
+    let mut ids = Vec::new();
+    for description in ["Item 1", "Item 2", "Item 3"] {
+        let item = NewTodo::new(description.to_string())
+            .create(&pool)
+            .await
+            .unwrap();
+        ids.push(item.id);
+    }
+
+    let rows_affected = Todo::update_done_by_ids(&pool, &true, &ids[..2])
+        .await
+        .unwrap();
+    assert_eq!(rows_affected, 2);
+
+    let mut all_items: Vec<(String, bool)> = Todo::list_all(&pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|x| (x.description, x.done))
+        .collect();
+    all_items.sort();
+    assert_eq!(
+        all_items,
+        vec![
+            ("Item 1".to_string(), true),
+            ("Item 2".to_string(), true),
+            ("Item 3".to_string(), false)
+        ]
+    );
+
+    let rows_affected = Todo::update_done_by_ids(&pool, &true, &[]).await.unwrap();
+    assert_eq!(rows_affected, 0);
+}
+
 #[sqlx::test(migrations = "examples/sqlite/migrations")]
 async fn test_delete(pool: SqlitePool) {
     let item = NewTodo::new("Item 1".to_string())
@@ -129,6 +689,20 @@ async fn test_delete(pool: SqlitePool) {
     assert!(retrieved_item.is_none());
 }
 
+#[sqlx::test(migrations = "examples/sqlite/migrations")]
+async fn test_delete_all(pool: SqlitePool) {
+    for i in 1..=3 {
+        let _ = NewTodo::new(format!("Item {i}"))
+            .create(&pool)
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(Todo::delete_all(&pool).await.unwrap(), 3);
+    assert!(Todo::list_all(&pool).await.unwrap().is_empty());
+    assert_eq!(Todo::delete_all(&pool).await.unwrap(), 0);
+}
+
 #[sqlx::test(migrations = "examples/sqlite/migrations")]
 async fn test_insert_get_with_a_transaction(pool: SqlitePool) {
     let mut tx = pool.begin().await.unwrap();
@@ -152,3 +726,1626 @@ async fn test_insert_get_with_a_transaction(pool: SqlitePool) {
         .unwrap();
     assert_eq!(checked_item.description(), "New description");
 }
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_enum_columns_round_trip(pool: SqlitePool) {
+    let task = Task {
+        id: 1,
+        status: TaskStatus::Open,
+        priority: TaskPriority::Low,
+    };
+    let task_id = task.create(&pool).await.unwrap();
+    assert_eq!(task_id, 1);
+
+    let checked_task = Task::get_by_id(&pool, &task_id).await.unwrap().unwrap();
+    assert_eq!(checked_task, task);
+
+    let stored: (String, i32) = sqlx::query_as("SELECT status, priority FROM task WHERE id = 1")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, ("open".to_string(), 1));
+}
+
+#[cfg(not(feature = "sqlite-no-returning"))]
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_enum_columns_with_set_option(pool: SqlitePool) {
+    Task {
+        id: 1,
+        status: TaskStatus::Open,
+        priority: TaskPriority::Low,
+    }
+    .create(&pool)
+    .await
+    .unwrap();
+
+    let updated_task = UpdateTask {
+        id: 1,
+        status: TaskStatus::Closed.into(),
+        ..Default::default()
+    }
+    .update(&pool)
+    .await
+    .unwrap();
+    assert_eq!(updated_task.status, TaskStatus::Closed);
+    assert_eq!(updated_task.priority, TaskPriority::Low);
+
+    let updated_task = UpdateTask {
+        id: 1,
+        priority: TaskPriority::High.into(),
+        ..Default::default()
+    }
+    .update(&pool)
+    .await
+    .unwrap();
+    assert_eq!(updated_task.status, TaskStatus::Closed);
+    assert_eq!(updated_task.priority, TaskPriority::High);
+}
+
+#[sqlx::test(migrations = "examples/sqlite/migrations")]
+async fn test_bulk_update_with_a_transaction(pool: SqlitePool) {
+    let mut tx = pool.begin().await.unwrap();
+    let item = NewTodo::new("Item 1".to_string())
+        .create(&mut *tx)
+        .await
+        .unwrap();
+    let rows_affected = Todo::update_done_by_ids(&mut *tx, &true, &[item.id])
+        .await
+        .unwrap();
+    assert_eq!(rows_affected, 1);
+    assert!(
+        Todo::get_by_id_or_err(&mut *tx, &item.id)
+            .await
+            .unwrap()
+            .done
+    );
+    tx.rollback().await.unwrap();
+
+    assert!(Todo::list_all(&pool).await.unwrap().is_empty());
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_all_methods_with_a_transaction(pool: SqlitePool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let mut contact = Contact {
+        id: 1,
+        first_name: "John".to_string(),
+        last_name: "Doe".to_string(),
+        email: "john@doe.com".to_string(),
+    };
+    contact.create(&mut *tx).await.unwrap();
+    let other_contact = Contact {
+        id: 2,
+        first_name: "Jane".to_string(),
+        last_name: "Doe".to_string(),
+        email: "jane@doe.com".to_string(),
+    };
+    assert!(other_contact.create_if_not_exists(&mut *tx).await.unwrap());
+
+    contact.last_name = "Smith".to_string();
+    contact.update(&mut *tx).await.unwrap();
+
+    assert_eq!(
+        Contact::get_by_id(&mut *tx, &1).await.unwrap().unwrap(),
+        contact
+    );
+    assert_eq!(
+        Contact::get_by_id_or_err(&mut *tx, &2).await.unwrap(),
+        other_contact
+    );
+    let contacts = Contact::get_by(&mut *tx, "lastName", "Smith")
+        .await
+        .unwrap();
+    assert_eq!(contacts, [contact]);
+    let contacts = Contact::get_by_ids_ordered(&mut *tx, &[2, 1])
+        .await
+        .unwrap();
+    assert_eq!(contacts.iter().map(|c| c.id).collect::<Vec<_>>(), [2, 1]);
+    let contact = Contact::find_one_by_email(&mut *tx, &"john@doe.com".to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(contact.last_name, "Smith");
+    assert_eq!(Contact::list_all(&mut *tx).await.unwrap().len(), 2);
+    assert_eq!(Contact::list_page(&mut *tx, 1, 0).await.unwrap().total, 2);
+
+    let author = Author {
+        id: 1,
+        name: "Jane".to_string(),
+    };
+    author.create(&mut *tx).await.unwrap();
+    let book = Book {
+        id: 1,
+        author_id: 1,
+        title: "A book".to_string(),
+    };
+    book.create(&mut *tx).await.unwrap();
+    assert_eq!(book.author(&mut *tx).await.unwrap().unwrap(), author);
+    assert_eq!(author.books(&mut *tx).await.unwrap(), [book]);
+    let book = Book::get_by_id_or_err(&mut *tx, &1).await.unwrap();
+    book.delete(&mut *tx).await.unwrap();
+
+    contact.delete(&mut *tx).await.unwrap();
+    assert_eq!(Contact::delete_all(&mut *tx).await.unwrap(), 1);
+    tx.rollback().await.unwrap();
+
+    assert!(Contact::list_all(&pool).await.unwrap().is_empty());
+    assert!(Author::list_all(&pool).await.unwrap().is_empty());
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_all_methods_with_a_connection(pool: SqlitePool) {
+    // A single connection checked out of the pool, neither the pool nor a transaction
+    let mut conn = pool.acquire().await.unwrap();
+
+    let mut contact = Contact {
+        id: 1,
+        first_name: "John".to_string(),
+        last_name: "Doe".to_string(),
+        email: "john@doe.com".to_string(),
+    };
+    contact.create(&mut *conn).await.unwrap();
+    let other_contact = Contact {
+        id: 2,
+        first_name: "Jane".to_string(),
+        last_name: "Doe".to_string(),
+        email: "jane@doe.com".to_string(),
+    };
+    assert!(other_contact
+        .create_if_not_exists(&mut *conn)
+        .await
+        .unwrap());
+
+    contact.last_name = "Smith".to_string();
+    contact.update(&mut *conn).await.unwrap();
+
+    assert!(Contact::table_exists(&mut *conn).await.unwrap());
+    assert_eq!(
+        Contact::get_by_id(&mut *conn, &1).await.unwrap().unwrap(),
+        contact
+    );
+    assert_eq!(
+        Contact::get_by_id_or_err(&mut *conn, &2).await.unwrap(),
+        other_contact
+    );
+    let contacts = Contact::get_by(&mut *conn, "lastName", "Smith")
+        .await
+        .unwrap();
+    assert_eq!(contacts, [contact]);
+    let contacts = Contact::get_by_ids_ordered(&mut *conn, &[2, 1])
+        .await
+        .unwrap();
+    assert_eq!(contacts.iter().map(|c| c.id).collect::<Vec<_>>(), [2, 1]);
+    assert_eq!(
+        Contact::existing_ids(&mut *conn, &[1, 3]).await.unwrap(),
+        [1]
+    );
+    let contact = Contact::find_one_by_email(&mut *conn, &"john@doe.com".to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(contact.last_name, "Smith");
+    assert_eq!(Contact::list_all(&mut *conn).await.unwrap().len(), 2);
+    assert_eq!(Contact::list_page(&mut *conn, 1, 0).await.unwrap().total, 2);
+    assert_eq!(
+        Contact::list_after(&mut *conn, &1, 10).await.unwrap(),
+        [other_contact]
+    );
+    let contacts = Contact::list_all_ordered(&mut *conn, &[("id", Order::Desc)])
+        .await
+        .unwrap();
+    assert_eq!(contacts.iter().map(|c| c.id).collect::<Vec<_>>(), [2, 1]);
+
+    let author = Author {
+        id: 1,
+        name: "Jane".to_string(),
+    };
+    author.create(&mut *conn).await.unwrap();
+    let book = Book {
+        id: 1,
+        author_id: 1,
+        title: "A book".to_string(),
+    };
+    book.create(&mut *conn).await.unwrap();
+    assert_eq!(book.author(&mut *conn).await.unwrap().unwrap(), author);
+    assert_eq!(author.books(&mut *conn).await.unwrap(), [book]);
+    let book = Book::get_by_id_or_err(&mut *conn, &1).await.unwrap();
+    book.delete(&mut *conn).await.unwrap();
+
+    let subscribers: Vec<Subscriber> = (1..=2)
+        .map(|id| Subscriber {
+            id,
+            email: format!("user{id}@example.com"),
+            deleted_at: None,
+        })
+        .collect();
+    assert_eq!(
+        Subscriber::upsert_many(&mut *conn, &subscribers)
+            .await
+            .unwrap(),
+        2
+    );
+    Subscriber::get_by_id_or_err(&mut *conn, &1)
+        .await
+        .unwrap()
+        .delete(&mut *conn)
+        .await
+        .unwrap();
+    assert!(Subscriber::get_by_id_with_deleted(&mut *conn, &1)
+        .await
+        .unwrap()
+        .unwrap()
+        .is_deleted());
+    assert_eq!(
+        Subscriber::list_all_with_deleted(&mut *conn)
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+    let filter = SubscriberFilter {
+        email: Some("user2@example.com".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        Subscriber::list_matching(&mut *conn, &filter)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+
+    contact.delete(&mut *conn).await.unwrap();
+    assert_eq!(Contact::delete_all(&mut *conn).await.unwrap(), 1);
+    drop(conn);
+
+    assert!(Contact::list_all(&pool).await.unwrap().is_empty());
+    assert!(Book::list_all(&pool).await.unwrap().is_empty());
+    assert_eq!(Subscriber::list_all(&pool).await.unwrap().len(), 1);
+}
+
+#[sqlx::test(migrations = "examples/sqlite/migrations")]
+async fn test_todo_methods_with_a_connection(pool: SqlitePool) {
+    let mut conn = pool.acquire().await.unwrap();
+
+    let mut item = NewTodo::new("Item".to_string())
+        .create(&mut *conn)
+        .await
+        .unwrap();
+    item.change_description("Updated item".to_string());
+    item.update(&mut *conn).await.unwrap();
+    item.touch(&mut *conn).await.unwrap();
+    assert_eq!(
+        Todo::update_done_by_ids(&mut *conn, &true, &[item.id])
+            .await
+            .unwrap(),
+        1
+    );
+    drop(conn);
+
+    let checked_item = Todo::get_by_id(&pool, &item.id).await.unwrap().unwrap();
+    assert!(checked_item.done);
+    assert_eq!(checked_item.description(), "Updated item");
+}
+
+#[cfg(not(feature = "sqlite-no-returning"))]
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_update_without_any_set_option_set(pool: SqlitePool) {
+    Task {
+        id: 1,
+        status: TaskStatus::Open,
+        priority: TaskPriority::Low,
+    }
+    .create(&pool)
+    .await
+    .unwrap();
+
+    let task = UpdateTask::new(1).update(&pool).await.unwrap();
+    assert_eq!(task.status, TaskStatus::Open);
+    assert_eq!(task.priority, TaskPriority::Low);
+
+    let missing_task = UpdateTask::new(2).update(&pool).await;
+    assert!(matches!(missing_task, Err(sqlx::Error::RowNotFound)));
+}
+
+#[test]
+fn test_set_option_builder() {
+    let update_task = UpdateTask::new(1).with_status(TaskStatus::Closed);
+    assert_eq!(update_task.id, 1);
+    assert_eq!(update_task.status, SetOption::Set(TaskStatus::Closed));
+    assert_eq!(update_task.priority, SetOption::NotSet);
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_create_if_not_exists(pool: SqlitePool) {
+    let task = Task {
+        id: 1,
+        status: TaskStatus::Open,
+        priority: TaskPriority::Low,
+    };
+    assert!(task.create_if_not_exists(&pool).await.unwrap());
+
+    let same_task = Task {
+        id: 1,
+        status: TaskStatus::Closed,
+        priority: TaskPriority::High,
+    };
+    assert!(!same_task.create_if_not_exists(&pool).await.unwrap());
+
+    let checked_task = Task::get_by_id(&pool, &1).await.unwrap().unwrap();
+    assert_eq!(checked_task, task);
+}
+
+#[cfg(not(feature = "sqlite-no-returning"))]
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_delete_returning(pool: SqlitePool) {
+    let task = Task {
+        id: 1,
+        status: TaskStatus::Open,
+        priority: TaskPriority::Low,
+    };
+    task.create(&pool).await.unwrap();
+
+    let deleted_task = task.delete(&pool).await.unwrap();
+    assert_eq!(deleted_task, Some(task));
+    assert!(Task::get_by_id(&pool, &1).await.unwrap().is_none());
+
+    let deleted_task = deleted_task.unwrap().delete(&pool).await.unwrap();
+    assert_eq!(deleted_task, None);
+}
+
+async fn create_then_get<T>(item: &T, pool: &SqlitePool) -> sqlx::Result<Option<T::ReturnObject>>
+where
+    T: TinyTable<Database = sqlx::Sqlite>,
+    T::Created: Into<T::PrimaryKey>,
+{
+    let id = item.create(pool).await?.into();
+    T::get_by_id(pool, &id).await
+}
+
+async fn delete_if_exists<T>(item: &T, pool: &SqlitePool) -> sqlx::Result<()>
+where
+    T: TinyTable<Database = sqlx::Sqlite>,
+{
+    item.delete(pool).await
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_generic_function_over_tiny_table(pool: SqlitePool) {
+    assert_eq!(<Task as TinyTable>::table_name(), "task");
+
+    let task = Task {
+        id: 1,
+        status: TaskStatus::Open,
+        priority: TaskPriority::High,
+    };
+    let checked_task = create_then_get(&task, &pool).await.unwrap();
+    assert_eq!(checked_task, Some(task));
+
+    let task = checked_task.unwrap();
+    delete_if_exists(&task, &pool).await.unwrap();
+    assert!(Task::get_by_id(&pool, &task.id).await.unwrap().is_none());
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_rename_all_columns(pool: SqlitePool) {
+    let mut contact = Contact {
+        id: 1,
+        first_name: "John".to_string(),
+        last_name: "Doe".to_string(),
+        email: "john@doe.com".to_string(),
+    };
+    contact.create(&pool).await.unwrap();
+
+    contact.last_name = "Smith".to_string();
+    contact.update(&pool).await.unwrap();
+
+    let checked_contact = Contact::get_by_id(&pool, &1).await.unwrap().unwrap();
+    assert_eq!(checked_contact, contact);
+
+    let stored: (String, String) =
+        sqlx::query_as("SELECT lastName, email_address FROM contact WHERE id = 1")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(stored, ("Smith".to_string(), "john@doe.com".to_string()));
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_find_one_by_unique_column(pool: SqlitePool) {
+    let contact = Contact {
+        id: 1,
+        first_name: "John".to_string(),
+        last_name: "Doe".to_string(),
+        email: "john@doe.com".to_string(),
+    };
+    contact.create(&pool).await.unwrap();
+
+    let checked_contact = Contact::find_one_by_email(&pool, &"john@doe.com".to_string())
+        .await
+        .unwrap();
+    assert_eq!(checked_contact, Some(contact));
+
+    let checked_contact = Contact::find_one_by_email(&pool, &"jane@doe.com".to_string())
+        .await
+        .unwrap();
+    assert!(checked_contact.is_none());
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_newtype_primary_key(pool: SqlitePool) {
+    let mut note = Note {
+        id: NoteId(Uuid::new_v4()),
+        body: "My note".to_string(),
+    };
+    let note_id: NoteId = note.create(&pool).await.unwrap();
+    assert_eq!(note_id, note.id);
+
+    note.body = "My updated note".to_string();
+    note.update(&pool).await.unwrap();
+
+    let checked_note = Note::get_by_id(&pool, &note_id).await.unwrap().unwrap();
+    assert_eq!(checked_note, note);
+
+    note.delete(&pool).await.unwrap();
+    assert!(Note::get_by_id(&pool, &note_id).await.unwrap().is_none());
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_get_by_id_with_owned_or_borrowed_key(pool: SqlitePool) {
+    let note = Note {
+        id: NoteId(Uuid::new_v4()),
+        body: "My note".to_string(),
+    };
+    let note_id = note.create(&pool).await.unwrap();
+
+    let borrowed_note = Note::get_by_id(&pool, &note_id).await.unwrap();
+    let owned_note = Note::get_by_id(&pool, note_id).await.unwrap();
+    assert_eq!(borrowed_note, Some(note));
+    assert_eq!(owned_note, borrowed_note);
+
+    let owned_note = Note::get_by_id_or_err(&pool, note_id).await.unwrap();
+    assert_eq!(Some(owned_note), borrowed_note);
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_db_default_column(pool: SqlitePool) {
+    let event = Event {
+        id: 1,
+        name: "signup".to_string(),
+        created_at: DateTime::UNIX_EPOCH,
+    };
+    event.create(&pool).await.unwrap();
+
+    let checked_event = Event::get_by_id(&pool, &1).await.unwrap().unwrap();
+    assert_eq!(checked_event.name, "signup".to_string());
+    assert!(checked_event.created_at > DateTime::UNIX_EPOCH);
+}
+
+#[cfg(not(feature = "sqlite-no-returning"))]
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_create_returning_generated_columns(pool: SqlitePool) {
+    let new_event = NewEvent {
+        id: 0,
+        name: "signup".to_string(),
+        created_at: DateTime::UNIX_EPOCH,
+    };
+    let (id, created_at) = new_event.create(&pool).await.unwrap();
+
+    let checked_event = Event::get_by_id(&pool, &id).await.unwrap().unwrap();
+    assert_eq!(checked_event.created_at, created_at);
+    assert!(created_at > DateTime::UNIX_EPOCH);
+}
+
+#[cfg(not(feature = "sqlite-no-returning"))]
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_upsert_with_outcome(pool: SqlitePool) {
+    let mut tag = Tag {
+        name: TagName("rust".to_string()),
+        label: "Rust".to_string(),
+    };
+    let outcome = tag.upsert_with_outcome(&pool).await.unwrap();
+    assert_eq!(
+        outcome,
+        tiny_orm::UpsertOutcome::Inserted(Tag {
+            name: TagName("rust".to_string()),
+            label: "Rust".to_string(),
+        })
+    );
+
+    tag.label = "Rust lang".to_string();
+    let outcome = tag.upsert_with_outcome(&pool).await.unwrap();
+    assert_eq!(outcome, tiny_orm::UpsertOutcome::Updated(tag));
+}
+
+#[cfg(not(feature = "sqlite-no-returning"))]
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_primary_key_without_clone(pool: SqlitePool) {
+    let mut tag = Tag {
+        name: TagName("rust".to_string()),
+        label: "Rust".to_string(),
+    };
+    let tag_name: TagName = tag.create(&pool).await.unwrap();
+    assert_eq!(tag_name, tag.name);
+
+    tag.label = "Rust lang".to_string();
+    tag.update(&pool).await.unwrap();
+
+    let checked_tag = Tag::get_by_id(&pool, &tag_name).await.unwrap().unwrap();
+    assert_eq!(checked_tag, tag);
+
+    tag.delete(&pool).await.unwrap();
+    assert!(Tag::get_by_id(&pool, &tag_name).await.unwrap().is_none());
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_readonly_column(pool: SqlitePool) {
+    let mut person = Person {
+        id: 0,
+        first_name: "John".to_string(),
+        last_name: "Doe".to_string(),
+        full_name: "ignored".to_string(),
+    };
+    let id = person.create(&pool).await.unwrap();
+
+    let checked_person = Person::get_by_id(&pool, &id).await.unwrap().unwrap();
+    assert_eq!(checked_person.full_name, "John Doe".to_string());
+
+    person.id = id;
+    person.last_name = "Smith".to_string();
+    person.update(&pool).await.unwrap();
+
+    let checked_person = Person::get_by_id(&pool, &id).await.unwrap().unwrap();
+    assert_eq!(checked_person.full_name, "John Smith".to_string());
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_create_returning_id(pool: SqlitePool) {
+    let id = Person {
+        id: 0,
+        first_name: "Jane".to_string(),
+        last_name: "Doe".to_string(),
+        full_name: "ignored".to_string(),
+    }
+    .create_returning_id(&pool)
+    .await
+    .unwrap();
+
+    let checked_person = Person::get_by_id_or_err(&pool, &id).await.unwrap();
+    assert_eq!(checked_person.full_name, "Jane Doe".to_string());
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_create_many(pool: SqlitePool) {
+    assert!(Person::create_many(&pool, &[]).await.unwrap().is_empty());
+
+    let persons: Vec<Person> = ["Jane", "John", "Jim"]
+        .into_iter()
+        .map(|first_name| Person {
+            id: 0,
+            first_name: first_name.to_string(),
+            last_name: "Doe".to_string(),
+            full_name: "ignored".to_string(),
+        })
+        .collect();
+    let ids = Person::create_many(&pool, &persons).await.unwrap();
+    assert_eq!(ids.len(), 3);
+
+    for (id, person) in ids.iter().zip(&persons) {
+        let checked_person = Person::get_by_id_or_err(&pool, id).await.unwrap();
+        assert_eq!(checked_person.first_name, person.first_name);
+        assert_eq!(
+            checked_person.full_name,
+            format!("{} Doe", person.first_name)
+        );
+    }
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_create_for(pool: SqlitePool) {
+    let project = NewProject {
+        id: 0,
+        name: "Roadmap".to_string(),
+    };
+    let first_id = project.create_for(&7_i64, &pool).await.unwrap();
+    let second_id = project.create_for(&8_i64, &pool).await.unwrap();
+    assert_ne!(first_id, second_id);
+
+    let tenant_ids: Vec<i64> = sqlx::query_scalar("SELECT tenant_id FROM project ORDER BY id")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(tenant_ids, vec![7, 8]);
+}
+
+#[cfg(feature = "uuid")]
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_uuid_primary_key_generated_client_side(pool: SqlitePool) {
+    let memo = Memo {
+        id: Uuid::nil(),
+        body: "Hello".to_string(),
+    };
+    let id = memo.create(&pool).await.unwrap();
+    assert!(!id.is_nil());
+    let checked_memo = Memo::get_by_id(&pool, &id).await.unwrap().unwrap();
+    assert_eq!(checked_memo.body, "Hello".to_string());
+
+    // A key which is already set is kept
+    let id = Uuid::new_v4();
+    let memo = Memo {
+        id,
+        body: "World".to_string(),
+    };
+    assert_eq!(memo.create(&pool).await.unwrap(), id);
+}
+
+#[cfg(feature = "time")]
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_time_columns(pool: SqlitePool) {
+    use sqlx::{
+        types::time::{OffsetDateTime, PrimitiveDateTime},
+        Row,
+    };
+
+    let starts_at = OffsetDateTime::from_unix_timestamp(1739352600).unwrap();
+    let meeting = Meeting {
+        id: 1,
+        title: "Standup".to_string(),
+        starts_at,
+        updated_at: PrimitiveDateTime::new(starts_at.date(), starts_at.time()),
+        deleted_at: None,
+    };
+    meeting.create(&pool).await.unwrap();
+    let checked_meeting = Meeting::get_by_id(&pool, &1).await.unwrap().unwrap();
+    assert_eq!(checked_meeting, meeting);
+
+    let starts_at = OffsetDateTime::from_unix_timestamp(1739439000).unwrap();
+    let meeting = Meeting {
+        starts_at,
+        updated_at: PrimitiveDateTime::new(starts_at.date(), starts_at.time()),
+        ..meeting
+    };
+    meeting.update(&pool).await.unwrap();
+    let checked_meeting = Meeting::get_by_id(&pool, &1).await.unwrap().unwrap();
+    assert_eq!(checked_meeting.starts_at, starts_at);
+    assert_eq!(checked_meeting.title, meeting.title);
+
+    // The soft deletion also sets the `updated_at` column
+    meeting.delete(&pool).await.unwrap();
+    assert!(Meeting::get_by_id(&pool, &1).await.unwrap().is_none());
+    let row = sqlx::query("SELECT updated_at, deleted_at FROM meeting WHERE id = 1")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let updated_at: PrimitiveDateTime = row.get("updated_at");
+    let deleted_at: Option<PrimitiveDateTime> = row.get("deleted_at");
+    assert!(updated_at > meeting.updated_at);
+    assert_eq!(deleted_at, Some(updated_at));
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_table_exists(pool: SqlitePool) {
+    assert!(Author::table_exists(&pool).await.unwrap());
+    sqlx::query("DROP TABLE note").execute(&pool).await.unwrap();
+    assert!(!Note::table_exists(&pool).await.unwrap());
+}
+
+#[cfg(not(feature = "sqlite-no-returning"))]
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_save(pool: SqlitePool) {
+    let mut person = Person {
+        id: 0,
+        first_name: "John".to_string(),
+        last_name: "Doe".to_string(),
+        full_name: "ignored".to_string(),
+    };
+    assert!(person.save(&pool).await.unwrap());
+    assert!(person.id > 0);
+
+    person.last_name = "Smith".to_string();
+    assert!(!person.save(&pool).await.unwrap());
+
+    let checked_person = Person::get_by_id(&pool, &person.id).await.unwrap().unwrap();
+    assert_eq!(checked_person.full_name, "John Smith".to_string());
+    assert_eq!(Person::list_all(&pool).await.unwrap().len(), 1);
+
+    let mut document = Document {
+        id: None,
+        title: "Draft".to_string(),
+    };
+    assert!(document.save(&pool).await.unwrap());
+    let id = document.id.unwrap();
+
+    document.title = "Final".to_string();
+    assert!(!document.save(&pool).await.unwrap());
+    let checked_document = Document::get_by_id(&pool, &id).await.unwrap().unwrap();
+    assert_eq!(checked_document.title, "Final".to_string());
+}
+
+#[cfg(not(feature = "sqlite-no-returning"))]
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_optional_primary_key_generated_by_the_database(pool: SqlitePool) {
+    let mut document = Document {
+        id: None,
+        title: "Draft".to_string(),
+    };
+    let id: Uuid = document.create(&pool).await.unwrap();
+
+    document.id = Some(id);
+    document.title = "Final".to_string();
+    document.update(&pool).await.unwrap();
+
+    let checked_document = Document::get_by_id(&pool, &id).await.unwrap().unwrap();
+    assert_eq!(checked_document, document);
+
+    document.delete(&pool).await.unwrap();
+    assert!(Document::get_by_id(&pool, &id).await.unwrap().is_none());
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_optional_primary_key_supplied_by_the_client(pool: SqlitePool) {
+    let id = Uuid::new_v4();
+    let document = Document {
+        id: Some(id),
+        title: "Draft".to_string(),
+    };
+    assert_eq!(document.create(&pool).await.unwrap(), id);
+
+    let checked_document = Document::get_by_id(&pool, &id).await.unwrap().unwrap();
+    assert_eq!(checked_document, document);
+
+    let unsaved_document = Document {
+        id: None,
+        title: "Unsaved".to_string(),
+    };
+    let error = unsaved_document.update(&pool).await.unwrap_err();
+    assert!(matches!(error, sqlx::Error::RowNotFound));
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_set_option_of_option_column(pool: SqlitePool) {
+    // `NotSet` skips the column so the database default applies
+    let mut profile = Profile {
+        id: 1,
+        nickname: SetOption::NotSet,
+        bio: None,
+    };
+    profile.create(&pool).await.unwrap();
+    let checked_profile = Profile::get_by_id(&pool, &1).await.unwrap().unwrap();
+    assert_eq!(
+        checked_profile.nickname,
+        SetOption::Set(Some("anonymous".to_string()))
+    );
+
+    // `Set(None)` explicitly writes a NULL, which is decoded as `NotSet`
+    profile.nickname = SetOption::Set(None);
+    profile.update(&pool).await.unwrap();
+    let checked_profile = Profile::get_by_id(&pool, &1).await.unwrap().unwrap();
+    assert_eq!(checked_profile.nickname, SetOption::NotSet);
+
+    profile.nickname = SetOption::Set(Some("johnny".to_string()));
+    profile.update(&pool).await.unwrap();
+    profile.nickname = SetOption::NotSet;
+    profile.update(&pool).await.unwrap();
+    let checked_profile = Profile::get_by_id(&pool, &1).await.unwrap().unwrap();
+    assert_eq!(
+        checked_profile.nickname,
+        SetOption::Set(Some("johnny".to_string()))
+    );
+
+    let profile = Profile {
+        id: 2,
+        nickname: SetOption::Set(None),
+        bio: None,
+    };
+    profile.create(&pool).await.unwrap();
+    let checked_profile = Profile::get_by_id(&pool, &2).await.unwrap().unwrap();
+    assert_eq!(checked_profile.nickname, SetOption::NotSet);
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_update_optional_columns(pool: SqlitePool) {
+    Profile {
+        id: 1,
+        nickname: SetOption::Set(Some("johnny".to_string())),
+        bio: Some(SetOption::Set("Hello".to_string())),
+    }
+    .create(&pool)
+    .await
+    .unwrap();
+
+    // `NotSet` leaves the column unchanged while a plain `None` writes a NULL
+    UpdateProfile {
+        id: 1,
+        nickname: SetOption::NotSet,
+        bio: None,
+    }
+    .update(&pool)
+    .await
+    .unwrap();
+    let checked_profile = Profile::get_by_id(&pool, &1).await.unwrap().unwrap();
+    assert_eq!(
+        checked_profile.nickname,
+        SetOption::Set(Some("johnny".to_string()))
+    );
+    assert_eq!(checked_profile.bio, None);
+
+    UpdateProfile {
+        id: 1,
+        nickname: SetOption::Set("jo".to_string()),
+        bio: Some("Hi".to_string()),
+    }
+    .update(&pool)
+    .await
+    .unwrap();
+    let checked_profile = Profile::get_by_id(&pool, &1).await.unwrap().unwrap();
+    assert_eq!(
+        checked_profile.nickname,
+        SetOption::Set(Some("jo".to_string()))
+    );
+    assert_eq!(checked_profile.bio, Some(SetOption::Set("Hi".to_string())));
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_option_of_set_option_column(pool: SqlitePool) {
+    let mut profile = Profile {
+        id: 1,
+        nickname: SetOption::NotSet,
+        bio: Some(SetOption::Set("Hello".to_string())),
+    };
+    profile.create(&pool).await.unwrap();
+    let checked_profile = Profile::get_by_id(&pool, &1).await.unwrap().unwrap();
+    assert_eq!(
+        checked_profile.bio,
+        Some(SetOption::Set("Hello".to_string()))
+    );
+
+    // The column is never skipped, `Some(NotSet)` writes a NULL like `None`
+    profile.bio = Some(SetOption::NotSet);
+    profile.update(&pool).await.unwrap();
+    let checked_profile = Profile::get_by_id(&pool, &1).await.unwrap().unwrap();
+    assert_eq!(checked_profile.bio, None);
+
+    profile.bio = Some(SetOption::Set("Bye".to_string()));
+    profile.update(&pool).await.unwrap();
+    profile.bio = None;
+    profile.update(&pool).await.unwrap();
+    let checked_profile = Profile::get_by_id(&pool, &1).await.unwrap().unwrap();
+    assert_eq!(checked_profile.bio, None);
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_belongs_to(pool: SqlitePool) {
+    let author = Author {
+        id: 1,
+        name: "Jane".to_string(),
+    };
+    author.create(&pool).await.unwrap();
+    let book = Book {
+        id: 1,
+        author_id: 1,
+        title: "My book".to_string(),
+    };
+    book.create(&pool).await.unwrap();
+
+    assert_eq!(book.author(&pool).await.unwrap(), Some(author));
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_get_by_id_with_belongs_to(pool: SqlitePool) {
+    assert_eq!(Book::get_by_id_with_author(&pool, &1).await.unwrap(), None);
+
+    let author = Author {
+        id: 1,
+        name: "Jane".to_string(),
+    };
+    author.create(&pool).await.unwrap();
+    let book = Book {
+        id: 1,
+        author_id: 1,
+        title: "My book".to_string(),
+    };
+    book.create(&pool).await.unwrap();
+
+    let (record, related) = Book::get_by_id_with_author(&pool, &1)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(record, book);
+    assert_eq!(related.as_ref(), Some(&author));
+    assert_eq!(
+        Book::get_by_id_with_author(&pool, 1).await.unwrap(),
+        Some((book, Some(author)))
+    );
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_create_returning_nothing(pool: SqlitePool) {
+    let new_author = NewAuthor {
+        id: 1,
+        name: "Jane".to_string(),
+    };
+    let () = new_author.create(&pool).await.unwrap();
+    let author = Author::get_by_id(&pool, &1).await.unwrap().unwrap();
+    assert_eq!(author.name, "Jane".to_string());
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_custom_error(pool: SqlitePool) {
+    let author = Author {
+        id: 1,
+        name: "Jane".to_string(),
+    };
+    author.create(&pool).await.unwrap();
+    let mut novel = Novel {
+        id: 1,
+        author_id: 1,
+        title: "My novel".to_string(),
+    };
+    let result: Result<i64, AppError> = novel.create(&pool).await;
+    assert_eq!(result.unwrap(), 1);
+
+    novel.title = "My second novel".to_string();
+    novel.update(&pool).await.unwrap();
+    assert_eq!(Novel::list_all(&pool).await.unwrap(), vec![novel]);
+    let (novel, checked_author) = Novel::get_by_id_with_author(&pool, &1)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(checked_author, Some(author));
+
+    novel.delete(&pool).await.unwrap();
+    match Novel::get_by_id_or_err(&pool, &1).await {
+        Err(AppError::Database(sqlx::Error::RowNotFound)) => {}
+        result => panic!("Unexpected result {result:?}"),
+    }
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_existing_ids(pool: SqlitePool) {
+    assert!(Subscriber::existing_ids(&pool, &[])
+        .await
+        .unwrap()
+        .is_empty());
+
+    for id in 1..=3 {
+        Subscriber {
+            id,
+            email: format!("user{id}@example.com"),
+            deleted_at: None,
+        }
+        .create(&pool)
+        .await
+        .unwrap();
+    }
+    Subscriber::get_by_id(&pool, &2)
+        .await
+        .unwrap()
+        .unwrap()
+        .delete(&pool)
+        .await
+        .unwrap();
+
+    let mut ids = Subscriber::existing_ids(&pool, &[1, 2, 3, 4])
+        .await
+        .unwrap();
+    ids.sort();
+    assert_eq!(ids, vec![1, 3]);
+}
+
+#[sqlx::test(migrations = "examples/sqlite/migrations")]
+async fn test_delete_by_filter(pool: SqlitePool) {
+    let mut ids = Vec::new();
+    for description in ["Item 1", "Item 2", "Item 3"] {
+        let item = NewTodo::new(description.to_string())
+            .create(&pool)
+            .await
+            .unwrap();
+        ids.push(item.id);
+    }
+    Todo::update_done_by_ids(&pool, &true, &ids[..2])
+        .await
+        .unwrap();
+
+    let rows_affected = Todo::delete_by_done(&pool, &true).await.unwrap();
+    assert_eq!(rows_affected, 2);
+
+    let remaining: Vec<String> = Todo::list_all(&pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|x| x.description)
+        .collect();
+    assert_eq!(remaining, vec!["Item 3".to_string()]);
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM todo")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 1);
+}
+
+#[sqlx::test(migrations = "examples/sqlite/migrations")]
+async fn test_list_all_ordered(pool: SqlitePool) {
+    let mut ids = Vec::new();
+    for description in ["Item 1", "Item 2", "Item 3"] {
+        let item = NewTodo::new(description.to_string())
+            .create(&pool)
+            .await
+            .unwrap();
+        ids.push(item.id);
+    }
+    Todo::update_done_by_ids(&pool, &true, &[ids[1]])
+        .await
+        .unwrap();
+
+    let items: Vec<i32> =
+        Todo::list_all_ordered(&pool, &[("done", Order::Desc), ("id", Order::Asc)])
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|x| x.id)
+            .collect();
+    assert_eq!(items, vec![ids[1], ids[0], ids[2]]);
+
+    let items = Todo::list_all_ordered(&pool, &[]).await.unwrap();
+    assert_eq!(items.len(), 3);
+
+    match Todo::list_all_ordered(&pool, &[("id; DROP TABLE todo", Order::Asc)]).await {
+        Err(sqlx::Error::ColumnNotFound(column)) => assert_eq!(column, "id; DROP TABLE todo"),
+        result => panic!("Unexpected result {result:?}"),
+    }
+}
+
+#[sqlx::test(migrations = "examples/sqlite/migrations")]
+async fn test_list_matching(pool: SqlitePool) {
+    let mut ids = Vec::new();
+    for description in ["Item 1", "Item 2", "Item 2"] {
+        let item = NewTodo::new(description.to_string())
+            .create(&pool)
+            .await
+            .unwrap();
+        ids.push(item.id);
+    }
+    Todo::update_done_by_ids(&pool, &true, &[ids[0], ids[1]])
+        .await
+        .unwrap();
+
+    let filter = TodoFilter {
+        description: Some("Item 2".to_string()),
+        done: Some(true),
+        ..Default::default()
+    };
+    let items: Vec<i32> = Todo::list_matching(&pool, &filter)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|x| x.id)
+        .collect();
+    assert_eq!(items, vec![ids[1]]);
+
+    let filter = TodoFilter {
+        done: Some(false),
+        ..Default::default()
+    };
+    let items = Todo::list_matching(&pool, &filter).await.unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].id, ids[2]);
+
+    // Without any field set, all the records are returned
+    let items = Todo::list_matching(&pool, &TodoFilter::default())
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 3);
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_with_deleted(pool: SqlitePool) {
+    for id in 1..=2 {
+        Subscriber {
+            id,
+            email: format!("user{id}@example.com"),
+            deleted_at: None,
+        }
+        .create(&pool)
+        .await
+        .unwrap();
+    }
+    Subscriber::get_by_id(&pool, &1)
+        .await
+        .unwrap()
+        .unwrap()
+        .delete(&pool)
+        .await
+        .unwrap();
+
+    assert!(Subscriber::get_by_id(&pool, &1).await.unwrap().is_none());
+    let subscriber = Subscriber::get_by_id_with_deleted(&pool, &1)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(subscriber.deleted_at.is_some());
+    assert!(subscriber.is_deleted());
+    assert!(!Subscriber::get_by_id(&pool, &2)
+        .await
+        .unwrap()
+        .unwrap()
+        .is_deleted());
+
+    assert_eq!(Subscriber::list_all(&pool).await.unwrap().len(), 1);
+    let mut ids: Vec<i64> = Subscriber::list_all_with_deleted(&pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|x| x.id)
+        .collect();
+    ids.sort();
+    assert_eq!(ids, vec![1, 2]);
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_list_matching_with_soft_deletion(pool: SqlitePool) {
+    for (id, email) in [
+        (1, "shared@example.com"),
+        (2, "shared@example.com"),
+        (3, "other@example.com"),
+    ] {
+        Subscriber {
+            id,
+            email: email.to_string(),
+            deleted_at: None,
+        }
+        .create(&pool)
+        .await
+        .unwrap();
+    }
+    Subscriber::get_by_id(&pool, &1)
+        .await
+        .unwrap()
+        .unwrap()
+        .delete(&pool)
+        .await
+        .unwrap();
+
+    let filter = SubscriberFilter {
+        email: Some("shared@example.com".to_string()),
+        ..Default::default()
+    };
+    let ids: Vec<i64> = Subscriber::list_matching(&pool, &filter)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|x| x.id)
+        .collect();
+    assert_eq!(ids, vec![2]);
+
+    let subscribers = Subscriber::list_matching(&pool, &SubscriberFilter::default())
+        .await
+        .unwrap();
+    assert_eq!(subscribers.len(), 2);
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_delete_by_filter_with_soft_deletion(pool: SqlitePool) {
+    for (id, email) in [
+        (1, "shared@example.com"),
+        (2, "shared@example.com"),
+        (3, "other@example.com"),
+    ] {
+        Subscriber {
+            id,
+            email: email.to_string(),
+            deleted_at: None,
+        }
+        .create(&pool)
+        .await
+        .unwrap();
+    }
+
+    let rows_affected = Subscriber::delete_by_email(&pool, &"shared@example.com".to_string())
+        .await
+        .unwrap();
+    assert_eq!(rows_affected, 2);
+
+    let remaining: Vec<i64> = Subscriber::list_all(&pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|x| x.id)
+        .collect();
+    assert_eq!(remaining, vec![3]);
+
+    // The records are still in the table
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM subscriber")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 3);
+
+    // Records already soft deleted are not deleted again
+    let rows_affected = Subscriber::delete_by_email(&pool, &"shared@example.com".to_string())
+        .await
+        .unwrap();
+    assert_eq!(rows_affected, 0);
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_upsert_many(pool: SqlitePool) {
+    assert_eq!(Subscriber::upsert_many(&pool, &[]).await.unwrap(), 0);
+
+    // More records than in a single chunk of SQLite
+    let subscribers: Vec<Subscriber> = (1..=12_000)
+        .map(|id| Subscriber {
+            id,
+            email: format!("user{id}@example.com"),
+            deleted_at: None,
+        })
+        .collect();
+    let rows_affected = Subscriber::upsert_many(&pool, &subscribers).await.unwrap();
+    assert_eq!(rows_affected, 12_000);
+
+    let subscribers: Vec<Subscriber> = (11_001..=13_000)
+        .map(|id| Subscriber {
+            id,
+            email: format!("updated{id}@example.com"),
+            deleted_at: None,
+        })
+        .collect();
+    let rows_affected = Subscriber::upsert_many(&pool, &subscribers).await.unwrap();
+    assert_eq!(rows_affected, 2_000);
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM subscriber")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 13_000);
+    assert_eq!(
+        Subscriber::get_by_id(&pool, &11_000)
+            .await
+            .unwrap()
+            .unwrap()
+            .email,
+        "user11000@example.com"
+    );
+    assert_eq!(
+        Subscriber::get_by_id(&pool, &12_000)
+            .await
+            .unwrap()
+            .unwrap()
+            .email,
+        "updated12000@example.com"
+    );
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_transform_on_write(pool: SqlitePool) {
+    let mut subscriber = EncodedSubscriber {
+        id: 1,
+        email: "user@example.com".to_string(),
+    };
+    subscriber.create(&pool).await.unwrap();
+    let email: String = sqlx::query_scalar("SELECT email FROM subscriber WHERE id = 1")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(email, "moc.elpmaxe@resu");
+
+    subscriber.email = "other@example.com".to_string();
+    subscriber.update(&pool).await.unwrap();
+    let email: String = sqlx::query_scalar("SELECT email FROM subscriber WHERE id = 1")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(email, "moc.elpmaxe@rehto");
+
+    // The generated `FromRow` decodes the value
+    let subscriber = EncodedSubscriber::get_by_id(&pool, &1)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(subscriber.email, "other@example.com");
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_generated_from_row_with_renamed_column(pool: SqlitePool) {
+    let mut mailing_address = MailingAddress {
+        id: 1,
+        address: "user@example.com".to_string(),
+        deleted_at: None,
+        label: Some("Home".to_string()),
+    };
+    mailing_address.create(&pool).await.unwrap();
+
+    mailing_address.address = "other@example.com".to_string();
+    mailing_address.update(&pool).await.unwrap();
+
+    let expected = MailingAddress {
+        id: 1,
+        address: "other@example.com".to_string(),
+        deleted_at: None,
+        label: None,
+    };
+    assert_eq!(
+        MailingAddress::get_by_id(&pool, &1).await.unwrap(),
+        Some(expected)
+    );
+    assert_eq!(MailingAddress::list_all(&pool).await.unwrap().len(), 1);
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_sqlx_skip_field_is_not_a_column(pool: SqlitePool) {
+    let mut author = CachedAuthor {
+        id: 1,
+        name: "Ursula".to_string(),
+        display_name: Some("Ursula K. Le Guin".to_string()),
+    };
+    author.create(&pool).await.unwrap();
+
+    author.name = "Ursula K.".to_string();
+    author.update(&pool).await.unwrap();
+
+    let author = CachedAuthor::get_by_id(&pool, &1).await.unwrap().unwrap();
+    assert_eq!(author.name, "Ursula K.");
+    assert_eq!(author.display_name, None);
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_custom_table_name_fn(pool: SqlitePool) {
+    assert_eq!(NamedAuthor::orm_table(), "author");
+    assert_eq!(Author::table_name(), "author");
+
+    Author {
+        id: 1,
+        name: "Jane".to_string(),
+    }
+    .create(&pool)
+    .await
+    .unwrap();
+    let author = NamedAuthor::get_by_id(&pool, &1).await.unwrap().unwrap();
+    assert_eq!(author.table_name(), "Jane's table");
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_stream_by_ids(pool: SqlitePool) {
+    sqlx::query(
+        "INSERT INTO author (id, name)
+        WITH RECURSIVE seq(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM seq WHERE x < 2500)
+        SELECT x, 'Author ' || x FROM seq",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let chunk_size = std::num::NonZeroUsize::new(1000).unwrap();
+    let ids: Vec<i64> = (1..=2500).collect();
+    let authors: Vec<Author> = Author::stream_by_ids(&pool, ids.clone(), chunk_size)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(
+        authors.iter().map(|author| author.id).collect::<Vec<i64>>(),
+        ids
+    );
+    assert_eq!(authors[2499].name, "Author 2500".to_string());
+
+    let authors: Vec<Author> = Author::stream_by_ids(&pool, vec![1, 4000], chunk_size)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(authors.len(), 1);
+    let mut authors = std::pin::pin!(Author::stream_by_ids(&pool, Vec::new(), chunk_size));
+    assert!(authors.next().await.is_none());
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_list_projection(pool: SqlitePool) {
+    Author {
+        id: 1,
+        name: "Jane".to_string(),
+    }
+    .create(&pool)
+    .await
+    .unwrap();
+    Book {
+        id: 1,
+        author_id: 1,
+        title: "Book 1".to_string(),
+    }
+    .create(&pool)
+    .await
+    .unwrap();
+
+    let titles = Book::list_all_title(&pool).await.unwrap();
+    assert_eq!(
+        titles,
+        vec![BookTitle {
+            id: 1,
+            title: "Book 1".to_string(),
+        }]
+    );
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_cascade_delete(pool: SqlitePool) {
+    for id in 1..=2 {
+        CascadingAuthor {
+            id,
+            name: format!("Author {id}"),
+        }
+        .create(&pool)
+        .await
+        .unwrap();
+        Book {
+            id,
+            author_id: id,
+            title: format!("Book {id}"),
+        }
+        .create(&pool)
+        .await
+        .unwrap();
+    }
+    let author = CascadingAuthor {
+        id: 1,
+        name: "Author 1".to_string(),
+    };
+
+    // Nothing is deleted when the transaction of the caller is rolled back
+    let mut tx = pool.begin().await.unwrap();
+    author.delete(&mut *tx).await.unwrap();
+    tx.rollback().await.unwrap();
+    assert!(Book::get_by_id(&pool, &1).await.unwrap().is_some());
+
+    author.delete(&pool).await.unwrap();
+    assert!(Author::get_by_id(&pool, &1).await.unwrap().is_none());
+    assert!(Book::get_by_id(&pool, &1).await.unwrap().is_none());
+    assert!(Book::get_by_id(&pool, &2).await.unwrap().is_some());
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_has_many(pool: SqlitePool) {
+    let author = Author {
+        id: 1,
+        name: "Jane".to_string(),
+    };
+    author.create(&pool).await.unwrap();
+    assert!(author.books(&pool).await.unwrap().is_empty());
+
+    for id in 1..=2 {
+        Book {
+            id,
+            author_id: 1,
+            title: format!("Book {id}"),
+        }
+        .create(&pool)
+        .await
+        .unwrap();
+    }
+    Author {
+        id: 2,
+        name: "John".to_string(),
+    }
+    .create(&pool)
+    .await
+    .unwrap();
+    Book {
+        id: 3,
+        author_id: 2,
+        title: "Another book".to_string(),
+    }
+    .create(&pool)
+    .await
+    .unwrap();
+
+    let books = author.books(&pool).await.unwrap();
+    assert_eq!(
+        books.iter().map(|book| book.id).collect::<Vec<i64>>(),
+        vec![1, 2]
+    );
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_columns_without_clone(pool: SqlitePool) {
+    let mut attachment = Attachment {
+        id: 1,
+        content: Content(vec![1, 2, 3]),
+        caption: SetOption::Set(Caption("A caption".to_string())),
+    };
+    attachment.create(&pool).await.unwrap();
+    let checked_attachment = Attachment::get_by_id(&pool, &1).await.unwrap().unwrap();
+    assert_eq!(checked_attachment, attachment);
+
+    attachment.content = Content(vec![4, 5]);
+    attachment.caption = SetOption::NotSet;
+    attachment.update(&pool).await.unwrap();
+    let checked_attachment = Attachment::get_by_id(&pool, &1).await.unwrap().unwrap();
+    assert_eq!(checked_attachment.content, Content(vec![4, 5]));
+    assert_eq!(
+        checked_attachment.caption,
+        SetOption::Set(Caption("A caption".to_string()))
+    );
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_partial_return_object_with_explicit_returning(pool: SqlitePool) {
+    Author {
+        id: 1,
+        name: "Jane".to_string(),
+    }
+    .create(&pool)
+    .await
+    .unwrap();
+
+    let summary = NewBookSummary {
+        author_id: 1,
+        title: "My book".to_string(),
+    }
+    .create(&pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        summary,
+        BookSummary {
+            id: 1,
+            title: "My book".to_string(),
+        }
+    );
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_json_columns(pool: SqlitePool) {
+    let mut settings = Settings {
+        id: 1,
+        metadata: SetOption::Set(serde_json::json!({"theme": "dark", "retries": 3})),
+        extra: SetOption::Set(None),
+    };
+    settings.create(&pool).await.unwrap();
+
+    let checked_settings = Settings::get_by_id(&pool, &1).await.unwrap().unwrap();
+    assert_eq!(checked_settings.metadata, settings.metadata);
+    // `Set(None)` is stored as a `NULL` and not as a JSON `null`
+    assert_eq!(checked_settings.extra, SetOption::NotSet);
+    let (extra_is_null,): (bool,) = sqlx::query_as("SELECT extra IS NULL FROM settings")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert!(extra_is_null);
+
+    settings.metadata = SetOption::NotSet;
+    settings.extra = SetOption::Set(Some(serde_json::json!(["a", "b"])));
+    settings.update(&pool).await.unwrap();
+
+    let checked_settings = Settings::get_by_id(&pool, &1).await.unwrap().unwrap();
+    assert_eq!(
+        checked_settings.metadata,
+        SetOption::Set(serde_json::json!({"theme": "dark", "retries": 3}))
+    );
+    assert_eq!(
+        checked_settings.extra,
+        SetOption::Set(Some(serde_json::json!(["a", "b"])))
+    );
+}
+
+#[sqlx::test(migrations = "tests/migrations")]
+async fn test_enum_get_by_id(pool: SqlitePool) {
+    sqlx::query(
+        "INSERT INTO shape (id, kind, radius, side) VALUES (1, 'circle', 2.0, NULL), (2, 'square', NULL, 3.0), (3, 'triangle', NULL, NULL)",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    assert_eq!(Shape::table_name(), "shape");
+    assert_eq!(
+        Shape::get_by_id(&pool, &1).await.unwrap(),
+        Some(Shape::Circle { id: 1, radius: 2.0 })
+    );
+    assert_eq!(
+        Shape::get_by_id(&pool, &2).await.unwrap(),
+        Some(Shape::Square { id: 2, side: 3.0 })
+    );
+    assert_eq!(Shape::get_by_id(&pool, &4).await.unwrap(), None);
+
+    let error = Shape::get_by_id(&pool, &3).await.unwrap_err();
+    assert!(matches!(error, sqlx::Error::ColumnDecode { index, .. } if index == "kind"));
+}
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS task (
+    id          INTEGER PRIMARY KEY NOT NULL,
+    status      TEXT                NOT NULL,
+    priority    INTEGER             NOT NULL
+);
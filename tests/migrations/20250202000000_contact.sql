@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS contact (
+    id INTEGER PRIMARY KEY NOT NULL,
+    firstName TEXT NOT NULL,
+    lastName TEXT NOT NULL,
+    email_address TEXT NOT NULL
+);
@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS note (
+    id      BLOB    PRIMARY KEY NOT NULL,
+    body    TEXT                NOT NULL
+);
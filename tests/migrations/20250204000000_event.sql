@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS event (
+    id          INTEGER     PRIMARY KEY NOT NULL,
+    name        TEXT                    NOT NULL,
+    created_at  DATETIME                NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS tag (
+    name    TEXT    PRIMARY KEY NOT NULL,
+    label   TEXT                NOT NULL
+);
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS person (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    first_name  TEXT    NOT NULL,
+    last_name   TEXT    NOT NULL,
+    full_name   TEXT    GENERATED ALWAYS AS (first_name || ' ' || last_name) VIRTUAL
+);
@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS document (
+    id      BLOB    PRIMARY KEY NOT NULL DEFAULT (randomblob(16)),
+    title   TEXT                NOT NULL
+);
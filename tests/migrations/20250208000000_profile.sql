@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS profile (
+    id          INTEGER PRIMARY KEY NOT NULL,
+    nickname    TEXT                DEFAULT 'anonymous',
+    bio         TEXT
+);
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS author (
+    id      INTEGER PRIMARY KEY NOT NULL,
+    name    TEXT                NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS book (
+    id          INTEGER PRIMARY KEY NOT NULL,
+    author_id   INTEGER             NOT NULL REFERENCES author (id),
+    title       TEXT                NOT NULL
+);
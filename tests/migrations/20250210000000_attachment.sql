@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS attachment (
+    id          INTEGER PRIMARY KEY NOT NULL,
+    content     BLOB                NOT NULL,
+    caption     TEXT
+);
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS settings (
+    id          INTEGER PRIMARY KEY NOT NULL,
+    metadata    TEXT,
+    extra       TEXT
+);
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS meeting (
+    id          INTEGER PRIMARY KEY NOT NULL,
+    title       TEXT NOT NULL,
+    starts_at   TEXT NOT NULL,
+    updated_at  TEXT NOT NULL,
+    deleted_at  TEXT
+);
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS subscriber (
+    id          INTEGER PRIMARY KEY NOT NULL,
+    email       TEXT                NOT NULL,
+    deleted_at  TEXT
+);
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS shape (
+    id          INTEGER PRIMARY KEY NOT NULL,
+    kind        TEXT    NOT NULL,
+    radius      REAL,
+    side        REAL
+);
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS project (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    tenant_id   INTEGER NOT NULL,
+    name        TEXT    NOT NULL
+);
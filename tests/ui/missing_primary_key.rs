@@ -0,0 +1,10 @@
+use tiny_orm::Table;
+
+#[derive(Table)]
+#[tiny_orm(only = "get")]
+struct Todo {
+    uuid: i32,
+    description: String,
+}
+
+fn main() {}
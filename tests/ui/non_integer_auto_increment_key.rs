@@ -0,0 +1,11 @@
+use tiny_orm::Table;
+
+#[derive(Table)]
+#[tiny_orm(database = "mysql", only = "create")]
+struct Todo {
+    #[tiny_orm(primary_key(auto))]
+    id: String,
+    description: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use tiny_orm::{SetOption, Table};
+
+#[derive(Table)]
+#[tiny_orm(only = "create")]
+struct Todo {
+    id: SetOption<i64>,
+    description: String,
+}
+
+fn main() {}
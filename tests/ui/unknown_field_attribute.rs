@@ -0,0 +1,10 @@
+use tiny_orm::Table;
+
+#[derive(Table)]
+struct Todo {
+    #[tiny_orm(primary_kye)]
+    id: i32,
+    description: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use tiny_orm::Table;
+
+#[derive(Table)]
+#[tiny_orm(belongs_to = "User", foreign_key = "owner_id")]
+struct Todo {
+    id: i64,
+    user_id: i64,
+}
+
+fn main() {}
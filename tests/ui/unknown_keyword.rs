@@ -0,0 +1,10 @@
+use tiny_orm::Table;
+
+#[derive(Table)]
+#[tiny_orm(tabble_name = "todos")]
+struct Todo {
+    id: i32,
+    description: String,
+}
+
+fn main() {}
@@ -1,69 +1,335 @@
 use std::{collections::HashSet, str::FromStr};
 use syn::{
-    parenthesized, parse_str, punctuated::Punctuated, token::Paren, Attribute, Data, DeriveInput,
-    Expr, ExprLit, Fields, Ident, Lit, Meta, Token,
+    parenthesized, parse_str, punctuated::Punctuated, token::Paren, Attribute, Data, DataEnum,
+    DataStruct, DeriveInput, Error, Expr, ExprLit, Field, Fields, Ident, Lit, LitStr, Meta, Path,
+    Token,
 };
 
-use crate::types::{Column, Operation, Operations, ParsedStruct, PrimaryKey};
+use crate::database::{self, DbType};
+use crate::types::{
+    BelongsTo, Column, ConflictTarget, HasMany, Inject, Operation, Operations, OrderBy,
+    ParsedStruct, PrimaryKey, Projection, RenameAll, Returning, Scope, SoftDeletion, Variant,
+};
 
 const NAME_MACRO_OPERATION_ARG: &str = "tiny_orm";
+const NAME_SQLX_ARG: &str = "sqlx";
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Attr {
     pub parsed_struct: ParsedStruct,
     pub primary_key: Option<PrimaryKey>,
+    pub lookup_key: Option<Column>,
+    pub cursor: Option<Column>,
     pub columns: Vec<Column>,
     pub operations: Operations,
-    pub soft_deletion: bool,
+    pub soft_deletion: Option<SoftDeletion>,
 }
 
 impl Attr {
-    pub fn parse(input: DeriveInput) -> Self {
+    pub fn db_type(&self) -> DbType {
+        match &self.parsed_struct.database {
+            Some(db_type) => db_type.clone(),
+            None => database::db_type(),
+        }
+    }
+
+    /// MySQL and SQLite before 3.35 (with the `sqlite-no-returning` feature) do not support the `RETURNING` statement
+    pub fn supports_returning(&self) -> bool {
+        match self.db_type() {
+            DbType::Postgres => true,
+            DbType::MySQL => false,
+            DbType::Sqlite => !self.parsed_struct.sqlite_no_returning,
+        }
+    }
+
+    /// The column used by `get_by_id`, the primary key unless `lookup_key` is set
+    pub fn lookup_column(&self) -> Option<&Column> {
+        self.lookup_key.as_ref().or(self.primary_key.as_ref())
+    }
+
+    /// The column used by `list_after`, the primary key unless `cursor` is set
+    pub fn cursor_column(&self) -> Option<&Column> {
+        self.cursor.as_ref().or(self.primary_key.as_ref())
+    }
+
+    /// Compare by ident so that the type of the column (eg: `SetOption`) does not matter
+    pub fn is_primary_key(&self, column: &Column) -> bool {
+        self.primary_key
+            .as_ref()
+            .is_some_and(|primary_key| primary_key.ident == column.ident)
+    }
+
+    pub fn parse(input: DeriveInput) -> syn::Result<Self> {
         let struct_name = input.ident;
-        let (parsed_struct, operations, soft_deletion) =
-            Parser::parse_struct_macro_arguments(&struct_name, &input.attrs);
-        let (primary_key, columns) = Parser::parse_fields_macro_arguments(input.data);
+        let StructArguments {
+            mut parsed_struct,
+            operations,
+            soft_deletion,
+            rename_all,
+            primary_key_field,
+            lookup_key,
+            cursor,
+            discriminator,
+        } = Parser::parse_struct_macro_arguments(&struct_name, &input.attrs)?;
+        parsed_struct.vis = input.vis;
+        parsed_struct.generics = input.generics;
+        parsed_struct.skipped_fields = Parser::skipped_fields(&input.data);
+        if let Some(rename_all) = rename_all {
+            parsed_struct.created_key = rename_all.apply(&parsed_struct.created_key);
+        }
+        let (primary_key, columns) = match input.data {
+            Data::Enum(data_enum) => {
+                let (primary_key, columns, variants) =
+                    Parser::parse_variants(data_enum, rename_all, primary_key_field)?;
+                parsed_struct.discriminator = Some(match discriminator {
+                    Some(lit_str) => lit_str.value().trim().to_string(),
+                    None => "kind".to_string(),
+                });
+                parsed_struct.variants = variants;
+                (primary_key, columns)
+            }
+            data => {
+                if let Some(lit_str) = discriminator {
+                    return Err(Error::new_spanned(
+                        lit_str,
+                        "The 'discriminator' keyword requires an enum",
+                    ));
+                }
+                Parser::parse_fields_macro_arguments(data, rename_all, primary_key_field)?
+            }
+        };
+        let lookup_key = match lookup_key {
+            Some(lit_str) => {
+                let name = lit_str.value().trim().to_string();
+                match columns.iter().find(|column| column.ident == name) {
+                    Some(column) => Some(column.clone()),
+                    None => {
+                        return Err(Error::new_spanned(
+                            lit_str,
+                            format!("No field named '{name}' found for the 'lookup_key' keyword"),
+                        ))
+                    }
+                }
+            }
+            None => None,
+        };
+        let cursor = match cursor {
+            Some(lit_str) => {
+                let name = lit_str.value().trim().to_string();
+                match columns.iter().find(|column| column.ident == name) {
+                    Some(column) => Some(column.clone()),
+                    None => {
+                        return Err(Error::new_spanned(
+                            lit_str,
+                            format!("No field named '{name}' found for the 'cursor' keyword"),
+                        ))
+                    }
+                }
+            }
+            None => None,
+        };
 
-        Attr {
+        Ok(Attr {
             parsed_struct,
             primary_key,
+            lookup_key,
+            cursor,
             columns,
             operations,
             soft_deletion,
-        }
+        })
     }
 }
 
+#[derive(Debug)]
+struct StructArguments {
+    parsed_struct: ParsedStruct,
+    operations: Operations,
+    soft_deletion: Option<SoftDeletion>,
+    rename_all: Option<RenameAll>,
+    primary_key_field: Option<LitStr>,
+    lookup_key: Option<LitStr>,
+    cursor: Option<LitStr>,
+    discriminator: Option<LitStr>,
+}
+
 struct Parser();
 
 impl Parser {
     fn parse_struct_macro_arguments(
         struct_name: &Ident,
         attrs: &[Attribute],
-    ) -> (ParsedStruct, Operations, bool) {
+    ) -> syn::Result<StructArguments> {
         let mut only: Option<Vec<Operation>> = None;
         let mut exclude: Option<Vec<Operation>> = None;
         let mut add: Option<Vec<Operation>> = None;
+        let mut all: Option<Path> = None;
+        let mut view: Option<Path> = None;
         let mut return_object: Option<Ident> = None;
+        let mut return_nothing = false;
+        let mut like: Option<Ident> = None;
         let mut table_name: Option<String> = None;
-        let mut soft_deletion: bool = false;
+        let mut table_prefix: Option<String> = None;
+        let mut table_name_fn: Option<Ident> = None;
+        let mut returning: Option<Returning> = None;
+        let mut scope: Option<Scope> = None;
+        let mut inject: Option<Inject> = None;
+        let mut order_by: Option<OrderBy> = None;
+        let mut conflict_target: Option<ConflictTarget> = None;
+        let mut conflict_where: Option<LitStr> = None;
+        let mut delete_returning = false;
+        let mut returning_generated = false;
+        let mut env_vars: Vec<LitStr> = Vec::new();
+        let mut skip_defaults = false;
+        let mut from_row = false;
+        let mut error: Option<Path> = None;
+        let mut database: Option<DbType> = None;
+        let mut soft_deletion: Option<SoftDeletion> = None;
+        let mut soft_delete_now: Option<LitStr> = None;
+        let mut rename_all: Option<RenameAll> = None;
+        let mut primary_key_field: Option<LitStr> = None;
+        let mut lookup_key: Option<LitStr> = None;
+        let mut cursor: Option<LitStr> = None;
+        let mut discriminator: Option<LitStr> = None;
+        let mut last_attr: Option<&Attribute> = None;
+        let mut belongs_to: Vec<BelongsTo> = Vec::new();
+        let mut has_many: Vec<HasMany> = Vec::new();
+        let mut cascade_delete: Vec<LitStr> = Vec::new();
+        let mut projections: Vec<Projection> = Vec::new();
 
         for attr in attrs {
+            if attr.path().is_ident(NAME_SQLX_ARG) {
+                // Only `rename_all` matters to us, the rest belongs to sqlx
+                let mut rename_all_lit: Option<LitStr> = None;
+                let _ = attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("rename_all") {
+                        rename_all_lit = Some(meta.value()?.parse()?);
+                    } else if meta.input.peek(Token![=]) {
+                        let _: Expr = meta.value()?.parse()?;
+                    }
+                    Ok(())
+                });
+                if let Some(lit_str) = rename_all_lit {
+                    rename_all = Some(RenameAll::from_str(&lit_str.value()).map_err(|_| {
+                        Error::new_spanned(
+                            &lit_str,
+                            format!("rename_all = \"{}\" is not supported", lit_str.value()),
+                        )
+                    })?);
+                }
+            }
             if attr.path().is_ident(NAME_MACRO_OPERATION_ARG) {
-                let nested = attr
-                    .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
-                    .unwrap();
+                last_attr = Some(attr);
+                // An association and its foreign key belong to the same attribute
+                let mut belongs_to_target: Option<Ident> = None;
+                let mut has_many_target: Option<Ident> = None;
+                let mut foreign_key: Option<LitStr> = None;
+                let mut child_table: Option<String> = None;
+                let mut child_soft_deletion: Option<SoftDeletion> = None;
+                let mut child_fn: Option<Ident> = None;
+                // The operations of several attributes are merged, but not within the same one
+                let mut attr_keywords: HashSet<&str> = HashSet::new();
+                let nested =
+                    attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
                 for meta in nested {
                     match meta {
-                        Meta::NameValue(name_value) if name_value.path.is_ident("table_name") => {
+                        Meta::NameValue(name_value) if name_value.path.is_ident("belongs_to") => {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = name_value.clone().value
+                            {
+                                belongs_to_target =
+                                    Some(parse_str::<Ident>(&lit_str.value()).map_err(|_| {
+                                        Error::new_spanned(
+                                            &lit_str,
+                                            "Failed to parse belongs_to as identifier",
+                                        )
+                                    })?);
+                            };
+                        }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("has_many") => {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = name_value.clone().value
+                            {
+                                has_many_target =
+                                    Some(parse_str::<Ident>(&lit_str.value()).map_err(|_| {
+                                        Error::new_spanned(
+                                            &lit_str,
+                                            "Failed to parse has_many as identifier",
+                                        )
+                                    })?);
+                            };
+                        }
+                        Meta::NameValue(name_value)
+                            if name_value.path.is_ident("cascade_delete") =>
+                        {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = name_value.clone().value
+                            {
+                                cascade_delete.push(lit_str);
+                            };
+                        }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("child_table") => {
+                            child_table = Some(Parser::parse_string_value(
+                                &name_value.value,
+                                &mut env_vars,
+                            )?);
+                        }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("child_fn") => {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = name_value.clone().value
+                            {
+                                child_fn =
+                                    Some(parse_str::<Ident>(&lit_str.value()).map_err(|_| {
+                                        Error::new_spanned(
+                                            &lit_str,
+                                            "Failed to parse child_fn as identifier",
+                                        )
+                                    })?);
+                            };
+                        }
+                        Meta::Path(path) if path.is_ident("child_soft_deletion") => {
+                            child_soft_deletion = Some(SoftDeletion::default());
+                        }
+                        Meta::NameValue(name_value)
+                            if name_value.path.is_ident("child_soft_deletion") =>
+                        {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = name_value.clone().value
+                            {
+                                child_soft_deletion = Some(SoftDeletion::new(&lit_str.value()));
+                            };
+                        }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("foreign_key") => {
                             if let Expr::Lit(ExprLit {
                                 lit: Lit::Str(lit_str),
                                 ..
                             }) = name_value.clone().value
                             {
-                                table_name = Some(lit_str.value().clone());
+                                foreign_key = Some(lit_str);
                             };
                         }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("table_name") => {
+                            table_name = Some(Parser::parse_string_value(
+                                &name_value.value,
+                                &mut env_vars,
+                            )?);
+                        }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("table_prefix") => {
+                            table_prefix = Some(Parser::parse_string_value(
+                                &name_value.value,
+                                &mut env_vars,
+                            )?);
+                        }
                         Meta::NameValue(name_value)
                             if name_value.path.is_ident("return_object") =>
                         {
@@ -72,160 +338,831 @@ impl Parser {
                                 ..
                             }) = name_value.clone().value
                             {
-                                return_object = Some(
-                                    parse_str::<Ident>(&lit_str.value())
-                                        .expect("Failed to parse return_object as identifier"),
+                                // `create` returns nothing, the other methods keep their default
+                                if lit_str.value().trim() == "()" {
+                                    return_nothing = true;
+                                    continue;
+                                }
+                                return_object =
+                                    Some(parse_str::<Ident>(&lit_str.value()).map_err(|_| {
+                                        Error::new_spanned(
+                                            &lit_str,
+                                            "Failed to parse return_object as identifier",
+                                        )
+                                    })?);
+                            };
+                        }
+                        Meta::NameValue(name_value)
+                            if name_value.path.is_ident("table_name_fn") =>
+                        {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = name_value.clone().value
+                            {
+                                table_name_fn =
+                                    Some(parse_str::<Ident>(&lit_str.value()).map_err(|_| {
+                                        Error::new_spanned(
+                                            &lit_str,
+                                            "Failed to parse table_name_fn as identifier",
+                                        )
+                                    })?);
+                            };
+                        }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("like") => {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = name_value.clone().value
+                            {
+                                like =
+                                    Some(parse_str::<Ident>(&lit_str.value()).map_err(|_| {
+                                        Error::new_spanned(
+                                            &lit_str,
+                                            "Failed to parse like as identifier",
+                                        )
+                                    })?);
+                            };
+                        }
+                        Meta::NameValue(name_value)
+                            if name_value.path.is_ident("primary_key_field") =>
+                        {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = name_value.clone().value
+                            {
+                                primary_key_field = Some(lit_str);
+                            };
+                        }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("lookup_key") => {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = name_value.clone().value
+                            {
+                                lookup_key = Some(lit_str);
+                            };
+                        }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("cursor") => {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = name_value.clone().value
+                            {
+                                cursor = Some(lit_str);
+                            };
+                        }
+                        Meta::NameValue(name_value)
+                            if name_value.path.is_ident("discriminator") =>
+                        {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = name_value.clone().value
+                            {
+                                discriminator = Some(lit_str);
+                            };
+                        }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("returning") => {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = name_value.clone().value
+                            {
+                                returning = Some(
+                                    Returning::new(&lit_str.value())
+                                        .map_err(|e| Error::new_spanned(&lit_str, e))?,
                                 );
                             };
                         }
-                        Meta::NameValue(name_value) if name_value.path.is_ident("only") => {
+                        Meta::NameValue(name_value) if name_value.path.is_ident("inject") => {
                             if let Expr::Lit(ExprLit {
                                 lit: Lit::Str(lit_str),
                                 ..
                             }) = name_value.clone().value
                             {
-                                if only.is_some() {
-                                    panic!("The 'only' keyword has already been specified")
-                                };
-                                only = Some(
-                                    lit_str
-                                        .value()
-                                        .split(',')
-                                        .map(|s| {
-                                            Operation::from_str(s.trim()).unwrap_or_else(|_| {
-                                                panic!("Operation {s} is not supported")
-                                            })
-                                        })
-                                        .collect(),
+                                inject = Some(
+                                    Inject::new(&lit_str.value())
+                                        .map_err(|e| Error::new_spanned(&lit_str, e))?,
                                 );
                             };
                         }
-                        Meta::NameValue(name_value) if name_value.path.is_ident("exclude") => {
+                        Meta::NameValue(name_value) if name_value.path.is_ident("scope") => {
                             if let Expr::Lit(ExprLit {
                                 lit: Lit::Str(lit_str),
                                 ..
                             }) = name_value.clone().value
                             {
-                                if exclude.is_some() {
-                                    panic!("The 'exclude' keyword has already been specified")
-                                };
-                                exclude = Some(
-                                    lit_str
-                                        .value()
-                                        .split(',')
-                                        .map(|s| {
-                                            Operation::from_str(s.trim()).unwrap_or_else(|_| {
-                                                panic!("Operation {s} is not supported")
-                                            })
-                                        })
-                                        .collect(),
+                                scope = Some(
+                                    Scope::new(&lit_str.value())
+                                        .map_err(|e| Error::new_spanned(&lit_str, e))?,
                                 );
                             };
                         }
-                        Meta::NameValue(name_value) if name_value.path.is_ident("add") => {
+                        Meta::NameValue(name_value) if name_value.path.is_ident("order_by") => {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = name_value.clone().value
+                            {
+                                order_by = Some(
+                                    OrderBy::new(&lit_str.value())
+                                        .map_err(|e| Error::new_spanned(&lit_str, e))?,
+                                );
+                            };
+                        }
+                        Meta::NameValue(name_value)
+                            if name_value.path.is_ident("conflict_target") =>
+                        {
                             if let Expr::Lit(ExprLit {
                                 lit: Lit::Str(lit_str),
                                 ..
                             }) = name_value.clone().value
                             {
-                                if add.is_some() {
-                                    panic!("The 'add' keyword has already been specified")
-                                };
-                                add = Some(
-                                    lit_str
-                                        .value()
-                                        .split(',')
-                                        .map(|s| {
-                                            Operation::from_str(s.trim()).unwrap_or_else(|_| {
-                                                panic!("Operation {s} is not supported")
-                                            })
-                                        })
-                                        .collect(),
+                                conflict_target = Some(
+                                    ConflictTarget::new(&lit_str.value())
+                                        .map_err(|e| Error::new_spanned(&lit_str, e))?,
                                 );
                             };
                         }
+                        Meta::NameValue(name_value)
+                            if name_value.path.is_ident("conflict_where") =>
+                        {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = name_value.clone().value
+                            {
+                                conflict_where = Some(lit_str);
+                            };
+                        }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("error") => {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = name_value.clone().value
+                            {
+                                error =
+                                    Some(parse_str::<Path>(&lit_str.value()).map_err(|_| {
+                                        Error::new_spanned(
+                                            &lit_str,
+                                            "Failed to parse error as a path",
+                                        )
+                                    })?);
+                            };
+                        }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("database") => {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = name_value.clone().value
+                            {
+                                database =
+                                    Some(DbType::from_str(&lit_str.value()).map_err(|_| {
+                                        Error::new_spanned(
+                                            &lit_str,
+                                            format!(
+                                                "Database '{}' is not supported",
+                                                lit_str.value()
+                                            ),
+                                        )
+                                    })?);
+                            };
+                        }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("only") => {
+                            if !attr_keywords.insert("only") {
+                                return Err(Error::new_spanned(
+                                    &name_value.path,
+                                    "The 'only' keyword has already been specified",
+                                ));
+                            };
+                            Parser::merge_operations(
+                                &mut only,
+                                Parser::parse_operations(&name_value.value)?,
+                            );
+                        }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("exclude") => {
+                            if !attr_keywords.insert("exclude") {
+                                return Err(Error::new_spanned(
+                                    &name_value.path,
+                                    "The 'exclude' keyword has already been specified",
+                                ));
+                            };
+                            Parser::merge_operations(
+                                &mut exclude,
+                                Parser::parse_operations(&name_value.value)?,
+                            );
+                        }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("add") => {
+                            if !attr_keywords.insert("add") {
+                                return Err(Error::new_spanned(
+                                    &name_value.path,
+                                    "The 'add' keyword has already been specified",
+                                ));
+                            };
+                            Parser::merge_operations(
+                                &mut add,
+                                Parser::parse_operations(&name_value.value)?,
+                            );
+                        }
                         Meta::Path(path) if path.is_ident("all") => {
-                            add = Some(Operation::all());
+                            all = Some(path);
+                        }
+                        Meta::Path(path) if path.is_ident("view") => {
+                            view = Some(path);
+                        }
+                        Meta::Path(path) if path.is_ident("delete_returning") => {
+                            delete_returning = true;
+                        }
+                        Meta::Path(path) if path.is_ident("returning_generated") => {
+                            returning_generated = true;
+                        }
+                        Meta::Path(path) if path.is_ident("skip_defaults") => {
+                            skip_defaults = true;
+                        }
+                        Meta::Path(path) if path.is_ident("from_row") => {
+                            from_row = true;
                         }
                         Meta::Path(path) if path.is_ident("soft_deletion") => {
-                            soft_deletion = true;
+                            soft_deletion = Some(SoftDeletion::default());
+                        }
+                        Meta::NameValue(name_value)
+                            if name_value.path.is_ident("soft_deletion") =>
+                        {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = name_value.clone().value
+                            {
+                                soft_deletion = Some(SoftDeletion::new(&lit_str.value()));
+                            };
+                        }
+                        Meta::List(meta_list) if meta_list.path.is_ident("projections") => {
+                            meta_list.parse_nested_meta(|meta| {
+                                let target = meta.path.require_ident()?.clone();
+                                let lit_str: LitStr = meta.value()?.parse()?;
+                                projections.push(
+                                    Projection::new(target, &lit_str.value())
+                                        .map_err(|e| Error::new_spanned(&lit_str, e))?,
+                                );
+                                Ok(())
+                            })?;
+                        }
+                        Meta::List(meta_list) if meta_list.path.is_ident("soft_deletion") => {
+                            meta_list.parse_nested_meta(|meta| {
+                                if meta.path.is_ident("bool") {
+                                    let lit_str: LitStr = meta.value()?.parse()?;
+                                    soft_deletion = Some(SoftDeletion::new_bool(&lit_str.value()));
+                                    Ok(())
+                                } else {
+                                    Err(meta.error("Expected `soft_deletion(bool = \"column\")`"))
+                                }
+                            })?;
+                        }
+                        Meta::NameValue(name_value)
+                            if name_value.path.is_ident("soft_delete_now") =>
+                        {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = name_value.clone().value
+                            {
+                                soft_delete_now = Some(lit_str);
+                            };
                         }
-                        _ => {
-                            panic!("Error - Skip unknown name value");
+                        meta => {
+                            return Err(Error::new_spanned(
+                                meta.path(),
+                                "Unknown tiny_orm attribute",
+                            ));
                         }
                     }
                 }
+                if has_many_target.is_none()
+                    && (child_table.is_some()
+                        || child_soft_deletion.is_some()
+                        || child_fn.is_some())
+                {
+                    return Err(Error::new_spanned(
+                        attr,
+                        "The 'child_table', 'child_soft_deletion' and 'child_fn' keywords require a 'has_many'",
+                    ));
+                }
+                match (belongs_to_target, has_many_target, foreign_key) {
+                    (Some(target), Some(_), _) => {
+                        return Err(Error::new_spanned(
+                            target,
+                            "The 'belongs_to' and 'has_many' keywords cannot be used together",
+                        ));
+                    }
+                    (Some(target), None, Some(foreign_key)) => belongs_to.push(BelongsTo {
+                        target,
+                        foreign_key: Ident::new(foreign_key.value().trim(), foreign_key.span()),
+                    }),
+                    (None, Some(target), Some(foreign_key)) => has_many.push(HasMany {
+                        accessor: child_fn,
+                        ..HasMany::new(
+                            target,
+                            &foreign_key.value(),
+                            child_table,
+                            child_soft_deletion,
+                        )
+                    }),
+                    (Some(target), None, None) => {
+                        return Err(Error::new_spanned(
+                            target,
+                            "The 'belongs_to' keyword requires a 'foreign_key'",
+                        ));
+                    }
+                    (None, Some(target), None) => {
+                        return Err(Error::new_spanned(
+                            target,
+                            "The 'has_many' keyword requires a 'foreign_key'",
+                        ));
+                    }
+                    (None, None, Some(foreign_key)) => {
+                        return Err(Error::new_spanned(
+                            foreign_key,
+                            "The 'foreign_key' keyword requires a 'belongs_to' or a 'has_many'",
+                        ));
+                    }
+                    (None, None, None) => {}
+                }
             }
         }
 
-        let parsed_struct = ParsedStruct::new(struct_name, table_name, return_object);
-        let operations = Parser::get_operations(
-            only,
-            exclude,
-            add,
-            parsed_struct.struct_type.default_operation(),
-        )
-        .expect("Cannot parse the only/exclude operations properly.");
-
-        (parsed_struct, operations, soft_deletion)
-    }
+        if let Some(path) = all {
+            if only.is_some() {
+                return Err(Error::new_spanned(
+                    path,
+                    "The 'all' and 'only' keywords cannot be used together",
+                ));
+            }
+            add = Some(
+                Operation::all()
+                    .into_iter()
+                    .chain(add.unwrap_or_default())
+                    .collect(),
+            );
+        }
 
-    fn parse_fields_macro_arguments(data: Data) -> (Option<PrimaryKey>, Vec<Column>) {
-        let mut primary_key: Option<PrimaryKey> = None;
-        let mut columns = Vec::new();
+        if let Some(lit_str) = soft_delete_now {
+            match soft_deletion {
+                Some(ref mut soft_deletion) => soft_deletion
+                    .set_now(&lit_str.value())
+                    .map_err(|e| Error::new_spanned(&lit_str, e))?,
+                None => {
+                    return Err(Error::new_spanned(
+                        lit_str,
+                        "The 'soft_delete_now' keyword requires 'soft_deletion'",
+                    ));
+                }
+            }
+        }
 
-        match data {
-            Data::Struct(data_struct) => match &data_struct.fields {
-                Fields::Named(fields) => {
-                    for field in fields.named.iter() {
-                        let mut column = Column::new(
-                            &field
-                                .ident
-                                .as_ref()
-                                .expect("Field ident is expected to get its name")
-                                .to_string(),
-                            field.ty.clone(),
-                        );
+        if let Some(lit_str) = conflict_where {
+            conflict_target = match conflict_target {
+                Some(conflict_target) => Some(
+                    conflict_target
+                        .with_predicate(&lit_str.value())
+                        .map_err(|e| Error::new_spanned(&lit_str, e))?,
+                ),
+                None => {
+                    return Err(Error::new_spanned(
+                        lit_str,
+                        "The 'conflict_where' keyword requires 'conflict_target'",
+                    ));
+                }
+            };
+        }
 
-                        for attr in &field.attrs {
-                            if attr.path().is_ident(NAME_MACRO_OPERATION_ARG) {
-                                attr.parse_nested_meta(|meta| {
-                                    if meta.path.is_ident("primary_key") {
-                                        column.set_primary_key();
+        // `like` only borrows the name of the other struct, not its attributes
+        if let Some(like) = like {
+            table_name = table_name.or_else(|| Some(like.to_string()));
+            return_object = return_object.or(Some(like));
+        }
 
-                                        if meta.input.peek(Paren) {
-                                            let content;
-                                            parenthesized!(content in meta.input);
+        let mut parsed_struct = ParsedStruct::new(struct_name, table_name, return_object);
+        parsed_struct.return_nothing = return_nothing;
+        parsed_struct.sqlite_no_returning = cfg!(feature = "sqlite-no-returning");
+        parsed_struct.returning = returning;
+        parsed_struct.scope = scope;
+        parsed_struct.inject = inject;
+        parsed_struct.order_by = order_by;
+        parsed_struct.conflict_target = conflict_target;
+        parsed_struct.delete_returning = delete_returning;
+        parsed_struct.returning_generated = returning_generated;
+        parsed_struct.env_vars = env_vars;
+        parsed_struct.skip_defaults = skip_defaults;
+        parsed_struct.from_row = from_row;
+        parsed_struct.table_name_fn = table_name_fn;
+        parsed_struct.error = error;
+        parsed_struct.database = database;
+        parsed_struct.belongs_to = belongs_to;
+        // The associations can be declared after `cascade_delete`
+        for lit_str in cascade_delete {
+            for name in lit_str.value().split(',').map(str::trim) {
+                match has_many
+                    .iter_mut()
+                    .find(|has_many| has_many.fn_name() == name)
+                {
+                    Some(has_many) => has_many.cascade_delete = true,
+                    None => {
+                        return Err(Error::new_spanned(
+                            &lit_str,
+                            format!(
+                                "No has_many named '{name}' found for the 'cascade_delete' keyword"
+                            ),
+                        ))
+                    }
+                }
+            }
+        }
+        parsed_struct.has_many = has_many;
+        parsed_struct.projections = projections;
+        if let Some(table_prefix) = table_prefix {
+            parsed_struct.table_name = parsed_struct.table_name.with_prefix(&table_prefix);
+        }
+        // A view is read-only
+        let default_operations = match view {
+            Some(_) => vec![Operation::Get, Operation::List],
+            None => parsed_struct.struct_type.default_operation(),
+        };
+        let operations =
+            Parser::get_operations(only, exclude, add, default_operations).map_err(|e| {
+                match last_attr {
+                    Some(attr) => Error::new_spanned(attr, e),
+                    None => Error::new_spanned(struct_name, e),
+                }
+            })?;
+        if let Some(path) = view {
+            if operations.iter().any(|operation| {
+                matches!(
+                    operation,
+                    Operation::Create | Operation::Update | Operation::Delete
+                )
+            }) {
+                return Err(Error::new_spanned(
+                    path,
+                    "A 'view' is read-only, only the 'get' and 'list' operations can be used",
+                ));
+            }
+        }
+
+        Ok(StructArguments {
+            parsed_struct,
+            operations,
+            soft_deletion,
+            rename_all,
+            primary_key_field,
+            lookup_key,
+            cursor,
+            discriminator,
+        })
+    }
+
+    /// A string literal or an `env!("VAR")` read when the macro is expanded, the name of the variable being kept in `env_vars`
+    fn parse_string_value(value: &Expr, env_vars: &mut Vec<LitStr>) -> syn::Result<String> {
+        match value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(lit_str),
+                ..
+            }) => Ok(lit_str.value()),
+            Expr::Macro(expr_macro) if expr_macro.mac.path.is_ident("env") => {
+                let var_name: LitStr = expr_macro.mac.parse_body()?;
+                env_vars.push(var_name.clone());
+                std::env::var(var_name.value()).map_err(|_| {
+                    Error::new_spanned(
+                        &var_name,
+                        format!(
+                            "The environment variable '{}' is not defined",
+                            var_name.value()
+                        ),
+                    )
+                })
+            }
+            _ => Err(Error::new_spanned(
+                value,
+                "Expected a string literal or env!(\"...\")",
+            )),
+        }
+    }
+
+    fn merge_operations(operations: &mut Option<Operations>, other: Operations) {
+        let operations = operations.get_or_insert_with(Vec::new);
+        for operation in other {
+            if !operations.contains(&operation) {
+                operations.push(operation);
+            }
+        }
+    }
+
+    fn parse_operations(value: &Expr) -> syn::Result<Operations> {
+        match value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(lit_str),
+                ..
+            }) => lit_str
+                .value()
+                .split(',')
+                .map(|s| {
+                    Operation::from_str(s.trim()).map_err(|_| {
+                        Error::new_spanned(
+                            lit_str,
+                            format!("Operation '{}' is not supported", s.trim()),
+                        )
+                    })
+                })
+                .collect(),
+            _ => Err(Error::new_spanned(
+                value,
+                "Expected a string of comma separated operations",
+            )),
+        }
+    }
+
+    /// A field with `#[sqlx(skip)]` is not a column, `FromRow` sets it to its `Default::default()` value
+    fn is_skipped(field: &Field) -> bool {
+        let mut skip = false;
+        for attr in &field.attrs {
+            if attr.path().is_ident(NAME_SQLX_ARG) {
+                let _ = attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("skip") {
+                        skip = true;
+                    } else if meta.input.peek(Token![=]) {
+                        let _: Expr = meta.value()?.parse()?;
+                    }
+                    Ok(())
+                });
+            }
+        }
+        skip
+    }
+
+    /// The column name given to `FromRow` with `#[sqlx(rename = "...")]`
+    fn sqlx_rename(field: &Field) -> syn::Result<Option<String>> {
+        let mut rename = None;
+        for attr in &field.attrs {
+            if attr.path().is_ident(NAME_SQLX_ARG) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("rename") {
+                        let lit_str: LitStr = meta.value()?.parse()?;
+                        rename = Some(lit_str.value().trim().to_string());
+                    } else if meta.input.peek(Token![=]) {
+                        let _: Expr = meta.value()?.parse()?;
+                    } else if meta.input.peek(Paren) {
+                        // eg: `#[sqlx(json(nullable))]`
+                        meta.parse_nested_meta(|_| Ok(()))?;
+                    }
+                    Ok(())
+                })?;
+            }
+        }
+        Ok(rename)
+    }
+
+    fn skipped_fields(data: &Data) -> Vec<Ident> {
+        match data {
+            Data::Struct(data_struct) => data_struct
+                .fields
+                .iter()
+                .filter(|field| Self::is_skipped(field))
+                .filter_map(|field| field.ident.clone())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Every variant of an enum mapped to a single table must have named fields and the same primary key
+    fn parse_variants(
+        data_enum: DataEnum,
+        rename_all: Option<RenameAll>,
+        primary_key_field: Option<LitStr>,
+    ) -> syn::Result<(Option<PrimaryKey>, Vec<Column>, Vec<Variant>)> {
+        if data_enum.variants.is_empty() {
+            return Err(Error::new_spanned(
+                data_enum.enum_token,
+                "An enum requires at least one variant",
+            ));
+        }
+        let mut primary_key: Option<PrimaryKey> = None;
+        let mut columns: Vec<Column> = Vec::new();
+        let mut variants = Vec::new();
+
+        for variant in data_enum.variants {
+            if !matches!(variant.fields, Fields::Named(_)) {
+                return Err(Error::new_spanned(
+                    &variant,
+                    "Only variants with named fields are supported",
+                ));
+            }
+            let data = Data::Struct(DataStruct {
+                struct_token: Default::default(),
+                fields: variant.fields,
+                semi_token: None,
+            });
+            let skipped_fields = Self::skipped_fields(&data);
+            let (variant_primary_key, variant_columns) =
+                Self::parse_fields_macro_arguments(data, rename_all, primary_key_field.clone())?;
+            match (&primary_key, variant_primary_key) {
+                (_, None) => {
+                    return Err(Error::new_spanned(
+                        &variant.ident,
+                        "Every variant requires the primary key; add `#[tiny_orm(primary_key)]` or an `id` field.",
+                    ))
+                }
+                (Some(primary_key), Some(variant_primary_key))
+                    if primary_key.name != variant_primary_key.name =>
+                {
+                    return Err(Error::new_spanned(
+                        &variant.ident,
+                        format!(
+                            "Every variant requires the same primary key '{}'",
+                            primary_key.name
+                        ),
+                    ))
+                }
+                (None, Some(variant_primary_key)) => primary_key = Some(variant_primary_key),
+                _ => {}
+            }
+            for column in &variant_columns {
+                if !columns.iter().any(|other| other.name == column.name) {
+                    columns.push(column.clone());
+                }
+            }
+            variants.push(Variant::new(
+                &variant.ident,
+                variant_columns,
+                skipped_fields,
+            ));
+        }
+        Ok((primary_key, columns, variants))
+    }
+
+    fn parse_fields_macro_arguments(
+        data: Data,
+        rename_all: Option<RenameAll>,
+        primary_key_field: Option<LitStr>,
+    ) -> syn::Result<(Option<PrimaryKey>, Vec<Column>)> {
+        let default_primary_key = primary_key_field
+            .as_ref()
+            .map(|lit_str| lit_str.value().trim().to_string())
+            .unwrap_or("id".to_string());
+        let mut primary_key: Option<PrimaryKey> = None;
+        let mut columns = Vec::new();
+
+        match data {
+            Data::Struct(data_struct) => match &data_struct.fields {
+                Fields::Named(fields) => {
+                    for field in fields.named.iter() {
+                        if Self::is_skipped(field) {
+                            if let Some(attr) = field
+                                .attrs
+                                .iter()
+                                .find(|attr| attr.path().is_ident(NAME_MACRO_OPERATION_ARG))
+                            {
+                                return Err(Error::new_spanned(
+                                    attr,
+                                    "A field with `#[sqlx(skip)]` is not a column and cannot have tiny_orm attributes",
+                                ));
+                            }
+                            continue;
+                        }
+
+                        let mut column = Column::new(
+                            &field
+                                .ident
+                                .as_ref()
+                                .expect("Field ident is expected to get its name")
+                                .to_string(),
+                            field.ty.clone(),
+                        );
+                        if let Some(rename_all) = rename_all {
+                            column.set_name(&rename_all.apply(&column.name));
+                        }
+                        // `column` still takes precedence over the name given to `FromRow`
+                        if let Some(rename) = Self::sqlx_rename(field)? {
+                            column.set_name(&rename);
+                        }
+
+                        for attr in &field.attrs {
+                            if attr.path().is_ident(NAME_MACRO_OPERATION_ARG) {
+                                attr.parse_nested_meta(|meta| {
+                                    if meta.path.is_ident("primary_key") {
+                                        column.set_primary_key();
+
+                                        if meta.input.peek(Paren) {
+                                            let content;
+                                            parenthesized!(content in meta.input);
 
                                             let ident: Option<Ident> = content.parse().ok();
                                             if let Some(ident) = ident {
                                                 if ident == "auto" {
                                                     column.set_auto_increment();
+                                                } else if ident == "uuid" {
+                                                    if !cfg!(feature = "uuid") {
+                                                        return Err(Error::new_spanned(
+                                                            ident,
+                                                            "The `uuid` feature is required for `primary_key(uuid)`",
+                                                        ));
+                                                    }
+                                                    column.set_uuid();
+                                                } else if ident == "sequence" {
+                                                    content.parse::<Token![=]>()?;
+                                                    let lit_str: LitStr = content.parse()?;
+                                                    column
+                                                        .set_sequence(lit_str.value().trim())
+                                                        .map_err(|error| Error::new_spanned(&lit_str, error))?;
                                                 }
                                             }
                                         }
                                         primary_key = Some(column.clone());
+                                    } else if meta.path.is_ident("db_default") {
+                                        column.set_db_default();
+                                    } else if meta.path.is_ident("bulk_updatable") {
+                                        column.set_bulk_updatable();
+                                    } else if meta.path.is_ident("readonly") {
+                                        column.set_readonly();
+                                    } else if meta.path.is_ident("unique") {
+                                        column.set_unique();
+                                    } else if meta.path.is_ident("filterable") {
+                                        column.set_filterable();
+                                    } else if meta.path.is_ident("json") {
+                                        column.set_json();
+                                    } else if meta.path.is_ident("transform") {
+                                        let lit_str: LitStr = meta.value()?.parse()?;
+                                        column.set_transform(lit_str.parse()?);
+                                    } else if meta.path.is_ident("column") {
+                                        let lit_str: LitStr = meta.value()?.parse()?;
+                                        column.set_name(lit_str.value().trim());
+                                        if column.primary_key {
+                                            primary_key = Some(column.clone());
+                                        }
+                                    } else {
+                                        return Err(meta.error("Unknown tiny_orm field attribute"));
                                     }
                                     Ok(())
-                                })
-                                .unwrap_or(());
+                                })?;
                             }
                         }
 
+                        if column.readonly && column.bulk_updatable {
+                            return Err(Error::new_spanned(
+                                field,
+                                "The 'readonly' and 'bulk_updatable' attributes cannot be used together",
+                            ));
+                        }
+
+                        if column.json && column.transform.is_some() {
+                            return Err(Error::new_spanned(
+                                field,
+                                "The 'json' and 'transform' attributes cannot be used together",
+                            ));
+                        }
+
                         // Default fallbacks
-                        if &column.name == "id" && primary_key.is_none() {
+                        if column.ident == default_primary_key && primary_key.is_none() {
                             column.set_primary_key();
                             primary_key = Some(column.clone());
                         }
 
                         columns.push(column);
                     }
+
+                    if let Some(lit_str) = primary_key_field {
+                        if !columns
+                            .iter()
+                            .any(|column| column.ident == default_primary_key)
+                        {
+                            return Err(Error::new_spanned(
+                                lit_str,
+                                format!("No field named '{default_primary_key}' found for the 'primary_key_field' keyword"),
+                            ));
+                        }
+                    }
+                }
+                fields => {
+                    return Err(Error::new_spanned(
+                        fields,
+                        "Only named fields are supported",
+                    ))
                 }
-                _ => panic!("Only named fields are supported"),
             },
-            _ => panic!("Only structs are supported"),
+            Data::Enum(data_enum) => {
+                return Err(Error::new_spanned(
+                    data_enum.enum_token,
+                    "Only structs are supported",
+                ))
+            }
+            Data::Union(data_union) => {
+                return Err(Error::new_spanned(
+                    data_union.union_token,
+                    "Only structs are supported",
+                ))
+            }
         };
-        (primary_key, columns)
+        Ok((primary_key, columns))
     }
 
     fn get_operations(
@@ -404,18 +1341,26 @@ mod tests {
     }
 
     mod parse_struct_macro_arguments {
-        use quote::format_ident;
+        use quote::{format_ident, quote};
         use syn::parse_quote;
 
-        use crate::attr::Parser;
-        use crate::types::{Operation, StructType};
+        use crate::attr::{Parser, StructArguments};
+        use crate::database::DbType;
+        use crate::types::{
+            BelongsTo, ConflictTarget, HasMany, Inject, Operation, OrderBy, Projection, RenameAll,
+            Returning, Scope, SoftDeletion, StructType, TableName,
+        };
 
         #[test]
         fn test_parse_only_attribute_alone() {
             let struct_name = format_ident!("MyStruct");
             let attrs = vec![parse_quote!(#[tiny_orm(only = "create,get")])];
-            let (parsed_struct, operations, soft_deletion) =
-                Parser::parse_struct_macro_arguments(&struct_name, &attrs);
+            let StructArguments {
+                parsed_struct,
+                operations,
+                soft_deletion,
+                ..
+            } = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
             assert_eq!(parsed_struct.name.to_string(), "MyStruct".to_string());
             assert_eq!(parsed_struct.struct_type, StructType::Generic);
             assert_eq!(
@@ -424,15 +1369,19 @@ mod tests {
             );
             assert_eq!(parsed_struct.return_object, format_ident!("Self"));
             assert_eq!(operations, vec![Operation::Create, Operation::Get]);
-            assert!(!soft_deletion);
+            assert_eq!(soft_deletion, None);
         }
 
         #[test]
         fn test_parse_exclude_attribute_alone() {
             let struct_name = format_ident!("MyStruct");
             let attrs = vec![parse_quote!(#[tiny_orm(exclude = "create,get")])];
-            let (parsed_struct, mut operations, soft_deletion) =
-                Parser::parse_struct_macro_arguments(&struct_name, &attrs);
+            let StructArguments {
+                parsed_struct,
+                mut operations,
+                soft_deletion,
+                ..
+            } = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
             operations.sort();
 
             assert_eq!(parsed_struct.name.to_string(), "MyStruct".to_string());
@@ -443,15 +1392,19 @@ mod tests {
             );
             assert_eq!(parsed_struct.return_object, format_ident!("Self"));
             assert_eq!(operations, vec![Operation::List, Operation::Delete]);
-            assert!(!soft_deletion);
+            assert_eq!(soft_deletion, None);
         }
 
         #[test]
         fn test_parse_table_name_attribute_alone() {
             let struct_name = format_ident!("MyStruct");
             let attrs = vec![parse_quote!(#[tiny_orm(table_name = "custom_name")])];
-            let (parsed_struct, mut operations, soft_deletion) =
-                Parser::parse_struct_macro_arguments(&struct_name, &attrs);
+            let StructArguments {
+                parsed_struct,
+                mut operations,
+                soft_deletion,
+                ..
+            } = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
             operations.sort();
             assert_eq!(parsed_struct.name.to_string(), "MyStruct".to_string());
             assert_eq!(parsed_struct.struct_type, StructType::Generic);
@@ -464,15 +1417,268 @@ mod tests {
                 operations,
                 vec![Operation::Get, Operation::List, Operation::Delete]
             );
-            assert!(!soft_deletion);
+            assert_eq!(soft_deletion, None);
+        }
+
+        #[test]
+        fn test_parse_like_attribute() {
+            let struct_name = format_ident!("TodoDraft");
+            let attrs = vec![parse_quote!(#[tiny_orm(like = "TodoItem")])];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(parsed_struct.table_name.0, "todo_item".to_string());
+            assert_eq!(parsed_struct.struct_type, StructType::Generic);
+            assert_eq!(parsed_struct.return_object, format_ident!("TodoItem"));
+        }
+
+        #[test]
+        fn test_parse_like_attribute_with_explicit_values() {
+            let struct_name = format_ident!("NewTodo");
+            let attrs = vec![
+                parse_quote!(#[tiny_orm(like = "Todo", table_name = "todos", table_prefix = "app_")]),
+            ];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(parsed_struct.table_name.0, "app_todos".to_string());
+            assert_eq!(parsed_struct.struct_type, StructType::Create);
+            assert_eq!(parsed_struct.return_object, format_ident!("Todo"));
+
+            let attrs =
+                vec![parse_quote!(#[tiny_orm(like = "Todo", return_object = "TodoRecord")])];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(parsed_struct.table_name.0, "todo".to_string());
+            assert_eq!(parsed_struct.return_object, format_ident!("TodoRecord"));
+        }
+
+        #[test]
+        fn test_parse_table_name_fn() {
+            let struct_name = format_ident!("Invoice");
+            let attrs = vec![parse_quote!(#[tiny_orm(table_name_fn = "orm_table")])];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(
+                parsed_struct.table_name_fn,
+                Some(format_ident!("orm_table"))
+            );
+
+            let attrs = vec![parse_quote!(#[tiny_orm(table_name = "invoice")])];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(parsed_struct.table_name_fn, None);
+
+            let attrs = vec![parse_quote!(#[tiny_orm(table_name_fn = "orm-table")])];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "Failed to parse table_name_fn as identifier"
+            );
+        }
+
+        #[test]
+        fn test_parse_qualified_table_name() {
+            let struct_name = format_ident!("Invoice");
+            let attrs = vec![parse_quote!(#[tiny_orm(table_name = "Billing.InvoiceItem")])];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(
+                parsed_struct.table_name.0,
+                "billing.invoice_item".to_string()
+            );
+
+            let attrs = vec![
+                parse_quote!(#[tiny_orm(table_name = "billing.invoice", table_prefix = "tenant_")]),
+            ];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(
+                parsed_struct.table_name.0,
+                "billing.tenant_invoice".to_string()
+            );
+        }
+
+        #[test]
+        fn test_cannot_parse_invalid_like_attribute() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm(like = "my-struct")])];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(error.to_string(), "Failed to parse like as identifier");
+        }
+
+        #[test]
+        fn test_parse_belongs_to_attribute() {
+            let struct_name = format_ident!("Todo");
+            let attrs = vec![
+                parse_quote!(#[tiny_orm(all)]),
+                parse_quote!(#[tiny_orm(belongs_to = "User", foreign_key = "user_id")]),
+                parse_quote!(#[tiny_orm(belongs_to = "Project", foreign_key = " project_id ")]),
+            ];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(
+                parsed_struct.belongs_to,
+                vec![
+                    BelongsTo {
+                        target: format_ident!("User"),
+                        foreign_key: format_ident!("user_id"),
+                    },
+                    BelongsTo {
+                        target: format_ident!("Project"),
+                        foreign_key: format_ident!("project_id"),
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn test_cannot_parse_belongs_to_without_foreign_key() {
+            let struct_name = format_ident!("Todo");
+            let attrs = vec![
+                parse_quote!(#[tiny_orm(belongs_to = "User")]),
+                parse_quote!(#[tiny_orm(foreign_key = "user_id")]),
+            ];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "The 'belongs_to' keyword requires a 'foreign_key'"
+            );
+
+            let attrs = vec![parse_quote!(#[tiny_orm(foreign_key = "user_id")])];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "The 'foreign_key' keyword requires a 'belongs_to' or a 'has_many'"
+            );
+        }
+
+        #[test]
+        fn test_parse_has_many_attribute() {
+            let struct_name = format_ident!("User");
+            let attrs = vec![
+                parse_quote!(#[tiny_orm(has_many = "TodoItem", foreign_key = "user_id")]),
+                parse_quote!(#[tiny_orm(has_many = "Post", foreign_key = "author_id", child_table = "blog_post", child_soft_deletion)]),
+                parse_quote!(#[tiny_orm(has_many = "Comment", foreign_key = "user_id", child_soft_deletion = "archived_at")]),
+                parse_quote!(#[tiny_orm(has_many = "Category", foreign_key = "user_id", child_fn = "categories")]),
+            ];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(
+                parsed_struct.has_many,
+                vec![
+                    HasMany {
+                        target: format_ident!("TodoItem"),
+                        foreign_key: "user_id".to_string(),
+                        table_name: TableName("todo_item".to_string()),
+                        soft_deletion: None,
+                        cascade_delete: false,
+                        accessor: None,
+                    },
+                    HasMany {
+                        target: format_ident!("Post"),
+                        foreign_key: "author_id".to_string(),
+                        table_name: TableName("blog_post".to_string()),
+                        soft_deletion: Some(SoftDeletion::default()),
+                        cascade_delete: false,
+                        accessor: None,
+                    },
+                    HasMany {
+                        target: format_ident!("Comment"),
+                        foreign_key: "user_id".to_string(),
+                        table_name: TableName("comment".to_string()),
+                        soft_deletion: Some(SoftDeletion::new("archived_at")),
+                        cascade_delete: false,
+                        accessor: None,
+                    },
+                    HasMany {
+                        target: format_ident!("Category"),
+                        foreign_key: "user_id".to_string(),
+                        table_name: TableName("category".to_string()),
+                        soft_deletion: None,
+                        cascade_delete: false,
+                        accessor: Some(format_ident!("categories")),
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn test_parse_cascade_delete_attribute() {
+            let struct_name = format_ident!("User");
+            let attrs = vec![
+                parse_quote!(#[tiny_orm(cascade_delete = "todo_items, posts")]),
+                parse_quote!(#[tiny_orm(has_many = "TodoItem", foreign_key = "user_id")]),
+                parse_quote!(#[tiny_orm(has_many = "Post", foreign_key = "author_id")]),
+                parse_quote!(#[tiny_orm(has_many = "Comment", foreign_key = "user_id")]),
+            ];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(
+                parsed_struct
+                    .has_many
+                    .iter()
+                    .map(|has_many| has_many.cascade_delete)
+                    .collect::<Vec<bool>>(),
+                vec![true, true, false]
+            );
+
+            let attrs = vec![
+                parse_quote!(#[tiny_orm(has_many = "TodoItem", foreign_key = "user_id")]),
+                parse_quote!(#[tiny_orm(cascade_delete = "todos")]),
+            ];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "No has_many named 'todos' found for the 'cascade_delete' keyword"
+            );
+        }
+
+        #[test]
+        fn test_cannot_parse_invalid_has_many_attribute() {
+            let struct_name = format_ident!("User");
+            let attrs = vec![parse_quote!(#[tiny_orm(has_many = "Todo")])];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "The 'has_many' keyword requires a 'foreign_key'"
+            );
+
+            let attrs = vec![
+                parse_quote!(#[tiny_orm(has_many = "Todo", belongs_to = "Team", foreign_key = "user_id")]),
+            ];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "The 'belongs_to' and 'has_many' keywords cannot be used together"
+            );
+
+            let attrs = vec![parse_quote!(#[tiny_orm(all, child_table = "todo")])];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "The 'child_table', 'child_soft_deletion' and 'child_fn' keywords require a 'has_many'"
+            );
+        }
+
+        #[test]
+        fn test_parse_return_nothing() {
+            let struct_name = format_ident!("NewEvent");
+            let attrs = vec![parse_quote!(#[tiny_orm(return_object = "()")])];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert!(parsed_struct.return_nothing);
+            assert_eq!(parsed_struct.return_object, format_ident!("Event"));
         }
 
         #[test]
         fn test_parse_return_object_attribute_alone() {
             let struct_name = format_ident!("MyStruct");
             let attrs = vec![parse_quote!(#[tiny_orm(return_object = "Operation")])];
-            let (parsed_struct, mut operations, soft_deletion) =
-                Parser::parse_struct_macro_arguments(&struct_name, &attrs);
+            let StructArguments {
+                parsed_struct,
+                mut operations,
+                soft_deletion,
+                ..
+            } = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
             operations.sort();
             assert_eq!(
                 parsed_struct.table_name.0.to_string(),
@@ -484,7 +1690,7 @@ mod tests {
                 operations,
                 vec![Operation::Get, Operation::List, Operation::Delete]
             );
-            assert!(!soft_deletion);
+            assert_eq!(soft_deletion, None);
         }
 
         #[test]
@@ -493,13 +1699,17 @@ mod tests {
             let attrs = vec![
                 parse_quote!(#[tiny_orm(table_name = "custom", return_object = "Operation", only = "create")]),
             ];
-            let (parsed_struct, mut operations, soft_deletion) =
-                Parser::parse_struct_macro_arguments(&struct_name, &attrs);
+            let StructArguments {
+                parsed_struct,
+                mut operations,
+                soft_deletion,
+                ..
+            } = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
             operations.sort();
             assert_eq!(parsed_struct.table_name.0.to_string(), "custom".to_string());
             assert_eq!(parsed_struct.return_object, format_ident!("Operation"));
             assert_eq!(operations, vec![Operation::Create]);
-            assert!(!soft_deletion);
+            assert_eq!(soft_deletion, None);
         }
 
         #[test]
@@ -508,21 +1718,29 @@ mod tests {
             let attrs = vec![
                 parse_quote!(#[tiny_orm(table_name = "   custom ", return_object = "   Operation  ", only = "  create   ,  delete")]),
             ];
-            let (parsed_struct, mut operations, soft_deletion) =
-                Parser::parse_struct_macro_arguments(&struct_name, &attrs);
+            let StructArguments {
+                parsed_struct,
+                mut operations,
+                soft_deletion,
+                ..
+            } = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
             operations.sort();
             assert_eq!(parsed_struct.table_name.0.to_string(), "custom".to_string());
             assert_eq!(parsed_struct.return_object, format_ident!("Operation"));
             assert_eq!(operations, vec![Operation::Create, Operation::Delete]);
-            assert!(!soft_deletion);
+            assert_eq!(soft_deletion, None);
         }
 
         #[test]
         fn test_default_parse_create_type_of_struct() {
             let struct_name = format_ident!("NewMyStruct");
             let attrs = vec![parse_quote!(#[tiny_orm()])];
-            let (parsed_struct, mut operations, soft_deletion) =
-                Parser::parse_struct_macro_arguments(&struct_name, &attrs);
+            let StructArguments {
+                parsed_struct,
+                mut operations,
+                soft_deletion,
+                ..
+            } = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
             operations.sort();
             assert_eq!(parsed_struct.name.to_string(), "NewMyStruct".to_string());
             assert_eq!(
@@ -532,15 +1750,19 @@ mod tests {
             assert_eq!(parsed_struct.struct_type, StructType::Create);
             assert_eq!(parsed_struct.return_object, format_ident!("MyStruct"));
             assert_eq!(operations, vec![Operation::Create]);
-            assert!(!soft_deletion);
+            assert_eq!(soft_deletion, None);
         }
 
         #[test]
         fn test_default_parse_update_type_of_struct() {
             let struct_name = format_ident!("UpdateMyStruct");
             let attrs = vec![parse_quote!(#[tiny_orm()])];
-            let (parsed_struct, mut operations, soft_deletion) =
-                Parser::parse_struct_macro_arguments(&struct_name, &attrs);
+            let StructArguments {
+                parsed_struct,
+                mut operations,
+                soft_deletion,
+                ..
+            } = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
             operations.sort();
             assert_eq!(parsed_struct.name.to_string(), "UpdateMyStruct".to_string());
             assert_eq!(
@@ -550,7 +1772,7 @@ mod tests {
             assert_eq!(parsed_struct.struct_type, StructType::Update);
             assert_eq!(parsed_struct.return_object, format_ident!("MyStruct"));
             assert_eq!(operations, vec![Operation::Update]);
-            assert!(!soft_deletion);
+            assert_eq!(soft_deletion, None);
         }
 
         #[test]
@@ -559,15 +1781,19 @@ mod tests {
             let attrs = vec![
                 parse_quote!(#[tiny_orm(table_name = "   custom ", return_object = "   Operation  ", only = "  create   ,  delete")]),
             ];
-            let (parsed_struct, mut operations, soft_deletion) =
-                Parser::parse_struct_macro_arguments(&struct_name, &attrs);
+            let StructArguments {
+                parsed_struct,
+                mut operations,
+                soft_deletion,
+                ..
+            } = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
             operations.sort();
             assert_eq!(parsed_struct.name.to_string(), "NewMyStruct".to_string());
             assert_eq!(parsed_struct.table_name.0.to_string(), "custom".to_string());
             assert_eq!(parsed_struct.struct_type, StructType::Create);
             assert_eq!(parsed_struct.return_object, format_ident!("Operation"));
             assert_eq!(operations, vec![Operation::Create, Operation::Delete]);
-            assert!(!soft_deletion);
+            assert_eq!(soft_deletion, None);
         }
 
         #[test]
@@ -576,229 +1802,1204 @@ mod tests {
             let attrs = vec![
                 parse_quote!(#[tiny_orm(table_name = "   custom ", return_object = "   Operation  ", only = "  create   ,  delete", soft_deletion)]),
             ];
-            let (parsed_struct, mut operations, soft_deletion) =
-                Parser::parse_struct_macro_arguments(&struct_name, &attrs);
+            let StructArguments {
+                parsed_struct,
+                mut operations,
+                soft_deletion,
+                ..
+            } = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
             operations.sort();
             assert_eq!(parsed_struct.name.to_string(), "UpdateMyStruct".to_string());
             assert_eq!(parsed_struct.table_name.0.to_string(), "custom".to_string());
             assert_eq!(parsed_struct.struct_type, StructType::Update);
             assert_eq!(parsed_struct.return_object, format_ident!("Operation"));
             assert_eq!(operations, vec![Operation::Create, Operation::Delete]);
-            assert!(soft_deletion);
+            assert_eq!(soft_deletion, Some(SoftDeletion::default()));
         }
 
         #[test]
         fn test_return_all_operations_for_generic_struct() {
             let struct_name = format_ident!("MyStruct");
             let attrs = vec![parse_quote!(#[tiny_orm(all, table_name = "custom")])];
-            let (parsed_struct, mut operations, soft_deletion) =
-                Parser::parse_struct_macro_arguments(&struct_name, &attrs);
+            let StructArguments {
+                parsed_struct,
+                mut operations,
+                soft_deletion,
+                ..
+            } = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
             operations.sort();
             assert_eq!(parsed_struct.name.to_string(), "MyStruct".to_string());
             assert_eq!(parsed_struct.table_name.0.to_string(), "custom".to_string());
             assert_eq!(operations, Operation::all());
-            assert!(!soft_deletion);
+            assert_eq!(soft_deletion, None);
         }
 
         #[test]
         fn test_return_all_operations_for_update_struct() {
             let struct_name = format_ident!("NewMyStruct");
             let attrs = vec![parse_quote!(#[tiny_orm(all, soft_deletion)])];
-            let (parsed_struct, mut operations, soft_deletion) =
-                Parser::parse_struct_macro_arguments(&struct_name, &attrs);
+            let StructArguments {
+                parsed_struct,
+                mut operations,
+                soft_deletion,
+                ..
+            } = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
             operations.sort();
             assert_eq!(parsed_struct.struct_type, StructType::Create);
             assert_eq!(operations, Operation::all());
-            assert!(soft_deletion);
+            assert_eq!(soft_deletion, Some(SoftDeletion::default()));
         }
 
         #[test]
         fn test_return_all_operations_for_create_struct() {
             let struct_name = format_ident!("UpdateMyStruct");
             let attrs = vec![parse_quote!(#[tiny_orm(all)])];
-            let (parsed_struct, mut operations, soft_deletion) =
-                Parser::parse_struct_macro_arguments(&struct_name, &attrs);
+            let StructArguments {
+                parsed_struct,
+                mut operations,
+                soft_deletion,
+                ..
+            } = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
             operations.sort();
             assert_eq!(parsed_struct.struct_type, StructType::Update);
             assert_eq!(operations, Operation::all());
-            assert!(!soft_deletion);
+            assert_eq!(soft_deletion, None);
         }
 
         #[test]
-        #[should_panic]
-        fn test_cannot_pass_all_with_only() {
+        fn test_parse_soft_deletion_with_custom_column() {
             let struct_name = format_ident!("MyStruct");
-            let attrs = vec![parse_quote!(#[tiny_orm(all, only = "create")])];
-            let _ = Parser::parse_struct_macro_arguments(&struct_name, &attrs);
+            let attrs = vec![parse_quote!(#[tiny_orm(soft_deletion = "archived_at")])];
+            let StructArguments { soft_deletion, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(soft_deletion, Some(SoftDeletion::new("archived_at")));
+            assert_eq!(soft_deletion.unwrap().column, "archived_at".to_string());
         }
 
         #[test]
-        fn test_pass_all_with_exclude() {
+        fn test_parse_soft_deletion_defaults_to_deleted_at() {
             let struct_name = format_ident!("MyStruct");
-            let attrs = vec![parse_quote!(#[tiny_orm(all, exclude = "delete")])];
-            let (parsed_struct, mut operations, soft_deletion) =
-                Parser::parse_struct_macro_arguments(&struct_name, &attrs);
-            operations.sort();
-            assert_eq!(parsed_struct.struct_type, StructType::Generic);
-            assert_eq!(
-                operations,
-                vec![
-                    Operation::Get,
-                    Operation::List,
-                    Operation::Create,
-                    Operation::Update
-                ]
-            );
-            assert!(!soft_deletion);
+            let attrs = vec![parse_quote!(#[tiny_orm(soft_deletion)])];
+            let StructArguments { soft_deletion, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(soft_deletion.unwrap().column, "deleted_at".to_string());
         }
-    }
-
-    mod parse_fields_macro_arguments {
-        use syn::{parse_quote, DeriveInput};
-
-        use crate::attr::{Column, Parser};
 
         #[test]
-        fn test_parse_default() {
-            let input: DeriveInput = parse_quote! {
-                struct Contact {
-                    id: i64,
-                    created_at: DateTime<Utc>,
-                    updated_at: DateTime<Utc>,
-                    last_name: String,
-                }
-            };
+        fn test_parse_soft_deletion_with_boolean_column() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm(soft_deletion(bool = "is_deleted"))])];
+            let StructArguments { soft_deletion, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(soft_deletion, Some(SoftDeletion::new_bool("is_deleted")));
+            assert_eq!(soft_deletion.unwrap().condition(), "is_deleted = false");
+
+            let attrs = vec![parse_quote!(#[tiny_orm(soft_deletion(column = "is_deleted"))])];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "Expected `soft_deletion(bool = \"column\")`"
+            );
 
-            let (primary_key, field_names) = Parser::parse_fields_macro_arguments(input.data);
-            let mut expected_pk = Column::new("id", parse_quote!(i64));
-            expected_pk.set_primary_key();
-            assert_eq!(primary_key, Some(expected_pk.clone()));
+            let attrs = vec![parse_quote!(
+                #[tiny_orm(soft_deletion(bool = "is_deleted"), soft_delete_now = "clock_timestamp()")]
+            )];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
             assert_eq!(
-                field_names,
-                vec![
-                    expected_pk,
-                    Column::new("created_at", parse_quote!(DateTime<Utc>)),
-                    Column::new("updated_at", parse_quote!(DateTime<Utc>)),
-                    Column::new("last_name", parse_quote!(String))
-                ]
+                error.to_string(),
+                "The 'soft_delete_now' keyword cannot be used with a boolean 'soft_deletion'"
             );
         }
 
         #[test]
-        fn test_parse_no_default_columns() {
-            let input: DeriveInput = parse_quote! {
-                struct Contact {
-                    last_name: String,
-                }
-            };
-
-            let (primary_key, field_names) = Parser::parse_fields_macro_arguments(input.data);
-            assert_eq!(primary_key, None);
+        fn test_parse_returning_attribute() {
+            let struct_name = format_ident!("UpdateMyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm(returning = "id, created_at")])];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
             assert_eq!(
-                field_names,
-                vec![Column::new("last_name", parse_quote!(String))]
+                parsed_struct.returning,
+                Some(Returning::new("id,created_at").unwrap())
             );
         }
 
         #[test]
-        fn test_parse_override_default_values() {
-            let input: DeriveInput = parse_quote! {
-                struct Contact {
-                    #[tiny_orm(primary_key)]
-                    custom_key: u32,
-                    inserted_at: DateTime<Utc>,
-                    something_at: DateTime<Utc>,
-                    last_name: String,
-                }
-            };
+        fn test_parse_scope_attribute() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm(scope = "archived = false")])];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(
+                parsed_struct.scope,
+                Some(Scope::new("archived = false").unwrap())
+            );
 
-            let (primary_key, field_names) = Parser::parse_fields_macro_arguments(input.data);
-            let mut expected_pk = Column::new("custom_key", parse_quote!(u32));
-            expected_pk.set_primary_key();
-            assert_eq!(primary_key, Some(expected_pk.clone()));
+            let attrs = vec![parse_quote!(#[tiny_orm(scope = "archived = false; --")])];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
             assert_eq!(
-                field_names,
-                vec![
-                    expected_pk,
-                    Column::new("inserted_at", parse_quote!(DateTime<Utc>)),
-                    Column::new("something_at", parse_quote!(DateTime<Utc>)),
-                    Column::new("last_name", parse_quote!(String))
-                ]
+                error.to_string(),
+                "'archived = false; --' is not a valid condition for the 'scope' keyword"
             );
         }
 
         #[test]
-        fn test_parse_override_default_values_set_auto() {
-            let input: DeriveInput = parse_quote! {
+        fn test_parse_projections_attribute() {
+            let struct_name = format_ident!("Todo");
+            let attrs = vec![
+                parse_quote!(#[tiny_orm(projections(TodoSummary = "id, description", TodoTitle = "title"))]),
+            ];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(
+                parsed_struct.projections,
+                vec![
+                    Projection::new(format_ident!("TodoSummary"), "id, description").unwrap(),
+                    Projection::new(format_ident!("TodoTitle"), "title").unwrap(),
+                ]
+            );
+
+            let attrs = vec![parse_quote!(#[tiny_orm(projections(TodoSummary = "id;"))])];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "'id;' is not a valid column name for the 'projections' keyword"
+            );
+        }
+
+        #[test]
+        fn test_parse_inject_attribute() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm(inject = "tenant_id")])];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(
+                parsed_struct.inject,
+                Some(Inject::new("tenant_id").unwrap())
+            );
+
+            let attrs = vec![parse_quote!(#[tiny_orm(inject = "tenant_id, account_id")])];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "The 'inject' keyword takes a single column"
+            );
+        }
+
+        #[test]
+        fn test_parse_order_by_attribute() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm(order_by = "created_at DESC, id")])];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(
+                parsed_struct.order_by,
+                Some(OrderBy::new("created_at DESC, id").unwrap())
+            );
+
+            let attrs = vec![parse_quote!(#[tiny_orm(order_by = "created_at sideways")])];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "'created_at sideways' is not a valid order for the 'order_by' keyword"
+            );
+        }
+
+        #[test]
+        fn test_parse_view_attribute() {
+            let struct_name = format_ident!("TodoStats");
+            let attrs = vec![parse_quote!(#[tiny_orm(view)])];
+            let StructArguments { mut operations, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            operations.sort();
+            assert_eq!(operations, vec![Operation::Get, Operation::List]);
+
+            let attrs = vec![parse_quote!(#[tiny_orm(view, only = "list")])];
+            let StructArguments { operations, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(operations, vec![Operation::List]);
+        }
+
+        #[test]
+        fn test_parse_view_attribute_rejects_write_operations() {
+            let struct_name = format_ident!("TodoStats");
+            for attr in [
+                quote! { #[tiny_orm(view, all)] },
+                quote! { #[tiny_orm(view, add = "create")] },
+                quote! { #[tiny_orm(view, only = "get,update")] },
+                quote! { #[tiny_orm(view, add = "delete")] },
+            ] {
+                let attrs = vec![parse_quote!(#attr)];
+                let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+                assert_eq!(
+                    error.to_string(),
+                    "A 'view' is read-only, only the 'get' and 'list' operations can be used"
+                );
+            }
+
+            // The `create` default operation of a `New` struct is not used for a view
+            let struct_name = format_ident!("NewTodoStats");
+            let attrs = vec![parse_quote!(#[tiny_orm(view)])];
+            let StructArguments { operations, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(operations, vec![Operation::Get, Operation::List]);
+        }
+
+        #[test]
+        fn test_parse_from_row_attribute() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm(from_row)])];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert!(parsed_struct.from_row);
+
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &[]).unwrap();
+            assert!(!parsed_struct.from_row);
+        }
+
+        #[cfg(feature = "sqlite-no-returning")]
+        #[test]
+        fn test_parse_sqlite_no_returning_feature() {
+            let struct_name = format_ident!("MyStruct");
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &[]).unwrap();
+            assert!(parsed_struct.sqlite_no_returning);
+        }
+
+        #[test]
+        fn test_parse_conflict_target_attribute() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![
+                parse_quote!(#[tiny_orm(conflict_target = "email", conflict_where = "deleted_at IS NULL")]),
+            ];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(
+                parsed_struct.conflict_target,
+                Some(
+                    ConflictTarget::new("email")
+                        .unwrap()
+                        .with_predicate("deleted_at IS NULL")
+                        .unwrap()
+                )
+            );
+
+            let attrs = vec![parse_quote!(#[tiny_orm(conflict_where = "deleted_at IS NULL")])];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "The 'conflict_where' keyword requires 'conflict_target'"
+            );
+        }
+
+        #[test]
+        fn test_parse_returning_generated_attribute() {
+            let struct_name = format_ident!("Contact");
+            let attrs = vec![parse_quote!(#[tiny_orm(returning_generated)])];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert!(parsed_struct.returning_generated);
+        }
+
+        #[test]
+        fn test_parse_delete_returning_attribute() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm(delete_returning)])];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert!(parsed_struct.delete_returning);
+
+            let attrs = vec![parse_quote!(#[tiny_orm()])];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert!(!parsed_struct.delete_returning);
+        }
+
+        #[test]
+        fn test_parse_skip_defaults_attribute() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm(skip_defaults)])];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert!(parsed_struct.skip_defaults);
+
+            let attrs = vec![parse_quote!(#[tiny_orm()])];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert!(!parsed_struct.skip_defaults);
+        }
+
+        #[test]
+        fn test_parse_error_attribute() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm(error = "crate::AppError")])];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(parsed_struct.error, Some(parse_quote!(crate::AppError)));
+
+            let attrs = vec![parse_quote!(#[tiny_orm(error = "not a path")])];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(error.to_string(), "Failed to parse error as a path");
+        }
+
+        #[test]
+        fn test_parse_table_name_from_env() {
+            std::env::set_var("TINY_ORM_TEST_TABLE_NAME", "tenant_todo");
+            let struct_name = format_ident!("Todo");
+            let attrs =
+                vec![parse_quote!(#[tiny_orm(table_name = env!("TINY_ORM_TEST_TABLE_NAME"))])];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(parsed_struct.table_name.to_string(), "tenant_todo");
+            assert_eq!(parsed_struct.env_vars.len(), 1);
+            assert_eq!(
+                parsed_struct.env_vars[0].value(),
+                "TINY_ORM_TEST_TABLE_NAME"
+            );
+        }
+
+        #[test]
+        fn test_parse_table_name_from_undefined_env() {
+            let struct_name = format_ident!("Todo");
+            let attrs =
+                vec![parse_quote!(#[tiny_orm(table_name = env!("TINY_ORM_UNDEFINED_VAR"))])];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "The environment variable 'TINY_ORM_UNDEFINED_VAR' is not defined"
+            );
+        }
+
+        #[test]
+        fn test_parse_table_name_from_unsupported_expression() {
+            let struct_name = format_ident!("Todo");
+            let attrs = vec![parse_quote!(#[tiny_orm(table_name = TABLE_NAME)])];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "Expected a string literal or env!(\"...\")"
+            );
+        }
+
+        #[test]
+        fn test_parse_table_prefix() {
+            std::env::set_var("TINY_ORM_TEST_TABLE_PREFIX", "tenant1_");
+            let struct_name = format_ident!("NewTodoItem");
+            let attrs =
+                vec![parse_quote!(#[tiny_orm(table_prefix = env!("TINY_ORM_TEST_TABLE_PREFIX"))])];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(parsed_struct.table_name.to_string(), "tenant1_todo_item");
+
+            let attrs =
+                vec![parse_quote!(#[tiny_orm(table_name = "items", table_prefix = "app_")])];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(parsed_struct.table_name.to_string(), "app_items");
+        }
+
+        #[test]
+        fn test_parse_database_attribute() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm(database = "postgres")])];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(parsed_struct.database, Some(DbType::Postgres));
+
+            let attrs = vec![parse_quote!(#[tiny_orm()])];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(parsed_struct.database, None);
+        }
+
+        #[test]
+        fn test_parse_unknown_database_attribute() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm(database = "oracle")])];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(error.to_string(), "Database 'oracle' is not supported");
+        }
+
+        #[test]
+        fn test_parse_returning_defaults_to_none() {
+            let struct_name = format_ident!("UpdateMyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm()])];
+            let StructArguments { parsed_struct, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(parsed_struct.returning, None);
+        }
+
+        #[test]
+        fn test_parse_primary_key_field_attribute() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm(primary_key_field = "uuid")])];
+            let StructArguments {
+                primary_key_field, ..
+            } = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(primary_key_field.unwrap().value(), "uuid".to_string());
+        }
+
+        #[test]
+        fn test_parse_sqlx_rename_all() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![
+                parse_quote!(#[tiny_orm(all)]),
+                parse_quote!(#[sqlx(rename_all = "camelCase")]),
+            ];
+            let StructArguments { rename_all, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(rename_all, Some(RenameAll::Camel));
+        }
+
+        #[test]
+        fn test_parse_sqlx_attributes_without_rename_all() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![parse_quote!(#[sqlx(type_name = "my_struct", transparent)])];
+            let StructArguments { rename_all, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(rename_all, None);
+        }
+
+        #[test]
+        fn test_parse_sqlx_rename_all_not_supported() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![parse_quote!(#[sqlx(rename_all = "Train-Case")])];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "rename_all = \"Train-Case\" is not supported"
+            );
+        }
+
+        #[test]
+        fn test_cannot_pass_all_with_only() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm(all, only = "create")])];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "The 'all' and 'only' keywords cannot be used together"
+            );
+        }
+
+        #[test]
+        fn test_cannot_pass_only_with_exclude() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm(only = "create", exclude = "get")])];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "Only and Exclude are specified which is not allowed"
+            );
+        }
+
+        #[test]
+        fn test_cannot_pass_unknown_keyword() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm(tabble_name = "custom")])];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(error.to_string(), "Unknown tiny_orm attribute");
+        }
+
+        #[test]
+        fn test_cannot_pass_unknown_operation() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm(only = "create, upsert")])];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(error.to_string(), "Operation 'upsert' is not supported");
+        }
+
+        #[test]
+        fn test_cannot_pass_the_same_keyword_twice() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm(add = "create", add = "update")])];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "The 'add' keyword has already been specified"
+            );
+        }
+
+        #[test]
+        fn test_merge_several_attributes() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![
+                parse_quote!(#[tiny_orm(table_name = "generated", only = "get, list")]),
+                parse_quote!(#[tiny_orm(table_name = "custom", only = "list, update", soft_deletion)]),
+            ];
+            let StructArguments {
+                parsed_struct,
+                operations,
+                soft_deletion,
+                ..
+            } = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(parsed_struct.table_name.to_string(), "custom");
+            assert_eq!(
+                operations,
+                vec![Operation::Get, Operation::List, Operation::Update]
+            );
+            assert_eq!(soft_deletion, Some(SoftDeletion::default()));
+
+            let attrs = vec![
+                parse_quote!(#[tiny_orm(all, exclude = "delete")]),
+                parse_quote!(#[tiny_orm(exclude = "create")]),
+            ];
+            let StructArguments { mut operations, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            operations.sort();
+            assert_eq!(
+                operations,
+                vec![Operation::Get, Operation::List, Operation::Update]
+            );
+        }
+
+        #[test]
+        fn test_pass_all_with_add() {
+            let struct_name = format_ident!("NewMyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm(all, add = "create")])];
+            let StructArguments { mut operations, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            operations.sort();
+            assert_eq!(operations, Operation::all());
+        }
+
+        #[test]
+        fn test_pass_all_with_exclude() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm(all, exclude = "delete")])];
+            let StructArguments {
+                parsed_struct,
+                mut operations,
+                soft_deletion,
+                ..
+            } = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            operations.sort();
+            assert_eq!(parsed_struct.struct_type, StructType::Generic);
+            assert_eq!(
+                operations,
+                vec![
+                    Operation::Get,
+                    Operation::List,
+                    Operation::Create,
+                    Operation::Update
+                ]
+            );
+            assert_eq!(soft_deletion, None);
+        }
+
+        #[test]
+        fn test_parse_soft_delete_now() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![
+                parse_quote!(#[tiny_orm(soft_deletion, soft_delete_now = " clock_timestamp() ")]),
+            ];
+            let StructArguments { soft_deletion, .. } =
+                Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            assert_eq!(
+                soft_deletion.unwrap().now,
+                Some("clock_timestamp()".to_string())
+            );
+
+            let attrs = vec![
+                parse_quote!(#[tiny_orm(soft_delete_now = "NOW() + INTERVAL '1 day'", soft_deletion)]),
+            ];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "'NOW() + INTERVAL '1 day'' is not a valid function call for the 'soft_delete_now' keyword"
+            );
+
+            let attrs = vec![
+                parse_quote!(#[tiny_orm(soft_deletion, soft_delete_now = "NOW(); DROP TABLE todo")]),
+            ];
+            assert!(Parser::parse_struct_macro_arguments(&struct_name, &attrs).is_err());
+        }
+
+        #[test]
+        fn test_cannot_pass_soft_delete_now_without_soft_deletion() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm(soft_delete_now = "clock_timestamp()")])];
+            let error = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "The 'soft_delete_now' keyword requires 'soft_deletion'"
+            );
+        }
+
+        #[test]
+        fn test_pass_all_with_exclude_and_soft_deletion() {
+            let struct_name = format_ident!("MyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm(all, exclude = "delete", soft_deletion)])];
+            let StructArguments {
+                mut operations,
+                soft_deletion,
+                ..
+            } = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            operations.sort();
+            assert_eq!(
+                operations,
+                vec![
+                    Operation::Get,
+                    Operation::List,
+                    Operation::Create,
+                    Operation::Update
+                ]
+            );
+            assert_eq!(soft_deletion, Some(SoftDeletion::default()));
+        }
+
+        #[test]
+        fn test_pass_all_with_exclude_overrides_the_struct_type_default() {
+            let struct_name = format_ident!("NewMyStruct");
+            let attrs = vec![parse_quote!(#[tiny_orm(all, exclude = "create, list")])];
+            let StructArguments {
+                parsed_struct,
+                mut operations,
+                ..
+            } = Parser::parse_struct_macro_arguments(&struct_name, &attrs).unwrap();
+            operations.sort();
+            assert_eq!(parsed_struct.struct_type, StructType::Create);
+            assert_eq!(
+                operations,
+                vec![Operation::Get, Operation::Update, Operation::Delete]
+            );
+        }
+    }
+
+    mod parse_fields_macro_arguments {
+        use syn::{parse_quote, DeriveInput};
+
+        use crate::attr::{Column, Parser};
+        use crate::types::RenameAll;
+
+        #[test]
+        fn test_parse_default() {
+            let input: DeriveInput = parse_quote! {
+                struct Contact {
+                    id: i64,
+                    created_at: DateTime<Utc>,
+                    updated_at: DateTime<Utc>,
+                    last_name: String,
+                }
+            };
+
+            let (primary_key, field_names) =
+                Parser::parse_fields_macro_arguments(input.data, None, None).unwrap();
+            let mut expected_pk = Column::new("id", parse_quote!(i64));
+            expected_pk.set_primary_key();
+            assert_eq!(primary_key, Some(expected_pk.clone()));
+            assert_eq!(
+                field_names,
+                vec![
+                    expected_pk,
+                    Column::new("created_at", parse_quote!(DateTime<Utc>)),
+                    Column::new("updated_at", parse_quote!(DateTime<Utc>)),
+                    Column::new("last_name", parse_quote!(String))
+                ]
+            );
+        }
+
+        #[test]
+        fn test_parse_no_default_columns() {
+            let input: DeriveInput = parse_quote! {
+                struct Contact {
+                    last_name: String,
+                }
+            };
+
+            let (primary_key, field_names) =
+                Parser::parse_fields_macro_arguments(input.data, None, None).unwrap();
+            assert_eq!(primary_key, None);
+            assert_eq!(
+                field_names,
+                vec![Column::new("last_name", parse_quote!(String))]
+            );
+        }
+
+        #[test]
+        fn test_parse_override_default_values() {
+            let input: DeriveInput = parse_quote! {
+                struct Contact {
+                    #[tiny_orm(primary_key)]
+                    custom_key: u32,
+                    inserted_at: DateTime<Utc>,
+                    something_at: DateTime<Utc>,
+                    last_name: String,
+                }
+            };
+
+            let (primary_key, field_names) =
+                Parser::parse_fields_macro_arguments(input.data, None, None).unwrap();
+            let mut expected_pk = Column::new("custom_key", parse_quote!(u32));
+            expected_pk.set_primary_key();
+            assert_eq!(primary_key, Some(expected_pk.clone()));
+            assert_eq!(
+                field_names,
+                vec![
+                    expected_pk,
+                    Column::new("inserted_at", parse_quote!(DateTime<Utc>)),
+                    Column::new("something_at", parse_quote!(DateTime<Utc>)),
+                    Column::new("last_name", parse_quote!(String))
+                ]
+            );
+        }
+
+        #[test]
+        fn test_parse_override_default_values_set_auto() {
+            let input: DeriveInput = parse_quote! {
+                struct Contact {
+                    #[tiny_orm(primary_key(auto))]
+                    custom_key: u32,
+                    inserted_at: DateTime<Utc>,
+                    something_at: DateTime<Utc>,
+                    last_name: String,
+                }
+            };
+
+            let (primary_key, _) =
+                Parser::parse_fields_macro_arguments(input.data, None, None).unwrap();
+            let mut pk = Column::new("custom_key", parse_quote!(u32));
+            pk.set_primary_key();
+            pk.set_auto_increment();
+            assert_eq!(primary_key, Some(pk));
+        }
+
+        #[test]
+        fn test_parse_primary_key_uuid() {
+            let input: DeriveInput = parse_quote! {
+                struct Note {
+                    #[tiny_orm(primary_key(uuid))]
+                    id: Uuid,
+                    title: String,
+                }
+            };
+
+            let parsed = Parser::parse_fields_macro_arguments(input.data, None, None);
+            if cfg!(feature = "uuid") {
+                let (primary_key, _) = parsed.unwrap();
+                let mut pk = Column::new("id", parse_quote!(Uuid));
+                pk.set_primary_key();
+                pk.set_uuid();
+                assert_eq!(primary_key, Some(pk));
+            } else {
+                assert_eq!(
+                    parsed.unwrap_err().to_string(),
+                    "The `uuid` feature is required for `primary_key(uuid)`"
+                );
+            }
+        }
+
+        #[test]
+        fn test_parse_primary_key_sequence() {
+            let input: DeriveInput = parse_quote! {
+                struct Contact {
+                    #[tiny_orm(primary_key(sequence = "public.contact_id_seq"))]
+                    id: i64,
+                    last_name: String,
+                }
+            };
+
+            let (primary_key, _) =
+                Parser::parse_fields_macro_arguments(input.data, None, None).unwrap();
+            let primary_key = primary_key.unwrap();
+            assert!(primary_key.auto_increment);
+            assert_eq!(
+                primary_key.nextval(),
+                Some("nextval('public.contact_id_seq')".to_string())
+            );
+
+            let input: DeriveInput = parse_quote! {
+                struct Contact {
+                    #[tiny_orm(primary_key(sequence = "contact_id_seq'); DROP"))]
+                    id: i64,
+                }
+            };
+            assert_eq!(
+                Parser::parse_fields_macro_arguments(input.data, None, None)
+                    .unwrap_err()
+                    .to_string(),
+                "'contact_id_seq'); DROP' is not a valid sequence name"
+            );
+        }
+
+        #[test]
+        fn test_parse_keep_override_even_when_default_values_present() {
+            let input: DeriveInput = parse_quote! {
+                struct Contact {
+                    #[tiny_orm(primary_key)]
+                    custom_key: u32,
+                    inserted_at: DateTime<Utc>,
+                    something_at: DateTime<Utc>,
+                    id: u32,
+                    created_at: DateTime<Utc>,
+                    updated_at: DateTime<Utc>,
+                    last_name: String,
+                }
+            };
+
+            let (primary_key, field_names) =
+                Parser::parse_fields_macro_arguments(input.data, None, None).unwrap();
+            let mut expect_pk = Column::new("custom_key", parse_quote!(u32));
+            expect_pk.set_primary_key();
+            assert_eq!(primary_key, Some(expect_pk.clone()));
+            assert_eq!(
+                field_names,
+                vec![
+                    expect_pk,
+                    Column::new("inserted_at", parse_quote!(DateTime<Utc>)),
+                    Column::new("something_at", parse_quote!(DateTime<Utc>)),
+                    Column::new("id", parse_quote!(u32)),
+                    Column::new("created_at", parse_quote!(DateTime<Utc>)),
+                    Column::new("updated_at", parse_quote!(DateTime<Utc>)),
+                    Column::new("last_name", parse_quote!(String))
+                ]
+            );
+        }
+
+        #[test]
+        fn test_parse_keep_override_when_default_present_and_auto_is_set() {
+            let input: DeriveInput = parse_quote! {
+                struct Contact {
+                    #[tiny_orm(primary_key(auto))]
+                    custom_key: u32,
+                    inserted_at: DateTime<Utc>,
+                    something_at: DateTime<Utc>,
+                    id: u32,
+                    created_at: DateTime<Utc>,
+                    updated_at: DateTime<Utc>,
+                    last_name: String,
+                }
+            };
+
+            let (primary_key, _) =
+                Parser::parse_fields_macro_arguments(input.data, None, None).unwrap();
+            let mut pk = Column::new("custom_key", parse_quote!(u32));
+            pk.set_primary_key();
+            pk.set_auto_increment();
+            assert_eq!(primary_key, Some(pk));
+        }
+
+        #[test]
+        fn test_parse_custom_default_primary_key() {
+            let input: DeriveInput = parse_quote! {
+                struct Contact {
+                    id: i64,
+                    uuid: Uuid,
+                    last_name: String,
+                }
+            };
+
+            let (primary_key, field_names) =
+                Parser::parse_fields_macro_arguments(input.data, None, Some(parse_quote!("uuid")))
+                    .unwrap();
+            let mut expected_pk = Column::new("uuid", parse_quote!(Uuid));
+            expected_pk.set_primary_key();
+            assert_eq!(primary_key, Some(expected_pk.clone()));
+            assert_eq!(
+                field_names,
+                vec![
+                    Column::new("id", parse_quote!(i64)),
+                    expected_pk,
+                    Column::new("last_name", parse_quote!(String))
+                ]
+            );
+        }
+
+        #[test]
+        fn test_parse_explicit_primary_key_wins_over_custom_default() {
+            let input: DeriveInput = parse_quote! {
+                struct Contact {
+                    uuid: Uuid,
+                    #[tiny_orm(primary_key)]
+                    contact_id: i64,
+                }
+            };
+
+            let (primary_key, _) =
+                Parser::parse_fields_macro_arguments(input.data, None, Some(parse_quote!("uuid")))
+                    .unwrap();
+            let mut expected_pk = Column::new("contact_id", parse_quote!(i64));
+            expected_pk.set_primary_key();
+            assert_eq!(primary_key, Some(expected_pk));
+        }
+
+        #[test]
+        fn test_parse_custom_default_primary_key_missing_field() {
+            let input: DeriveInput = parse_quote! {
+                struct Contact {
+                    id: i64,
+                    last_name: String,
+                }
+            };
+
+            let error =
+                Parser::parse_fields_macro_arguments(input.data, None, Some(parse_quote!("uuid")))
+                    .unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "No field named 'uuid' found for the 'primary_key_field' keyword"
+            );
+        }
+
+        #[test]
+        fn test_parse_db_default() {
+            let input: DeriveInput = parse_quote! {
+                struct Todo {
+                    id: i64,
+                    #[tiny_orm(db_default)]
+                    created_at: DateTime<Utc>,
+                }
+            };
+
+            let (_, field_names) =
+                Parser::parse_fields_macro_arguments(input.data, None, None).unwrap();
+            assert!(!field_names[0].db_default);
+            assert!(field_names[1].db_default);
+        }
+
+        #[test]
+        fn test_parse_readonly() {
+            let input: DeriveInput = parse_quote! {
+                struct Todo {
+                    id: i64,
+                    #[tiny_orm(readonly)]
+                    full_name: String,
+                }
+            };
+
+            let (_, field_names) =
+                Parser::parse_fields_macro_arguments(input.data, None, None).unwrap();
+            assert!(!field_names[0].readonly);
+            assert!(field_names[1].readonly);
+        }
+
+        #[test]
+        fn test_parse_unique() {
+            let input: DeriveInput = parse_quote! {
+                struct User {
+                    id: i64,
+                    #[tiny_orm(unique)]
+                    email: String,
+                }
+            };
+
+            let (_, field_names) =
+                Parser::parse_fields_macro_arguments(input.data, None, None).unwrap();
+            assert!(!field_names[0].unique);
+            assert!(field_names[1].unique);
+        }
+
+        #[test]
+        fn test_parse_filterable() {
+            let input: DeriveInput = parse_quote! {
+                struct Todo {
+                    id: i64,
+                    #[tiny_orm(filterable)]
+                    done: bool,
+                }
+            };
+
+            let (_, field_names) =
+                Parser::parse_fields_macro_arguments(input.data, None, None).unwrap();
+            assert!(!field_names[0].filterable);
+            assert!(field_names[1].filterable);
+        }
+
+        #[test]
+        fn test_parse_json() {
+            let input: DeriveInput = parse_quote! {
+                struct Settings {
+                    id: i64,
+                    #[tiny_orm(json)]
+                    metadata: SetOption<serde_json::Value>,
+                }
+            };
+
+            let (_, field_names) =
+                Parser::parse_fields_macro_arguments(input.data, None, None).unwrap();
+            assert!(!field_names[0].json);
+            assert!(field_names[1].json);
+        }
+
+        #[test]
+        fn test_parse_transform() {
+            let input: DeriveInput = parse_quote! {
+                struct Patient {
+                    id: i64,
+                    #[tiny_orm(transform = "crate::crypto")]
+                    ssn: String,
+                }
+            };
+
+            let (_, field_names) =
+                Parser::parse_fields_macro_arguments(input.data, None, None).unwrap();
+            assert_eq!(field_names[0].transform, None);
+            assert_eq!(field_names[1].transform, Some(parse_quote!(crate::crypto)));
+        }
+
+        #[test]
+        fn test_parse_transform_with_json() {
+            let input: DeriveInput = parse_quote! {
+                struct Patient {
+                    id: i64,
+                    #[tiny_orm(json, transform = "crate::crypto")]
+                    metadata: serde_json::Value,
+                }
+            };
+
+            match Parser::parse_fields_macro_arguments(input.data, None, None) {
+                Err(err) => assert_eq!(
+                    err.to_string(),
+                    "The 'json' and 'transform' attributes cannot be used together"
+                ),
+                Ok(_) => panic!("Expected an error"),
+            }
+        }
+
+        #[test]
+        fn test_parse_readonly_with_bulk_updatable() {
+            let input: DeriveInput = parse_quote! {
+                struct Todo {
+                    id: i64,
+                    #[tiny_orm(readonly, bulk_updatable)]
+                    full_name: String,
+                }
+            };
+
+            let error = Parser::parse_fields_macro_arguments(input.data, None, None).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "The 'readonly' and 'bulk_updatable' attributes cannot be used together"
+            );
+        }
+
+        #[test]
+        fn test_parse_bulk_updatable() {
+            let input: DeriveInput = parse_quote! {
+                struct Todo {
+                    id: i64,
+                    #[tiny_orm(bulk_updatable)]
+                    done: bool,
+                }
+            };
+
+            let (_, field_names) =
+                Parser::parse_fields_macro_arguments(input.data, None, None).unwrap();
+            assert!(!field_names[0].bulk_updatable);
+            assert!(field_names[1].bulk_updatable);
+        }
+
+        #[test]
+        fn test_parse_unknown_field_attribute() {
+            let input: DeriveInput = parse_quote! {
                 struct Contact {
-                    #[tiny_orm(primary_key(auto))]
-                    custom_key: u32,
-                    inserted_at: DateTime<Utc>,
-                    something_at: DateTime<Utc>,
-                    last_name: String,
+                    #[tiny_orm(primary_kye)]
+                    id: i64,
                 }
             };
 
-            let (primary_key, _) = Parser::parse_fields_macro_arguments(input.data);
-            let mut pk = Column::new("custom_key", parse_quote!(u32));
-            pk.set_primary_key();
-            pk.set_auto_increment();
-            assert_eq!(primary_key, Some(pk));
+            let error = Parser::parse_fields_macro_arguments(input.data, None, None).unwrap_err();
+            assert_eq!(error.to_string(), "Unknown tiny_orm field attribute");
         }
 
         #[test]
-        fn test_parse_keep_override_even_when_default_values_present() {
+        fn test_parse_only_structs_with_named_fields() {
+            let input: DeriveInput = parse_quote! {
+                struct Contact(i64, String);
+            };
+            let error = Parser::parse_fields_macro_arguments(input.data, None, None).unwrap_err();
+            assert_eq!(error.to_string(), "Only named fields are supported");
+
+            let input: DeriveInput = parse_quote! {
+                enum Contact {
+                    Person,
+                }
+            };
+            let error = Parser::parse_fields_macro_arguments(input.data, None, None).unwrap_err();
+            assert_eq!(error.to_string(), "Only structs are supported");
+        }
+
+        #[test]
+        fn test_parse_rename_all() {
             let input: DeriveInput = parse_quote! {
                 struct Contact {
-                    #[tiny_orm(primary_key)]
-                    custom_key: u32,
-                    inserted_at: DateTime<Utc>,
-                    something_at: DateTime<Utc>,
-                    id: u32,
+                    id: i64,
                     created_at: DateTime<Utc>,
-                    updated_at: DateTime<Utc>,
                     last_name: String,
                 }
             };
 
-            let (primary_key, field_names) = Parser::parse_fields_macro_arguments(input.data);
-            let mut expect_pk = Column::new("custom_key", parse_quote!(u32));
-            expect_pk.set_primary_key();
-            assert_eq!(primary_key, Some(expect_pk.clone()));
+            let (primary_key, field_names) =
+                Parser::parse_fields_macro_arguments(input.data, Some(RenameAll::Pascal), None)
+                    .unwrap();
+            let mut expected_pk = Column::new("id", parse_quote!(i64));
+            expected_pk.set_name("Id");
+            expected_pk.set_primary_key();
+            let mut created_at = Column::new("created_at", parse_quote!(DateTime<Utc>));
+            created_at.set_name("CreatedAt");
+            let mut last_name = Column::new("last_name", parse_quote!(String));
+            last_name.set_name("LastName");
+            assert_eq!(primary_key, Some(expected_pk.clone()));
+            assert_eq!(field_names, vec![expected_pk, created_at, last_name]);
+        }
+
+        #[test]
+        fn test_parse_column_overrides_rename_all() {
+            let input: DeriveInput = parse_quote! {
+                struct Contact {
+                    #[tiny_orm(primary_key, column = "contact_id")]
+                    id: i64,
+                    #[tiny_orm(column = "last_name")]
+                    last_name: String,
+                    first_name: String,
+                }
+            };
+
+            let (primary_key, field_names) =
+                Parser::parse_fields_macro_arguments(input.data, Some(RenameAll::Camel), None)
+                    .unwrap();
+            let mut expected_pk = Column::new("id", parse_quote!(i64));
+            expected_pk.set_name("contact_id");
+            expected_pk.set_primary_key();
+            let mut first_name = Column::new("first_name", parse_quote!(String));
+            first_name.set_name("firstName");
+            assert_eq!(primary_key, Some(expected_pk.clone()));
             assert_eq!(
                 field_names,
                 vec![
-                    expect_pk,
-                    Column::new("inserted_at", parse_quote!(DateTime<Utc>)),
-                    Column::new("something_at", parse_quote!(DateTime<Utc>)),
-                    Column::new("id", parse_quote!(u32)),
-                    Column::new("created_at", parse_quote!(DateTime<Utc>)),
-                    Column::new("updated_at", parse_quote!(DateTime<Utc>)),
-                    Column::new("last_name", parse_quote!(String))
+                    expected_pk,
+                    Column::new("last_name", parse_quote!(String)),
+                    first_name
                 ]
             );
         }
 
         #[test]
-        fn test_parse_keep_override_when_default_present_and_auto_is_set() {
+        fn test_parse_sqlx_rename() {
             let input: DeriveInput = parse_quote! {
                 struct Contact {
-                    #[tiny_orm(primary_key(auto))]
-                    custom_key: u32,
-                    inserted_at: DateTime<Utc>,
-                    something_at: DateTime<Utc>,
-                    id: u32,
-                    created_at: DateTime<Utc>,
-                    updated_at: DateTime<Utc>,
+                    id: i64,
+                    #[sqlx(json(nullable), rename = "email_address")]
+                    email: String,
+                    #[sqlx(rename = "surname")]
+                    #[tiny_orm(column = "family_name")]
                     last_name: String,
+                    first_name: String,
                 }
             };
 
-            let (primary_key, _) = Parser::parse_fields_macro_arguments(input.data);
-            let mut pk = Column::new("custom_key", parse_quote!(u32));
-            pk.set_primary_key();
-            pk.set_auto_increment();
-            assert_eq!(primary_key, Some(pk));
+            let (_, field_names) =
+                Parser::parse_fields_macro_arguments(input.data, Some(RenameAll::Camel), None)
+                    .unwrap();
+            let names: Vec<&str> = field_names
+                .iter()
+                .map(|column| column.name.as_str())
+                .collect();
+            // `rename` takes precedence over `rename_all`, and `column` over both
+            assert_eq!(
+                names,
+                vec!["id", "email_address", "family_name", "firstName"]
+            );
         }
     }
 
@@ -806,10 +3007,255 @@ mod tests {
         use quote::format_ident;
         use syn::{parse_quote, DeriveInput};
 
-        use crate::attr::{Column, Operation, ParsedStruct};
+        use crate::attr::{Column, Operation, ParsedStruct, Variant};
 
         use super::Attr;
 
+        #[test]
+        fn test_parse_created_key_with_rename_all() {
+            let input: DeriveInput = parse_quote! {
+                #[sqlx(rename_all = "UPPERCASE")]
+                #[tiny_orm(table_name = "contact", only = "create")]
+                struct NewContact {
+                    email: String,
+                }
+            };
+
+            let result = Attr::parse(input).unwrap();
+            assert_eq!(result.parsed_struct.created_key, "ID");
+        }
+
+        #[test]
+        fn test_parse_lookup_key() {
+            let input: DeriveInput = parse_quote! {
+                #[tiny_orm(all, lookup_key = "slug")]
+                struct Contact {
+                    id: i64,
+                    slug: String,
+                }
+            };
+
+            let result = Attr::parse(input).unwrap();
+            assert_eq!(
+                result.lookup_key,
+                Some(Column::new("slug", parse_quote!(String)))
+            );
+            assert_eq!(result.lookup_column().unwrap().ident, "slug");
+            assert_eq!(result.primary_key.unwrap().ident, "id");
+        }
+
+        #[test]
+        fn test_parse_sqlx_skip() {
+            let input: DeriveInput = parse_quote! {
+                #[tiny_orm(all)]
+                struct Contact {
+                    id: i64,
+                    last_name: String,
+                    #[sqlx(skip)]
+                    display_cache: Option<String>,
+                    #[sqlx(default)]
+                    nickname: String,
+                }
+            };
+
+            let result = Attr::parse(input).unwrap();
+            assert_eq!(
+                result
+                    .columns
+                    .iter()
+                    .map(|column| column.name.as_str())
+                    .collect::<Vec<&str>>(),
+                vec!["id", "last_name", "nickname"]
+            );
+            assert_eq!(
+                result.parsed_struct.skipped_fields,
+                vec![format_ident!("display_cache")]
+            );
+
+            // Neither bound nor part of the INSERT, UPDATE and SELECT queries
+            let generated = crate::generate_impl(&result).unwrap().to_string();
+            assert!(!generated.contains("display_cache"));
+        }
+
+        #[test]
+        fn test_parse_sqlx_skip_with_tiny_orm_attribute() {
+            let input: DeriveInput = parse_quote! {
+                struct Contact {
+                    id: i64,
+                    #[sqlx(skip)]
+                    #[tiny_orm(readonly)]
+                    display_cache: Option<String>,
+                }
+            };
+
+            let error = Attr::parse(input).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "A field with `#[sqlx(skip)]` is not a column and cannot have tiny_orm attributes"
+            );
+        }
+
+        #[test]
+        fn test_parse_enum_variants() {
+            let input: DeriveInput = parse_quote! {
+                #[tiny_orm(table_name = "shape")]
+                enum Shape {
+                    Circle { id: i64, radius: f64 },
+                    RightSquare { id: i64, #[tiny_orm(column = "width")] side: f64 },
+                }
+            };
+
+            let result = Attr::parse(input).unwrap();
+            let mut primary_key = Column::new("id", parse_quote!(i64));
+            primary_key.set_primary_key();
+            let mut side = Column::new("side", parse_quote!(f64));
+            side.set_name("width");
+            assert_eq!(result.parsed_struct.discriminator, Some("kind".to_string()));
+            assert_eq!(result.primary_key, Some(primary_key.clone()));
+            assert_eq!(
+                result.columns,
+                vec![
+                    primary_key.clone(),
+                    Column::new("radius", parse_quote!(f64)),
+                    side.clone()
+                ]
+            );
+            assert_eq!(
+                result.parsed_struct.variants,
+                vec![
+                    Variant::new(
+                        &format_ident!("Circle"),
+                        vec![
+                            primary_key.clone(),
+                            Column::new("radius", parse_quote!(f64))
+                        ],
+                        vec![]
+                    ),
+                    Variant::new(
+                        &format_ident!("RightSquare"),
+                        vec![primary_key, side],
+                        vec![]
+                    ),
+                ]
+            );
+            assert_eq!(
+                result.parsed_struct.variants[1].discriminator,
+                "right_square"
+            );
+        }
+
+        #[test]
+        fn test_parse_enum_with_a_custom_discriminator() {
+            let input: DeriveInput = parse_quote! {
+                #[tiny_orm(discriminator = "shape_type")]
+                enum Shape {
+                    Circle { id: i64, radius: f64 },
+                }
+            };
+            let result = Attr::parse(input).unwrap();
+            assert_eq!(
+                result.parsed_struct.discriminator,
+                Some("shape_type".to_string())
+            );
+
+            let input: DeriveInput = parse_quote! {
+                #[tiny_orm(discriminator = "shape_type")]
+                struct Circle {
+                    id: i64,
+                }
+            };
+            assert_eq!(
+                Attr::parse(input).unwrap_err().to_string(),
+                "The 'discriminator' keyword requires an enum"
+            );
+        }
+
+        #[test]
+        fn test_cannot_parse_invalid_enum_variants() {
+            for (input, error) in [
+                (
+                    parse_quote! {
+                        enum Shape {}
+                    },
+                    "An enum requires at least one variant",
+                ),
+                (
+                    parse_quote! {
+                        enum Shape {
+                            Circle(i64, f64),
+                        }
+                    },
+                    "Only variants with named fields are supported",
+                ),
+                (
+                    parse_quote! {
+                        enum Shape {
+                            Circle { id: i64, radius: f64 },
+                            Square { side: f64 },
+                        }
+                    },
+                    "Every variant requires the primary key; add `#[tiny_orm(primary_key)]` or an `id` field.",
+                ),
+                (
+                    parse_quote! {
+                        enum Shape {
+                            Circle { id: i64, radius: f64 },
+                            Square { #[tiny_orm(primary_key)] side: f64 },
+                        }
+                    },
+                    "Every variant requires the same primary key 'id'",
+                ),
+            ] {
+                let input: DeriveInput = input;
+                assert_eq!(Attr::parse(input).unwrap_err().to_string(), error);
+            }
+        }
+
+        #[test]
+        fn test_parse_cursor() {
+            let input: DeriveInput = parse_quote! {
+                #[tiny_orm(all, cursor = "created_at")]
+                struct Contact {
+                    id: i64,
+                    created_at: DateTime<Utc>,
+                }
+            };
+
+            let result = Attr::parse(input).unwrap();
+            assert_eq!(
+                result.cursor,
+                Some(Column::new("created_at", parse_quote!(DateTime<Utc>)))
+            );
+            assert_eq!(result.cursor_column().unwrap().ident, "created_at");
+
+            let input: DeriveInput = parse_quote! {
+                #[tiny_orm(all, cursor = "updated_at")]
+                struct Contact {
+                    id: i64,
+                }
+            };
+            assert_eq!(
+                Attr::parse(input).unwrap_err().to_string(),
+                "No field named 'updated_at' found for the 'cursor' keyword"
+            );
+        }
+
+        #[test]
+        fn test_parse_lookup_key_missing_field() {
+            let input: DeriveInput = parse_quote! {
+                #[tiny_orm(all, lookup_key = "slug")]
+                struct Contact {
+                    id: i64,
+                }
+            };
+
+            let error = Attr::parse(input).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "No field named 'slug' found for the 'lookup_key' keyword"
+            );
+        }
+
         #[test]
         fn test_parse_basic_struct() {
             let input: DeriveInput = parse_quote! {
@@ -821,8 +3267,9 @@ mod tests {
                 }
             };
 
-            let result = Attr::parse(input);
-            let parsed_struct = ParsedStruct::new(&format_ident!("Contact"), None, None);
+            let result = Attr::parse(input).unwrap();
+            let mut parsed_struct = ParsedStruct::new(&format_ident!("Contact"), None, None);
+            parsed_struct.sqlite_no_returning = cfg!(feature = "sqlite-no-returning");
             let mut primary_key = Column::new("id", parse_quote!(i64));
             primary_key.set_primary_key();
             assert_eq!(
@@ -830,6 +3277,8 @@ mod tests {
                 Attr {
                     parsed_struct,
                     primary_key: Some(primary_key.clone()),
+                    lookup_key: None,
+                    cursor: None,
                     columns: vec![
                         primary_key,
                         Column::new("created_at", parse_quote!(DateTime<Utc>)),
@@ -837,7 +3286,7 @@ mod tests {
                         Column::new("last_name", parse_quote!(String))
                     ],
                     operations: vec![Operation::Get, Operation::List, Operation::Delete],
-                    soft_deletion: false,
+                    soft_deletion: None,
                 }
             );
         }
@@ -857,17 +3306,20 @@ mod tests {
             let mut primary_key = Column::new("custom_pk", parse_quote!(i64));
             primary_key.set_primary_key();
 
-            let result = Attr::parse(input);
-            let parsed_struct = ParsedStruct::new(
+            let result = Attr::parse(input).unwrap();
+            let mut parsed_struct = ParsedStruct::new(
                 &format_ident!("Contact"),
                 Some("specific_table".to_string()),
                 Some(format_ident!("AnotherObject")),
             );
+            parsed_struct.sqlite_no_returning = cfg!(feature = "sqlite-no-returning");
             assert_eq!(
                 result,
                 Attr {
                     parsed_struct,
                     primary_key: Some(primary_key.clone()),
+                    lookup_key: None,
+                    cursor: None,
                     columns: vec![
                         primary_key,
                         Column::new("custom_created_at", parse_quote!(DateTime<Utc>)),
@@ -876,7 +3328,7 @@ mod tests {
                     ],
 
                     operations: vec![Operation::Create],
-                    soft_deletion: false,
+                    soft_deletion: None,
                 }
             );
         }
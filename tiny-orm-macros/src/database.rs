@@ -1,4 +1,5 @@
 use quote::format_ident;
+use std::str::FromStr;
 use syn::Ident;
 
 #[allow(dead_code)]
@@ -16,6 +17,26 @@ impl DbType {
             DbType::Sqlite => format_ident!("SqliteExecutor"),
         }
     }
+    pub fn to_database_ident(&self) -> Ident {
+        match self {
+            DbType::Postgres => format_ident!("Postgres"),
+            DbType::MySQL => format_ident!("MySql"),
+            DbType::Sqlite => format_ident!("Sqlite"),
+        }
+    }
+}
+
+impl FromStr for DbType {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<DbType, Self::Err> {
+        match input {
+            "postgres" => Ok(DbType::Postgres),
+            "mysql" => Ok(DbType::MySQL),
+            "sqlite" => Ok(DbType::Sqlite),
+            _ => Err(()),
+        }
+    }
 }
 
 #[cfg(feature = "postgres")]
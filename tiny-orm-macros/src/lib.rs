@@ -9,12 +9,18 @@ mod database;
 mod quotes;
 mod types;
 
-#[proc_macro_derive(Table, attributes(tiny_orm))]
+#[proc_macro_derive(Table, attributes(tiny_orm, sqlx))]
 pub fn derive_tiny_orm(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let attr = attr::Attr::parse(input);
+    let attr = match attr::Attr::parse(input) {
+        Ok(attr) => attr,
+        Err(error) => return TokenStream::from(error.to_compile_error()),
+    };
 
-    let expanded = generate_impl(&attr);
+    let expanded = match generate_impl(&attr) {
+        Ok(expanded) => expanded,
+        Err(error) => return TokenStream::from(error.to_compile_error()),
+    };
 
     #[cfg(test)]
     println!("Generated code:\n{}", expanded);
@@ -22,53 +28,408 @@ pub fn derive_tiny_orm(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
-fn generate_impl(attr: &attr::Attr) -> proc_macro2::TokenStream {
-    let struct_name = attr.parsed_struct.name.clone();
+fn check_primary_key(attr: &attr::Attr) -> syn::Result<()> {
+    if attr.primary_key.is_some() {
+        return Ok(());
+    }
+
+    let mut errors = [
+        (Operation::Get, "get"),
+        (Operation::Update, "update"),
+        (Operation::Delete, "delete"),
+    ]
+    .iter()
+    .filter(|(operation, _)| attr.operations.contains(operation))
+    .map(|(_, operation_name)| {
+        syn::Error::new_spanned(
+            &attr.parsed_struct.name,
+            format!("operation `{operation_name}` requires a primary key; add `#[tiny_orm(primary_key)]` or an `id` field."),
+        )
+    })
+    .chain(attr.parsed_struct.has_many.iter().map(|has_many| {
+        syn::Error::new_spanned(
+            &has_many.target,
+            "`has_many` requires a primary key; add `#[tiny_orm(primary_key)]` or an `id` field.",
+        )
+    }))
+    .chain(
+        attr.columns
+            .iter()
+            .filter(|column| column.bulk_updatable)
+            .map(|column| {
+                syn::Error::new_spanned(
+                    &column.ident,
+                    "`bulk_updatable` requires a primary key; add `#[tiny_orm(primary_key)]` or an `id` field.",
+                )
+            }),
+    );
+
+    match errors.next() {
+        Some(mut error) => {
+            errors.for_each(|other| error.combine(other));
+            Err(error)
+        }
+        None => Ok(()),
+    }
+}
+
+fn check_create_primary_key(attr: &attr::Attr) -> syn::Result<()> {
+    match attr.primary_key {
+        Some(ref primary_key)
+            if primary_key.use_set_options() && attr.operations.contains(&Operation::Create) =>
+        {
+            Err(syn::Error::new_spanned(
+                &primary_key._type,
+                "A primary key cannot be a `SetOption` with the `create` operation since it is returned by the query; use `Option<T>` instead.",
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn check_belongs_to(attr: &attr::Attr) -> syn::Result<()> {
+    match attr.parsed_struct.belongs_to.iter().find(|belongs_to| {
+        !attr
+            .columns
+            .iter()
+            .any(|column| column.ident == belongs_to.foreign_key)
+    }) {
+        Some(belongs_to) => Err(syn::Error::new_spanned(
+            &belongs_to.foreign_key,
+            format!(
+                "The foreign_key '{}' is not a field of the struct",
+                belongs_to.foreign_key
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+fn check_from_row(attr: &attr::Attr) -> syn::Result<()> {
+    let generics = &attr.parsed_struct.generics;
+    if attr.parsed_struct.from_row && !generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            generics,
+            "The 'from_row' attribute does not support generic structs; derive `sqlx::FromRow` instead.",
+        ));
+    }
+    Ok(())
+}
+
+fn check_sequence(attr: &attr::Attr) -> syn::Result<()> {
+    match attr.primary_key {
+        Some(ref primary_key)
+            if primary_key.sequence.is_some() && attr.db_type() != database::DbType::Postgres =>
+        {
+            Err(syn::Error::new_spanned(
+                &primary_key.ident,
+                "`primary_key(sequence)` is only supported by Postgres",
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Without `RETURNING`, the auto increment key is read back with `last_insert_id()`, which is an integer
+fn check_auto_increment_primary_key(attr: &attr::Attr) -> syn::Result<()> {
+    match attr.primary_key {
+        Some(ref primary_key)
+            if primary_key.auto_increment
+                && !primary_key.is_integer()
+                && !attr.supports_returning()
+                && !attr.parsed_struct.return_nothing
+                && attr.operations.contains(&Operation::Create) =>
+        {
+            let backend = match attr.db_type() {
+                database::DbType::MySQL => "MySQL",
+                _ => "SQLite without `RETURNING`",
+            };
+            Err(syn::Error::new_spanned(
+                &primary_key._type,
+                format!("{backend} only supports auto increment primary keys with an integer type (eg: i64, u32, ...)"),
+            ))
+        }
+        _ => Ok(()),
+    }
+}
 
-    let table_name_fn = if attr.parsed_struct.struct_type == types::StructType::Generic {
-        get_table_name(attr)
+fn check_returning_generated(attr: &attr::Attr) -> syn::Result<()> {
+    if !attr.parsed_struct.returning_generated {
+        return Ok(());
+    }
+    if !attr.supports_returning() {
+        return Err(syn::Error::new_spanned(
+            &attr.parsed_struct.name,
+            "`returning_generated` requires `RETURNING`, which is not supported by MySQL or `sqlite-no-returning`",
+        ));
+    }
+    if attr.parsed_struct.return_nothing {
+        return Err(syn::Error::new_spanned(
+            &attr.parsed_struct.name,
+            "`returning_generated` cannot be used with `return_object = \"()\"`",
+        ));
+    }
+    Ok(())
+}
+
+/// An enum mapped to a single table only gets `get_by_id()` along with the metadata of the table
+fn generate_enum_impl(attr: &attr::Attr) -> proc_macro2::TokenStream {
+    let enum_name = &attr.parsed_struct.name;
+    let (impl_generics, ty_generics, where_clause) = attr.parsed_struct.generics.split_for_impl();
+
+    let table_name_fn = get_table_name(attr);
+    let columns_fn = quotes::get_columns(attr);
+    let primary_key_name_fn = quotes::get_primary_key_name(attr);
+    let get_by_id_fn = quotes::custom_error_fns(attr, quotes::get_variant_by_id_fn(attr), false);
+    let track_env_vars = quotes::track_env_vars(attr);
+
+    let enum_impl = quote! {
+        impl #impl_generics #enum_name #ty_generics #where_clause {
+            #table_name_fn
+            #columns_fn
+            #primary_key_name_fn
+            #get_by_id_fn
+        }
+    };
+    let enum_impl = if cfg!(feature = "tracing") {
+        quotes::instrument_fns(attr, enum_impl)
     } else {
-        quote! {}
+        enum_impl
     };
+    quote! {
+        #track_env_vars
+        #enum_impl
+    }
+}
+
+fn generate_impl(attr: &attr::Attr) -> syn::Result<proc_macro2::TokenStream> {
+    if attr.parsed_struct.discriminator.is_some() {
+        return Ok(generate_enum_impl(attr));
+    }
+    check_primary_key(attr)?;
+    check_create_primary_key(attr)?;
+    check_belongs_to(attr)?;
+    check_from_row(attr)?;
+    check_sequence(attr)?;
+    check_auto_increment_primary_key(attr)?;
+    check_returning_generated(attr)?;
+
+    let struct_name = &attr.parsed_struct.name;
+    let (impl_generics, ty_generics, where_clause) = attr.parsed_struct.generics.split_for_impl();
+
+    let table_name_fn = get_table_name(attr);
+    let table_exists_fn = quotes::custom_error_fns(attr, quotes::table_exists_fn(attr), false);
+    let columns_fn = quotes::get_columns(attr);
+    let operations_fn = quotes::get_operations(attr);
+    let primary_key_name_fn = quotes::get_primary_key_name(attr);
+    let is_deleted_fn = quotes::is_deleted_fn(attr);
 
     let get_impl = if attr.operations.contains(&Operation::Get) {
-        quotes::get_by_id_fn(attr)
+        let get_by_id_fn = quotes::get_by_id_fn(attr);
+        let get_by_id_with_deleted_fn = quotes::get_by_id_with_deleted_fn(attr);
+        let get_by_id_or_err_fn = quotes::get_by_id_or_err_fn(attr);
+        let get_by_fn = quotes::get_by_fn(attr);
+        let get_by_ids_ordered_fn = quotes::get_by_ids_ordered_fn(attr);
+        let existing_ids_fn = quotes::existing_ids_fn(attr);
+        let stream_by_ids_fn = quotes::stream_by_ids_fn(attr);
+        let find_one_by_fns = quotes::find_one_by_fns(attr);
+        let get_by_id_with_fns = quotes::get_by_id_with_fns(attr);
+        let get_fns = quotes::custom_error_fns(
+            attr,
+            quote! {
+                #get_by_id_fn
+                #get_by_id_with_deleted_fn
+                #get_by_id_or_err_fn
+                #get_by_fn
+                #get_by_ids_ordered_fn
+                #existing_ids_fn
+                #find_one_by_fns
+            },
+            false,
+        );
+        let get_by_id_with_fns = quotes::custom_error_fns(attr, get_by_id_with_fns, true);
+        quote! {
+            #get_fns
+            #stream_by_ids_fn
+            #get_by_id_with_fns
+        }
     } else {
         quote! {}
     };
 
-    let list_impl = if attr.operations.contains(&Operation::List) {
-        quotes::list_all_fn(attr)
+    let (list_impl, filter_struct) = if attr.operations.contains(&Operation::List) {
+        let list_all_fn = quotes::list_all_fn(attr);
+        let list_all_with_deleted_fn = quotes::list_all_with_deleted_fn(attr);
+        let list_all_ordered_fn = quotes::list_all_ordered_fn(attr);
+        let list_projection_fns = quotes::list_projection_fns(attr);
+        let list_page_fn = quotes::list_page_fn(attr);
+        let list_after_fn = quotes::list_after_fn(attr);
+        let list_matching_fn = quotes::list_matching_fn(attr);
+        let list_fns = quotes::custom_error_fns(
+            attr,
+            quote! {
+                #list_all_fn
+                #list_all_with_deleted_fn
+                #list_all_ordered_fn
+                #list_projection_fns
+                #list_page_fn
+                #list_after_fn
+                #list_matching_fn
+            },
+            false,
+        );
+        (list_fns, quotes::filter_struct(attr))
     } else {
-        quote! {}
+        (quote! {}, quote! {})
     };
 
     let create_impl = if attr.operations.contains(&Operation::Create) {
-        quotes::create_fn(attr)
+        let create_fn = quotes::create_fn(attr);
+        let create_for_fn = quotes::create_for_fn(attr);
+        let create_if_not_exists_fn = quotes::create_if_not_exists_fn(attr);
+        let create_many_fn = quotes::create_many_fn(attr);
+        let create_returning_id_fn = quotes::create_returning_id_fn(attr);
+        let create_fns = quotes::custom_error_fns(
+            attr,
+            quote! {
+                #create_fn
+                #create_for_fn
+                #create_if_not_exists_fn
+                #create_many_fn
+            },
+            false,
+        );
+        let create_returning_id_fn = quotes::custom_error_fns(attr, create_returning_id_fn, true);
+        quote! {
+            #create_fns
+            #create_returning_id_fn
+        }
     } else {
         quote! {}
     };
 
     let update_impl = if attr.operations.contains(&Operation::Update) {
-        quotes::update_fn(attr)
+        let update_fn = quotes::update_fn(attr);
+        let touch_fn = quotes::touch_fn(attr);
+        quotes::custom_error_fns(
+            attr,
+            quote! {
+                #update_fn
+                #touch_fn
+            },
+            false,
+        )
+    } else {
+        quote! {}
+    };
+
+    let upsert_impl = if attr.operations.contains(&Operation::Create)
+        && attr.operations.contains(&Operation::Update)
+    {
+        let upsert_with_outcome_fn = quotes::upsert_with_outcome_fn(attr);
+        let upsert_with_outcome_fn = quotes::custom_error_fns(attr, upsert_with_outcome_fn, false);
+        let upsert_many_fn = quotes::custom_error_fns(attr, quotes::upsert_many_fn(attr), false);
+        let save_fn = quotes::custom_error_fns(attr, quotes::save_fn(attr), true);
+        quote! {
+            #upsert_with_outcome_fn
+            #upsert_many_fn
+            #save_fn
+        }
     } else {
         quote! {}
     };
 
     let delete_impl = if attr.operations.contains(&Operation::Delete) {
-        quotes::delete_fn(attr)
+        let delete_fn = quotes::delete_fn(attr);
+        let delete_all_fn = quotes::delete_all_fn(attr);
+        let delete_by_fns = quotes::delete_by_fns(attr);
+        quotes::custom_error_fns(
+            attr,
+            quote! {
+                #delete_fn
+                #delete_all_fn
+                #delete_by_fns
+            },
+            false,
+        )
     } else {
         quote! {}
     };
 
-    quote! {
-        impl #struct_name {
+    let tiny_table_impl = if [
+        Operation::Get,
+        Operation::Create,
+        Operation::Update,
+        Operation::Delete,
+    ]
+    .iter()
+    .all(|operation| attr.operations.contains(operation))
+        // `TinyTable::get_by_id` takes the primary key
+        && attr.lookup_key.is_none()
+        // The `TinyTable` methods return a `sqlx::Result`
+        && attr.parsed_struct.error.is_none()
+        // `TinyTable::delete` takes an executor, not a connection to begin a transaction with
+        && !attr.parsed_struct.has_many.iter().any(|has_many| has_many.cascade_delete)
+    {
+        quotes::tiny_table_impl(attr)
+    } else {
+        quote! {}
+    };
+
+    let from_row_impl = if attr.parsed_struct.from_row {
+        quotes::from_row_impl(attr)
+    } else {
+        quote! {}
+    };
+
+    let sql_fns = quotes::sql_fns(attr);
+    let bulk_update_fns = quotes::custom_error_fns(attr, quotes::bulk_update_fns(attr), false);
+    let [create_impl, update_impl, upsert_impl, delete_impl, bulk_update_fns] = [
+        create_impl,
+        update_impl,
+        upsert_impl,
+        delete_impl,
+        bulk_update_fns,
+    ]
+    .map(|fns| quotes::must_use_fns(attr, fns));
+    let set_option_builder_fns = quotes::set_option_builder_fns(attr);
+    let belongs_to_fns = quotes::custom_error_fns(attr, quotes::belongs_to_fns(attr), true);
+    let has_many_fns = quotes::custom_error_fns(attr, quotes::has_many_fns(attr), false);
+
+    let struct_impl = quote! {
+        impl #impl_generics #struct_name #ty_generics #where_clause {
             #table_name_fn
+            #table_exists_fn
+            #columns_fn
+            #operations_fn
+            #primary_key_name_fn
+            #is_deleted_fn
+            #sql_fns
             #get_impl
             #list_impl
             #create_impl
             #update_impl
+            #upsert_impl
             #delete_impl
+            #bulk_update_fns
+            #set_option_builder_fns
+            #belongs_to_fns
+            #has_many_fns
         }
-    }
+    };
+    let struct_impl = if cfg!(feature = "tracing") {
+        quotes::instrument_fns(attr, struct_impl)
+    } else {
+        struct_impl
+    };
+
+    let track_env_vars = quotes::track_env_vars(attr);
+
+    Ok(quote! {
+        #track_env_vars
+        #from_row_impl
+        #tiny_table_impl
+        #struct_impl
+        #filter_struct
+    })
 }
@@ -2,112 +2,191 @@ use quote::{format_ident, quote};
 
 use crate::{
     attr::Attr,
-    database::{self, DbType},
-    types::{Column, PrimaryKey, ReturnObject},
+    database::DbType,
+    types::{quote_identifier, Column, Operation, PrimaryKey, ReturnObject},
 };
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
 enum ReturnType {
     PrimaryKey(PrimaryKey),
     EntireRow(ReturnObject),
     OptionalRow(ReturnObject),
     MultipleRows(ReturnObject),
+    /// The primary key followed by the columns filled in by the database, as a tuple
+    GeneratedColumns(Vec<Column>),
     None,
 }
 
 impl ReturnType {
-    fn function_output(self) -> proc_macro2::TokenStream {
+    fn output_type(self) -> proc_macro2::TokenStream {
         match self {
             ReturnType::PrimaryKey(primary_key) => {
-                let pk_type = primary_key._type;
-                quote! {
-                    ::sqlx::Result<#pk_type>
-                }
+                let pk_type = primary_key.unwrapped_type();
+                quote! { #pk_type }
             }
-            ReturnType::EntireRow(return_object) => quote! {
-                ::sqlx::Result<#return_object>
-            },
-            ReturnType::OptionalRow(return_object) => quote! {
-                ::sqlx::Result<Option<#return_object>>
-            },
-            ReturnType::MultipleRows(return_object) => quote! {
-                ::sqlx::Result<Vec<#return_object>>
-            },
-            ReturnType::None => quote! {
-                ::sqlx::Result<()>
-            },
+            ReturnType::EntireRow(return_object) => quote! { #return_object },
+            ReturnType::OptionalRow(return_object) => quote! { Option<#return_object> },
+            ReturnType::MultipleRows(return_object) => quote! { Vec<#return_object> },
+            ReturnType::GeneratedColumns(columns) => {
+                let types = columns.iter().map(|column| column.unwrapped_type());
+                quote! { (#(#types,)*) }
+            }
+            ReturnType::None => quote! { () },
         }
     }
 
-    fn returning_statement(self) -> proc_macro2::TokenStream {
-        // MySQL does not support the RETURNING statement
-        if database::db_type() == DbType::MySQL {
+    fn function_output(self) -> proc_macro2::TokenStream {
+        let output_type = self.output_type();
+        quote! {
+            ::sqlx::Result<#output_type>
+        }
+    }
+
+    fn returning_statement(self, attr: &Attr) -> proc_macro2::TokenStream {
+        if !attr.supports_returning() {
             return quote! {};
         }
         match self {
             ReturnType::PrimaryKey(primary_key) => {
-                let pk_name = primary_key.name;
+                let pk_name = primary_key.sql_name(&attr.db_type());
                 quote! {
                     qb.push(" RETURNING ");
                     qb.push(#pk_name);
                 }
             }
             ReturnType::EntireRow(_) | ReturnType::OptionalRow(_) | ReturnType::MultipleRows(_) => {
+                match &attr.parsed_struct.returning {
+                    Some(returning) => {
+                        let returning_str =
+                            format!(" RETURNING {} ", returning.sql(&attr.db_type()));
+                        quote! {
+                            qb.push(#returning_str);
+                        }
+                    }
+                    None => quote! {
+                        qb.push(" RETURNING * ");
+                    },
+                }
+            }
+            ReturnType::GeneratedColumns(columns) => {
+                let returning_str = format!(
+                    " RETURNING {}",
+                    generated_column_names(&columns, &attr.db_type())
+                );
                 quote! {
-                    qb.push(" RETURNING * ");
+                    qb.push(#returning_str);
                 }
             }
             ReturnType::None => quote! {},
         }
     }
 
-    fn query_builder_execution(self) -> proc_macro2::TokenStream {
-        match (database::db_type(), self) {
-            (
-                DbType::MySQL,
-                ReturnType::PrimaryKey(Column {
-                    auto_increment: true,
-                    ..
-                }),
-            ) => quote! {
-                qb.build()
-                .execute(db)
-                .await
-                .map(|result| result.last_insert_id() as _)
-            },
-            (DbType::MySQL, ReturnType::PrimaryKey(primary_key)) => {
-                let pk_ident = primary_key.ident;
+    fn returning_sql(&self, attr: &Attr) -> String {
+        if !attr.supports_returning() {
+            return String::new();
+        }
+        match self {
+            ReturnType::PrimaryKey(primary_key) => {
+                format!(" RETURNING {}", primary_key.sql_name(&attr.db_type()))
+            }
+            ReturnType::EntireRow(_) | ReturnType::OptionalRow(_) | ReturnType::MultipleRows(_) => {
+                match &attr.parsed_struct.returning {
+                    Some(returning) => format!(" RETURNING {}", returning.sql(&attr.db_type())),
+                    None => " RETURNING *".to_string(),
+                }
+            }
+            ReturnType::GeneratedColumns(columns) => {
+                format!(
+                    " RETURNING {}",
+                    generated_column_names(columns, &attr.db_type())
+                )
+            }
+            ReturnType::None => String::new(),
+        }
+    }
+
+    fn query_builder_execution(self, attr: &Attr) -> proc_macro2::TokenStream {
+        self.query_execution(attr, quote! { qb.build() }, quote! { qb.build_query_as() })
+    }
+
+    fn query_execution(
+        self,
+        attr: &Attr,
+        query: proc_macro2::TokenStream,
+        query_as: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let backend = match attr.db_type() {
+            DbType::MySQL => "MySQL",
+            _ => "SQLite without `RETURNING`",
+        };
+        let last_insert_id = last_insert_id(attr);
+        match (attr.supports_returning(), self) {
+            (false, ReturnType::PrimaryKey(primary_key)) if primary_key.auto_increment => {
+                // The last inserted id is an integer, `check_auto_increment_primary_key` rejects any other type
                 quote! {
-                    qb.build()
+                    #query
+                    .execute(db)
+                    .await
+                    .map(|result| #last_insert_id as _)
+                }
+            }
+            (false, ReturnType::PrimaryKey(primary_key)) => {
+                // Without `RETURNING`, the inserted key cannot be returned, so it is the only path that needs `Clone`.
+                // The key is only cloned once the insert has succeeded.
+                let pk_ident = &primary_key.ident;
+                let pk_output = if primary_key.is_optional_primary_key() {
+                    let error =
+                        format!("{backend} cannot return a primary key generated by the database");
+                    quote! {
+                        match &self.#pk_ident {
+                            Some(id) => Ok(id.clone()),
+                            None => Err(::sqlx::Error::Protocol(#error.to_string())),
+                        }
+                    }
+                } else {
+                    quote! { Ok(self.#pk_ident.clone()) }
+                };
+                // An insert that affected no row (eg: `INSERT IGNORE`) must not report the key as created
+                quote! {
+                    let result = #query
                     .execute(db)
                     .await?;
+                    if result.rows_affected() == 0 {
+                        return Err(::sqlx::Error::RowNotFound);
+                    }
 
-                    Ok(self.#pk_ident.clone())
+                    #pk_output
                 }
             }
             (_, ReturnType::PrimaryKey(_)) => quote! {
-                qb.build()
+                #query
                 .fetch_one(db)
                 .await
-                .map(|row| row.get(0))
+                .map(|row| ::sqlx::Row::get(&row, 0))
             },
             (_, ReturnType::EntireRow(_)) => quote! {
-                qb.build_query_as()
+                #query_as
                 .fetch_one(db)
                 .await
             },
             (_, ReturnType::OptionalRow(_)) => quote! {
-                qb.build_query_as()
+                #query_as
                 .fetch_optional(db)
                 .await
             },
             (_, ReturnType::MultipleRows(_)) => quote! {
-                qb.build_query_as()
+                #query_as
                 .fetch_all(db)
                 .await
             },
+            (_, ReturnType::GeneratedColumns(_)) => quote! {
+                #query_as
+                .fetch_one(db)
+                .await
+            },
             (_, ReturnType::None) => quote! {
-                qb.build()
+                #query
                 .execute(db)
                 .await
                 .map(|_| ())
@@ -116,521 +195,5948 @@ impl ReturnType {
     }
 }
 
+fn generated_column_names(columns: &[Column], db_type: &DbType) -> String {
+    columns
+        .iter()
+        .map(|column| column.sql_name(db_type))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn where_statement(attr: &Attr) -> proc_macro2::TokenStream {
+    match attr.soft_deletion {
+        Some(ref soft_deletion) => {
+            let where_str = format!(" WHERE {} AND ", soft_deletion.condition());
+            quote! {
+                qb.push(#where_str);
+            }
+        }
+        None => quote! {
+            qb.push(" WHERE ");
+        },
+    }
+}
+
+fn list_where_statement(attr: &Attr) -> proc_macro2::TokenStream {
+    match attr.soft_deletion {
+        Some(ref soft_deletion) => {
+            let where_str = format!(" WHERE {} ", soft_deletion.condition());
+            quote! {
+                qb.push(#where_str);
+            }
+        }
+        None => quote! {},
+    }
+}
+
+/// The conditions of the `SELECT` queries, the soft deletion and the `scope` of the struct.
+fn select_conditions(attr: &Attr) -> Vec<String> {
+    let mut conditions = Vec::new();
+    if let Some(ref soft_deletion) = attr.soft_deletion {
+        conditions.push(soft_deletion.condition());
+    }
+    if let Some(ref scope) = attr.parsed_struct.scope {
+        conditions.push(format!("({scope})"));
+    }
+    conditions
+}
+
+/// Same as `where_statement` with the `scope` of the struct
+fn select_where_statement(attr: &Attr) -> proc_macro2::TokenStream {
+    let where_str = select_conditions(attr)
+        .iter()
+        .fold(" WHERE ".to_string(), |where_str, condition| {
+            format!("{where_str}{condition} AND ")
+        });
+    quote! {
+        qb.push(#where_str);
+    }
+}
+
+/// Same as `list_where_statement` with the `scope` of the struct
+fn select_list_where_statement(attr: &Attr) -> proc_macro2::TokenStream {
+    let conditions = select_conditions(attr);
+    if conditions.is_empty() {
+        return quote! {};
+    }
+    let where_str = format!(" WHERE {} ", conditions.join(" AND "));
+    quote! {
+        qb.push(#where_str);
+    }
+}
+
+/// The default order of the `list` queries, set with `order_by`
+fn order_by_statement(attr: &Attr) -> proc_macro2::TokenStream {
+    match attr.parsed_struct.order_by {
+        Some(ref order_by) => {
+            let order_by_str = format!(" ORDER BY {order_by}");
+            quote! {
+                qb.push(#order_by_str);
+            }
+        }
+        None => quote! {},
+    }
+}
+
+/// The variables read by `env!("VAR")` values are only read by the macro, which rustc does not track.
+/// Reading them again with `env!` recompiles the crate, so expands the macro again, when one of them changes.
+pub fn track_env_vars(attr: &Attr) -> proc_macro2::TokenStream {
+    let env_vars = &attr.parsed_struct.env_vars;
+    quote! {
+        #(const _: &str = ::core::env!(#env_vars);)*
+    }
+}
+
 pub fn get_table_name(attr: &Attr) -> proc_macro2::TokenStream {
     let table_name = attr.parsed_struct.table_name.0.as_str();
+    let table_name_fn = attr
+        .parsed_struct
+        .table_name_fn
+        .clone()
+        .unwrap_or_else(|| format_ident!("table_name"));
     quote! {
-        pub fn table_name<'a>() -> &'a str {
-            #table_name
+        pub const TABLE_NAME: &'static str = #table_name;
+
+        pub fn #table_name_fn() -> &'static str {
+            Self::TABLE_NAME
         }
     }
 }
 
-pub fn get_by_id_fn(attr: &Attr) -> proc_macro2::TokenStream {
-    let db_type_ident = database::db_type().to_ident();
-    let return_type = ReturnType::OptionalRow(attr.clone().parsed_struct.return_object);
-    let function_output = return_type.clone().function_output();
-    let query_builder_execution = return_type.query_builder_execution();
-    let table_name = attr.parsed_struct.table_name.clone().to_string();
-
-    let (pk_name, pk_type) = match attr.primary_key {
-        Some(ref pk) => (&pk.name, &pk._type),
-        None => panic!("No primary key field found which is mandatory for the 'get' operation"),
+pub fn table_exists_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    let db_type_ident = attr.db_type().to_ident();
+    let table_name = attr.parsed_struct.table_name.table();
+    // A qualified name is looked up in its own schema
+    let catalog_str = match (attr.db_type(), attr.parsed_struct.table_name.qualifier()) {
+        (DbType::Postgres | DbType::MySQL, Some(qualifier)) => format!("SELECT COUNT(*) FROM information_schema.tables WHERE table_schema = '{qualifier}' AND table_name = "),
+        (DbType::Postgres, None) => "SELECT COUNT(*) FROM information_schema.tables WHERE table_schema = current_schema() AND table_name = ".to_string(),
+        (DbType::MySQL, None) => "SELECT COUNT(*) FROM information_schema.tables WHERE table_schema = DATABASE() AND table_name = ".to_string(),
+        (DbType::Sqlite, Some(qualifier)) => format!("SELECT COUNT(*) FROM {qualifier}.sqlite_master WHERE type = 'table' AND name = "),
+        (DbType::Sqlite, None) => "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ".to_string(),
     };
 
-    let where_statement = match attr.soft_deletion {
-        true => quote! {
-            qb.push(" WHERE deleted_at IS NULL AND ");
-        },
-        false => quote! {
-            qb.push(" WHERE ");
-        },
-    };
     quote! {
-        pub async fn get_by_id<'e, E>(db: E, id: &#pk_type) -> #function_output
+        pub async fn table_exists<'e, E>(db: E) -> ::sqlx::Result<bool>
         where
             E: ::sqlx::#db_type_ident<'e>
         {
-        let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
-            qb.push(#table_name);
-            #where_statement
-            qb.push(#pk_name);
-            qb.push(" = ");
-            qb.push_bind(id);
+            let mut qb = ::sqlx::QueryBuilder::new(#catalog_str);
+            qb.push_bind(#table_name);
 
-            #query_builder_execution
+            qb.build_query_scalar()
+            .fetch_one(db)
+            .await
+            .map(|count: i64| count > 0)
         }
     }
 }
 
-pub fn list_all_fn(attr: &Attr) -> proc_macro2::TokenStream {
-    let db_type_ident = database::db_type().to_ident();
-    let return_type = ReturnType::MultipleRows(attr.clone().parsed_struct.return_object);
-    let function_output = return_type.clone().function_output();
-    let query_builder_execution = return_type.query_builder_execution();
-    let table_name = attr.parsed_struct.table_name.clone().to_string();
+pub fn get_columns(attr: &Attr) -> proc_macro2::TokenStream {
+    let column_names = attr.columns.iter().map(|column| column.name.as_str());
+    quote! {
+        pub const COLUMNS: &'static [&'static str] = &[#(#column_names),*];
 
-    let where_statement = match attr.soft_deletion {
-        true => quote! {
-            qb.push(" WHERE deleted_at IS NULL ");
-        },
-        false => quote! {},
-    };
+        pub fn columns() -> &'static [&'static str] {
+            Self::COLUMNS
+        }
+    }
+}
 
+pub fn get_operations(attr: &Attr) -> proc_macro2::TokenStream {
+    let operation_names = Operation::all()
+        .into_iter()
+        .filter(|operation| attr.operations.contains(operation))
+        .map(|operation| operation.to_string());
     quote! {
-        pub async fn list_all<'e, E>(db: E) -> #function_output
-        where
-            E: ::sqlx::#db_type_ident<'e>
-        {
-            let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
-            qb.push(#table_name);
-            #where_statement
-            #query_builder_execution
+        pub const OPERATIONS: &'static [&'static str] = &[#(#operation_names),*];
+
+        pub fn operations() -> &'static [&'static str] {
+            Self::OPERATIONS
         }
     }
 }
 
-pub fn create_fn(attr: &Attr) -> proc_macro2::TokenStream {
-    let db_type = database::db_type();
-    let db_type_ident = db_type.clone().to_ident();
-    let table_name = attr.parsed_struct.table_name.clone().to_string();
-
-    let mysql_specific_error = r#"MySQL does not support the `RETURNING *` statement
-    Thus it's not possible to create a record without a known primary_key column with the `Table` macro.
-    If an auto increment column is used, set a dummy value and it will be ignored."#;
-    let return_type = match (&db_type, attr.primary_key.clone()) {
-        (&DbType::MySQL, None) => panic!("{mysql_specific_error}"),
-        (_, None) => ReturnType::EntireRow(attr.parsed_struct.return_object.clone()),
-        (_, Some(primary_key)) => ReturnType::PrimaryKey(primary_key),
+pub fn get_primary_key_name(attr: &Attr) -> proc_macro2::TokenStream {
+    let primary_key_name = match attr.primary_key {
+        Some(ref pk) => {
+            let pk_name = pk.name.as_str();
+            quote! { Some(#pk_name) }
+        }
+        None => quote! { None },
     };
+    quote! {
+        pub const PRIMARY_KEY_NAME: Option<&'static str> = #primary_key_name;
 
-    let function_output = return_type.clone().function_output();
-    let returning_statement = return_type.clone().returning_statement();
-    let query_builder_execution = return_type.query_builder_execution();
-
-    let mut field_str_quote = Vec::new();
-    let mut field_values_quote = Vec::new();
-
-    for column in attr.columns.iter() {
-        if column.auto_increment {
-            continue;
+        pub fn primary_key_name() -> Option<&'static str> {
+            Self::PRIMARY_KEY_NAME
         }
-        let str_quote = if column.use_set_options() {
-            let column_ident = &column.ident;
-            let column_name = &column.name;
-            quote! {
-                if self.#column_ident.is_set() {
-                    fields_str.push(#column_name);
-                }
-            }
-        } else {
-            let column_name = &column.name;
-            quote! {
-                fields_str.push(#column_name);
-            }
-        };
-        field_str_quote.push(str_quote);
+    }
+}
 
-        let value_quote = if column.use_set_options() {
+/// Only generated when the soft deletion column is an `Option` field of the struct, or a `bool` one
+pub fn is_deleted_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    let Some(ref soft_deletion) = attr.soft_deletion else {
+        return quote! {};
+    };
+    let column = attr
+        .columns
+        .iter()
+        .find(|column| column.name == soft_deletion.column);
+    match column {
+        Some(column) if soft_deletion.boolean && column._type == syn::parse_quote!(bool) => {
             let column_ident = &column.ident;
             quote! {
-                if let SetOption::Set(v) = &self.#column_ident {
-                    separated.push_bind(v);
+                pub fn is_deleted(&self) -> bool {
+                    self.#column_ident
                 }
             }
-        } else {
+        }
+        Some(column) if !soft_deletion.boolean && column.option_inner_type().is_some() => {
             let column_ident = &column.ident;
             quote! {
-                separated.push_bind(&self.#column_ident);
+                pub fn is_deleted(&self) -> bool {
+                    self.#column_ident.is_some()
+                }
             }
-        };
-        field_values_quote.push(value_quote);
+        }
+        _ => quote! {},
+    }
+}
+
+fn select_by_id_statement(attr: &Attr, operation: &str) -> proc_macro2::TokenStream {
+    let table_name = attr.parsed_struct.table_name.quoted(&attr.db_type());
+    let pk_name = match attr.lookup_column() {
+        Some(pk) => pk.sql_name(&attr.db_type()),
+        None => {
+            panic!("No primary key field found which is mandatory for the '{operation}' operation")
+        }
+    };
+
+    let where_statement = select_where_statement(attr);
+    quote! {
+        let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+        qb.push(#table_name);
+        #where_statement
+        qb.push(#pk_name);
+        qb.push(" = ");
+        qb.push_bind(id);
     }
+}
+
+pub fn get_by_id_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    let db_type_ident = attr.db_type().to_ident();
+    let return_type = ReturnType::OptionalRow(attr.parsed_struct.return_object.clone());
+    let function_output = return_type.clone().function_output();
+    let query_builder_execution = return_type.query_builder_execution(attr);
+    let select_statement = select_by_id_statement(attr, "get");
+    let pk_type = attr.lookup_column().unwrap().unwrapped_type();
 
     quote! {
-        pub async fn create<'e, E>(&self, db: E) -> #function_output
+        pub async fn get_by_id<'e, E>(db: E, id: impl ::std::borrow::Borrow<#pk_type>) -> #function_output
         where
             E: ::sqlx::#db_type_ident<'e>
         {
-            let mut fields_str = Vec::new();
+            let id = ::std::borrow::Borrow::<#pk_type>::borrow(&id);
+            #select_statement
 
-            #(#field_str_quote)*
-
-            let mut qb = ::sqlx::QueryBuilder::new("INSERT INTO ");
-            qb.push(#table_name);
-            qb.push(" (");
-            qb.push(fields_str.join(", "));
-            qb.push(") VALUES (");
+            #query_builder_execution
+        }
+    }
+}
 
-            let mut separated = qb.separated(", ");
-            #(#field_values_quote)*
-            separated.push_unseparated(")");
+/// The `get_by_id` of an enum mapped to a single table builds the variant named by the discriminator column
+pub fn get_variant_by_id_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    let db_type_ident = attr.db_type().to_ident();
+    let select_statement = select_by_id_statement(attr, "get");
+    let pk_type = attr.lookup_column().unwrap().unwrapped_type();
+    let discriminator = attr
+        .parsed_struct
+        .discriminator
+        .as_deref()
+        .unwrap_or("kind");
+    let arms = attr.parsed_struct.variants.iter().map(|variant| {
+        let variant_ident = &variant.ident;
+        let variant_discriminator = &variant.discriminator;
+        let fields = row_fields(&variant.columns, &variant.skipped_fields);
+        quote! {
+            #variant_discriminator => Self::#variant_ident {
+                #(#fields),*
+            },
+        }
+    });
 
-            #returning_statement
+    quote! {
+        pub async fn get_by_id<'e, E>(db: E, id: impl ::std::borrow::Borrow<#pk_type>) -> ::sqlx::Result<Option<Self>>
+        where
+            E: ::sqlx::#db_type_ident<'e>
+        {
+            let id = ::std::borrow::Borrow::<#pk_type>::borrow(&id);
+            #select_statement
 
-            #query_builder_execution
+            let Some(row) = qb.build().fetch_optional(db).await? else {
+                return Ok(None);
+            };
+            let row = &row;
+            let kind: String = ::sqlx::Row::try_get(row, #discriminator)?;
+            Ok(Some(match kind.as_str() {
+                #(#arms)*
+                _ => {
+                    return Err(::sqlx::Error::ColumnDecode {
+                        index: #discriminator.to_string(),
+                        source: format!("no variant matches '{kind}'").into(),
+                    })
+                }
+            }))
         }
     }
 }
 
-pub fn update_fn(attr: &Attr) -> proc_macro2::TokenStream {
-    let db_type_ident = database::db_type().to_ident();
-
-    let self_ident = format_ident!("Self");
-    let return_type = match (database::db_type(), &attr.parsed_struct.return_object) {
-        (DbType::MySQL, _) => ReturnType::None, // MySQL is not capable to return the entire row.
-        (_, ident) if ident == &self_ident => ReturnType::None,
-        (_, _) => ReturnType::EntireRow(attr.parsed_struct.return_object.clone()),
+/// Same as `get_by_id` without the `soft_deletion` condition, only generated with `soft_deletion`
+pub fn get_by_id_with_deleted_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    if attr.soft_deletion.is_none() {
+        return quote! {};
+    }
+    let unfiltered_attr = Attr {
+        soft_deletion: None,
+        ..attr.clone()
     };
-
+    let db_type_ident = attr.db_type().to_ident();
+    let return_type = ReturnType::OptionalRow(attr.parsed_struct.return_object.clone());
     let function_output = return_type.clone().function_output();
-    let query_builder_execution = return_type.clone().query_builder_execution();
-    let returning_statement = return_type.returning_statement();
+    let query_builder_execution = return_type.query_builder_execution(attr);
+    let select_statement = select_by_id_statement(&unfiltered_attr, "get");
+    let pk_type = attr.lookup_column().unwrap().unwrapped_type();
 
-    let table_name = attr.parsed_struct.table_name.clone().to_string();
-    let (pk_name, pk_ident) = match attr.primary_key {
-        Some(ref pk) => (&pk.name, &pk.ident),
-        None => panic!("No primary key field found"),
-    };
-    let mut fields_quote = Vec::new();
+    quote! {
+        pub async fn get_by_id_with_deleted<'e, E>(db: E, id: impl ::std::borrow::Borrow<#pk_type>) -> #function_output
+        where
+            E: ::sqlx::#db_type_ident<'e>
+        {
+            let id = ::std::borrow::Borrow::<#pk_type>::borrow(&id);
+            #select_statement
 
-    for column in attr.columns.iter() {
-        if column.auto_increment || column.primary_key {
-            continue;
+            #query_builder_execution
         }
-        let column_ident = &column.ident;
-        let column_name = &column.name;
-
-        let quote = quote! {
-            if !first {
-                qb.push(", ");
-            }
-            qb.push(#column_name);
-            qb.push(" = ");
-            qb.push_bind(&self.#column_ident);
-            first = false;
-        };
-
-        let str_quote = if column.use_set_options() {
-            quote! {
-                if self.#column_ident.is_set() {
-                    #quote
-                }
-            }
-        } else {
-            quote
-        };
-        fields_quote.push(str_quote);
     }
+}
 
-    let where_statement = match attr.soft_deletion {
-        true => quote! {
-            qb.push(" WHERE deleted_at IS NULL AND ");
-        },
-        false => quote! {
-            qb.push(" WHERE ");
-        },
-    };
+pub fn get_by_id_or_err_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    let db_type_ident = attr.db_type().to_ident();
+    let return_type = ReturnType::EntireRow(attr.parsed_struct.return_object.clone());
+    let function_output = return_type.clone().function_output();
+    let query_builder_execution = return_type.query_builder_execution(attr);
+    let select_statement = select_by_id_statement(attr, "get");
+    let pk_type = attr.lookup_column().unwrap().unwrapped_type();
 
     quote! {
-        pub async fn update<'e, E>(&self, db: E) -> #function_output
+        pub async fn get_by_id_or_err<'e, E>(db: E, id: impl ::std::borrow::Borrow<#pk_type>) -> #function_output
         where
             E: ::sqlx::#db_type_ident<'e>
         {
-            let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
-            qb.push(#table_name);
-            qb.push(" SET ");
+            let id = ::std::borrow::Borrow::<#pk_type>::borrow(&id);
+            #select_statement
 
-            let mut first = true;
-            #(#fields_quote)*
+            #query_builder_execution
+        }
+    }
+}
+
+/// The names of `COLUMNS` in the SQL, for the methods given a column name
+fn sql_columns(attr: &Attr) -> proc_macro2::TokenStream {
+    let sql_names = attr
+        .columns
+        .iter()
+        .map(|column| column.sql_name(&attr.db_type()));
+    quote! {
+        const SQL_COLUMNS: &[&str] = &[#(#sql_names),*];
+    }
+}
+
+pub fn get_by_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    let db_type = attr.db_type();
+    let db_type_ident = db_type.to_ident();
+    let database_ident = db_type.to_database_ident();
+    let return_type = ReturnType::MultipleRows(attr.parsed_struct.return_object.clone());
+    let function_output = return_type.clone().function_output();
+    let query_builder_execution = return_type.query_builder_execution(attr);
+    let table_name = attr.parsed_struct.table_name.quoted(&attr.db_type());
+    let where_statement = select_where_statement(attr);
+    let sql_columns = sql_columns(attr);
+
+    quote! {
+        pub async fn get_by<'e, E, V>(db: E, column: &str, value: V) -> #function_output
+        where
+            E: ::sqlx::#db_type_ident<'e>,
+            V: 'e + ::sqlx::Encode<'e, ::sqlx::#database_ident> + ::sqlx::Type<::sqlx::#database_ident> + Send
+        {
+            // The column name is pushed in the query so it must be one of the known columns
+            #sql_columns
+            let Some(index) = Self::COLUMNS.iter().position(|name| *name == column) else {
+                return Err(::sqlx::Error::ColumnNotFound(column.to_string()));
+            };
 
+            let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+            qb.push(#table_name);
             #where_statement
-            qb.push(#pk_name);
+            qb.push(SQL_COLUMNS[index]);
             qb.push(" = ");
-            qb.push_bind(&self.#pk_ident);
-
-            #returning_statement
+            qb.push_bind(value);
 
             #query_builder_execution
         }
     }
 }
 
-pub fn delete_fn(attr: &Attr) -> proc_macro2::TokenStream {
-    let db_type_ident = database::db_type().to_ident();
-    let return_type = ReturnType::None;
-    let function_output = return_type.clone().function_output();
-    let query_builder_execution = return_type.query_builder_execution();
-    let table_name = attr.parsed_struct.table_name.clone().to_string();
-    let (pk_name, pk_ident) = match attr.primary_key {
-        Some(ref pk) => (&pk.name, &pk.ident),
-        None => panic!("No primary key field found"),
-    };
-    let delete_statement = match (attr.soft_deletion, database::db_type()) {
-        (true, DbType::Postgres) => quote! {
-            let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
-            qb.push(#table_name);
-            qb.push(" SET deleted_at = NOW() ");
-        },
-        (true, DbType::MySQL) => quote! {
-            let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
+pub fn get_by_ids_ordered_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    // The records are matched with the ids through their primary key field
+    if attr.parsed_struct.return_object != "Self" {
+        return quote! {};
+    }
+    let db_type = attr.db_type();
+    let db_type_ident = db_type.to_ident();
+    let table_name = attr.parsed_struct.table_name.quoted(&attr.db_type());
+    let where_statement = select_where_statement(attr);
+    let primary_key = attr.primary_key.as_ref().unwrap();
+    let pk_name = primary_key.sql_name(&attr.db_type());
+    let pk_ident = &primary_key.ident;
+    let pk_type = primary_key.unwrapped_type();
+
+    let query = match db_type {
+        DbType::Postgres => {
+            let order_by_str = format!(", {pk_name})");
+            quote! {
+                qb.push(" = ANY(");
+                qb.push_bind(ids);
+                qb.push(") ORDER BY array_position(");
+                qb.push_bind(ids);
+                qb.push(#order_by_str);
+
+                qb.build_query_as().fetch_all(db).await
+            }
+        }
+        DbType::MySQL | DbType::Sqlite => {
+            let pk_matches = if primary_key.is_optional_primary_key() {
+                quote! { item.#pk_ident.as_ref() == Some(id) }
+            } else {
+                quote! { &item.#pk_ident == id }
+            };
+            // There is no portable way to sort by the position in a list
+            quote! {
+                qb.push(" IN (");
+                let mut separated = qb.separated(", ");
+                for id in ids {
+                    separated.push_bind(id);
+                }
+                separated.push_unseparated(")");
+
+                let items: Vec<Self> = qb.build_query_as().fetch_all(db).await?;
+                let mut items: Vec<Option<Self>> = items.into_iter().map(Some).collect();
+                Ok(ids
+                    .iter()
+                    .filter_map(|id| {
+                        items
+                            .iter_mut()
+                            .find(|item| item.as_ref().is_some_and(|item| #pk_matches))
+                            .and_then(Option::take)
+                    })
+                    .collect())
+            }
+        }
+    };
+
+    quote! {
+        pub async fn get_by_ids_ordered<'e, E>(db: E, ids: &[#pk_type]) -> ::sqlx::Result<Vec<Self>>
+        where
+            E: ::sqlx::#db_type_ident<'e>
+        {
+            if ids.is_empty() {
+                return Ok(Vec::new());
+            }
+            let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
             qb.push(#table_name);
-            qb.push(" SET deleted_at = CURRENT_TIMESTAMP ");
-        },
-        (true, DbType::Sqlite) => quote! {
-            let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
+            #where_statement
+            qb.push(#pk_name);
+            #query
+        }
+    }
+}
+
+pub fn existing_ids_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    let db_type_ident = attr.db_type().to_ident();
+    let table_name = attr.parsed_struct.table_name.quoted(&attr.db_type());
+    let where_statement = select_where_statement(attr);
+    let primary_key = attr.primary_key.as_ref().unwrap();
+    let select_str = format!("SELECT {} FROM ", primary_key.sql_name(&attr.db_type()));
+    let pk_name = primary_key.sql_name(&attr.db_type());
+    let pk_type = primary_key.unwrapped_type();
+
+    quote! {
+        pub async fn existing_ids<'e, E>(db: E, ids: &[#pk_type]) -> ::sqlx::Result<Vec<#pk_type>>
+        where
+            E: ::sqlx::#db_type_ident<'e>
+        {
+            if ids.is_empty() {
+                return Ok(Vec::new());
+            }
+            let mut qb = ::sqlx::QueryBuilder::new(#select_str);
             qb.push(#table_name);
-            qb.push(" SET deleted_at = DATETIME('now') ");
-        },
-        (false, _) => quote! {
-            let mut qb = ::sqlx::QueryBuilder::new("DELETE FROM ");
+            #where_statement
+            qb.push(#pk_name);
+            qb.push(" IN (");
+            let mut separated = qb.separated(", ");
+            for id in ids {
+                separated.push_bind(id);
+            }
+            separated.push_unseparated(")");
+
+            qb.build_query_scalar().fetch_all(db).await
+        }
+    }
+}
+
+pub fn stream_by_ids_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    let db_type_ident = attr.db_type().to_ident();
+    let return_object = &attr.parsed_struct.return_object;
+    let table_name = attr.parsed_struct.table_name.quoted(&attr.db_type());
+    let where_statement = select_where_statement(attr);
+    let primary_key = attr.primary_key.as_ref().unwrap();
+    let pk_name = primary_key.sql_name(&attr.db_type());
+    let pk_type = primary_key.unwrapped_type();
+    let (item_type, error) = match attr.parsed_struct.error {
+        Some(ref error) => (
+            quote! { ::std::result::Result<#return_object, #error> },
+            quote! { ::core::convert::From::from(error) },
+        ),
+        None => (quote! { ::sqlx::Result<#return_object> }, quote! { error }),
+    };
+
+    quote! {
+        pub fn stream_by_ids<'e, E>(db: E, ids: Vec<#pk_type>, chunk_size: ::core::num::NonZeroUsize) -> impl ::tiny_orm::futures_util::Stream<Item = #item_type> + 'e
+        where
+            E: ::sqlx::#db_type_ident<'e> + Copy + 'e
+        {
+            let mut chunks = Vec::new();
+            let mut ids = ids;
+            while !ids.is_empty() {
+                let rest = ids.split_off(chunk_size.get().min(ids.len()));
+                chunks.push(::std::mem::replace(&mut ids, rest));
+            }
+
+            let results = ::tiny_orm::futures_util::StreamExt::then(
+                ::tiny_orm::futures_util::stream::iter(chunks),
+                move |ids| async move {
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push(#table_name);
+                    #where_statement
+                    qb.push(#pk_name);
+                    qb.push(" IN (");
+                    let mut separated = qb.separated(", ");
+                    for id in ids {
+                        separated.push_bind(id);
+                    }
+                    separated.push_unseparated(")");
+
+                    qb.build_query_as::<#return_object>().fetch_all(db).await
+                },
+            );
+            ::tiny_orm::futures_util::StreamExt::flat_map(results, |result| {
+                let items: Vec<#item_type> = match result {
+                    Ok(items) => items.into_iter().map(Ok).collect(),
+                    Err(error) => vec![Err(#error)],
+                };
+                ::tiny_orm::futures_util::stream::iter(items)
+            })
+        }
+    }
+}
+
+pub fn list_all_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    let db_type_ident = attr.db_type().to_ident();
+    let return_type = ReturnType::MultipleRows(attr.parsed_struct.return_object.clone());
+    let function_output = return_type.clone().function_output();
+    let query_builder_execution = return_type.query_builder_execution(attr);
+    let table_name = attr.parsed_struct.table_name.quoted(&attr.db_type());
+
+    let where_statement = select_list_where_statement(attr);
+    let order_by_statement = order_by_statement(attr);
+
+    quote! {
+        pub async fn list_all<'e, E>(db: E) -> #function_output
+        where
+            E: ::sqlx::#db_type_ident<'e>
+        {
+            let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
             qb.push(#table_name);
-        },
+            #where_statement
+            #order_by_statement
+            #query_builder_execution
+        }
+    }
+}
+
+/// Same as `list_all` without the `soft_deletion` condition, only generated with `soft_deletion`
+pub fn list_all_with_deleted_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    if attr.soft_deletion.is_none() {
+        return quote! {};
+    }
+    let unfiltered_attr = Attr {
+        soft_deletion: None,
+        ..attr.clone()
     };
-    let where_statement = match attr.soft_deletion {
-        true => quote! {
-            qb.push(" WHERE deleted_at IS NULL AND ");
-        },
-        false => quote! {
+    let db_type_ident = attr.db_type().to_ident();
+    let return_type = ReturnType::MultipleRows(attr.parsed_struct.return_object.clone());
+    let function_output = return_type.clone().function_output();
+    let query_builder_execution = return_type.query_builder_execution(attr);
+    let table_name = attr.parsed_struct.table_name.quoted(&attr.db_type());
+
+    let where_statement = select_list_where_statement(&unfiltered_attr);
+    let order_by_statement = order_by_statement(attr);
+
+    quote! {
+        pub async fn list_all_with_deleted<'e, E>(db: E) -> #function_output
+        where
+            E: ::sqlx::#db_type_ident<'e>
+        {
+            let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+            qb.push(#table_name);
+            #where_statement
+            #order_by_statement
+            #query_builder_execution
+        }
+    }
+}
+
+pub fn list_all_ordered_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    let db_type_ident = attr.db_type().to_ident();
+    let return_type = ReturnType::MultipleRows(attr.parsed_struct.return_object.clone());
+    let function_output = return_type.clone().function_output();
+    let query_builder_execution = return_type.query_builder_execution(attr);
+    let table_name = attr.parsed_struct.table_name.quoted(&attr.db_type());
+
+    let where_statement = select_list_where_statement(attr);
+    let sql_columns = sql_columns(attr);
+    // The default order is only used when no order is given, so that there is a single `ORDER BY`
+    let order_by_statement = match attr.parsed_struct.order_by {
+        Some(_) => {
+            let order_by_statement = order_by_statement(attr);
+            quote! {
+                if order.is_empty() {
+                    #order_by_statement
+                }
+            }
+        }
+        None => quote! {},
+    };
+
+    quote! {
+        pub async fn list_all_ordered<'e, E>(db: E, order: &[(&str, ::tiny_orm::Order)]) -> #function_output
+        where
+            E: ::sqlx::#db_type_ident<'e>
+        {
+            let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+            qb.push(#table_name);
+            #where_statement
+            #sql_columns
+            for (index, (column, direction)) in order.iter().enumerate() {
+                // Only the known columns are pushed in the query
+                let Some(position) = Self::COLUMNS.iter().position(|name| name == column) else {
+                    return Err(::sqlx::Error::ColumnNotFound(column.to_string()));
+                };
+                qb.push(if index == 0 { " ORDER BY " } else { ", " });
+                qb.push(SQL_COLUMNS[position]);
+                qb.push(" ");
+                qb.push(direction.as_sql());
+            }
+            #order_by_statement
+            #query_builder_execution
+        }
+    }
+}
+
+/// `list_all` reading only the columns of each projection into its own struct
+pub fn list_projection_fns(attr: &Attr) -> proc_macro2::TokenStream {
+    let db_type_ident = attr.db_type().to_ident();
+    let table_name = attr.parsed_struct.table_name.quoted(&attr.db_type());
+    let where_statement = select_list_where_statement(attr);
+    let order_by_statement = order_by_statement(attr);
+
+    let projection_fns = attr.parsed_struct.projections.iter().map(|projection| {
+        let fn_name = projection.fn_name(&attr.parsed_struct.name);
+        let target = &projection.target;
+        let select_str = format!("SELECT {} FROM ", projection.sql(&attr.db_type()));
+        quote! {
+            pub async fn #fn_name<'e, E>(db: E) -> ::sqlx::Result<Vec<#target>>
+            where
+                E: ::sqlx::#db_type_ident<'e>
+            {
+                let mut qb = ::sqlx::QueryBuilder::new(#select_str);
+                qb.push(#table_name);
+                #where_statement
+                #order_by_statement
+
+                qb.build_query_as::<#target>()
+                .fetch_all(db)
+                .await
+            }
+        }
+    });
+
+    quote! {
+        #(#projection_fns)*
+    }
+}
+
+/// Keyset pagination: the records after a value of the primary key (or of the `cursor` column) in its order
+pub fn list_after_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    let Some(cursor) = attr.cursor_column() else {
+        return quote! {};
+    };
+    let db_type_ident = attr.db_type().to_ident();
+    let return_type = ReturnType::MultipleRows(attr.parsed_struct.return_object.clone());
+    let function_output = return_type.clone().function_output();
+    let query_builder_execution = return_type.query_builder_execution(attr);
+    let table_name = attr.parsed_struct.table_name.quoted(&attr.db_type());
+    let cursor_type = cursor.unwrapped_type();
+    let cursor_name = cursor.sql_name(&attr.db_type());
+    let condition_str = format!("{cursor_name} > ");
+    let order_by_str = format!(" ORDER BY {cursor_name} LIMIT ");
+
+    let where_statement = select_where_statement(attr);
+
+    quote! {
+        pub async fn list_after<'e, E>(db: E, after: &#cursor_type, limit: i64) -> #function_output
+        where
+            E: ::sqlx::#db_type_ident<'e>
+        {
+            let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+            qb.push(#table_name);
+            #where_statement
+            qb.push(#condition_str);
+            qb.push_bind(after);
+            qb.push(#order_by_str);
+            qb.push_bind(limit);
+            #query_builder_execution
+        }
+    }
+}
+
+pub fn list_page_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    let database_ident = attr.db_type().to_database_ident();
+    let return_object = &attr.parsed_struct.return_object;
+    let table_name = attr.parsed_struct.table_name.quoted(&attr.db_type());
+    let where_statement = select_list_where_statement(attr);
+    let order_by_statement = order_by_statement(attr);
+
+    quote! {
+        pub async fn list_page<'c, A>(db: A, limit: i64, offset: i64) -> ::sqlx::Result<::tiny_orm::Page<#return_object>>
+        where
+            A: ::sqlx::Acquire<'c, Database = ::sqlx::#database_ident>
+        {
+            let mut conn = db.acquire().await?;
+
+            let mut qb = ::sqlx::QueryBuilder::new("SELECT COUNT(*) FROM ");
+            qb.push(#table_name);
+            #where_statement
+            let total: i64 = qb.build_query_scalar().fetch_one(&mut *conn).await?;
+
+            let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+            qb.push(#table_name);
+            #where_statement
+            #order_by_statement
+            qb.push(" LIMIT ");
+            qb.push_bind(limit);
+            qb.push(" OFFSET ");
+            qb.push_bind(offset);
+            let items = qb.build_query_as().fetch_all(&mut *conn).await?;
+
+            Ok(::tiny_orm::Page::new(items, total, limit, offset))
+        }
+    }
+}
+
+/// The columns of the filter struct of `list_matching`, a JSON value cannot be compared
+fn filter_columns(attr: &Attr) -> Vec<&Column> {
+    attr.columns.iter().filter(|column| !column.json).collect()
+}
+
+pub fn filter_struct(attr: &Attr) -> proc_macro2::TokenStream {
+    let struct_name = &attr.parsed_struct.name;
+    let filter_name = format_ident!("{}Filter", struct_name);
+    let vis = &attr.parsed_struct.vis;
+    let generics = &attr.parsed_struct.generics;
+    let where_clause = &generics.where_clause;
+    let doc = format!(
+        "The filter of `{struct_name}::list_matching()`, every field which is set must match"
+    );
+
+    let fields = filter_columns(attr).into_iter().map(|column| {
+        let column_ident = &column.ident;
+        let value_type = column.value_type();
+        quote! { pub #column_ident: Option<#value_type>, }
+    });
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Default)]
+        #vis struct #filter_name #generics #where_clause {
+            #(#fields)*
+        }
+    }
+}
+
+pub fn list_matching_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    let db_type_ident = attr.db_type().to_ident();
+    let return_type = ReturnType::MultipleRows(attr.parsed_struct.return_object.clone());
+    let function_output = return_type.clone().function_output();
+    let query_builder_execution = return_type.query_builder_execution(attr);
+    let table_name = attr.parsed_struct.table_name.quoted(&attr.db_type());
+    let filter_name = format_ident!("{}Filter", attr.parsed_struct.name);
+    let (_, ty_generics, _) = attr.parsed_struct.generics.split_for_impl();
+
+    let columns = filter_columns(attr);
+    let static_conditions = select_conditions(attr);
+    // Without static conditions, the `WHERE` is only needed when a field of the filter is set
+    let where_statement = match (static_conditions.is_empty(), columns.is_empty()) {
+        (false, _) => quote! {
             qb.push(" WHERE ");
         },
+        (true, true) => quote! {},
+        (true, false) => {
+            let is_set = columns.iter().map(|column| {
+                let column_ident = &column.ident;
+                quote! { filter.#column_ident.is_some() }
+            });
+            quote! {
+                if #(#is_set)||* {
+                    qb.push(" WHERE ");
+                }
+            }
+        }
+    };
+    let conditions = columns.iter().map(|column| {
+        let column_ident = &column.ident;
+        let condition_str = format!("{} = ", column.sql_name(&attr.db_type()));
+        quote! {
+            if let Some(value) = &filter.#column_ident {
+                separated.push(#condition_str);
+                separated.push_bind_unseparated(value);
+            }
+        }
+    });
+    let conditions = match (static_conditions.is_empty(), columns.is_empty()) {
+        (true, true) => quote! {},
+        _ => quote! {
+            let mut separated = qb.separated(" AND ");
+            #(separated.push(#static_conditions);)*
+            #(#conditions)*
+        },
     };
+    let order_by_statement = order_by_statement(attr);
+
     quote! {
-        pub async fn delete<'e, E>(&self, db: E) -> #function_output
+        pub async fn list_matching<'e, E>(db: E, filter: &#filter_name #ty_generics) -> #function_output
         where
             E: ::sqlx::#db_type_ident<'e>
         {
-            #delete_statement
+            let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+            qb.push(#table_name);
             #where_statement
-            qb.push(#pk_name);
-            qb.push(" = ");
-            qb.push_bind(&self.#pk_ident);
+            #conditions
+            #order_by_statement
 
             #query_builder_execution
         }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use syn::Ident;
-
-    pub fn clean_tokens(tokens: proc_macro2::TokenStream) -> String {
-        tokens.to_string().replace([' ', '\n'], "")
+/// The auto increment key of the last record inserted without `RETURNING`
+fn last_insert_id(attr: &Attr) -> proc_macro2::TokenStream {
+    match attr.db_type() {
+        DbType::MySQL => quote! { result.last_insert_id() },
+        _ => quote! { result.last_insert_rowid() },
     }
+}
 
-    #[cfg(feature = "mysql")]
-    fn db_ident() -> Ident {
-        format_ident!("MySqlExecutor")
+fn create_return_type(attr: &Attr) -> ReturnType {
+    if attr.parsed_struct.return_nothing {
+        return ReturnType::None;
     }
-    #[cfg(feature = "postgres")]
-    fn db_ident() -> Ident {
-        format_ident!("PgExecutor")
+    if attr.parsed_struct.returning_generated {
+        // The primary key comes first, then the columns `create` does not insert (eg: `created_at`)
+        let columns = attr
+            .primary_key
+            .iter()
+            .chain(
+                attr.columns
+                    .iter()
+                    .filter(|column| !attr.is_primary_key(column) && !column.is_inserted()),
+            )
+            .cloned()
+            .collect();
+        return ReturnType::GeneratedColumns(columns);
     }
-    #[cfg(feature = "sqlite")]
-    fn db_ident() -> Ident {
-        format_ident!("SqliteExecutor")
+    match attr.primary_key.clone() {
+        None => ReturnType::EntireRow(attr.parsed_struct.return_object.clone()),
+        Some(primary_key) => ReturnType::PrimaryKey(primary_key),
     }
+}
 
-    mod simple_attr {
-        use quote::format_ident;
-        use syn::parse_quote;
+/// Without `RETURNING`, the record created by a struct without primary key (eg: `NewTodo`) is read back
+/// with a second query on the same connection, by the `id` auto increment key of the last insert.
+fn reads_back_created_row(attr: &Attr, return_type: &ReturnType) -> bool {
+    !attr.supports_returning() && matches!(return_type, ReturnType::EntireRow(_))
+}
 
-        use crate::types::{Column, Operation, ParsedStruct};
+fn create_signature(attr: &Attr, return_type: &ReturnType) -> proc_macro2::TokenStream {
+    let function_output = return_type.clone().function_output();
+    let database_ident = attr.db_type().to_database_ident();
+    // `create_for()` takes the value of the injected column
+    let (fn_name, tenant_generic, tenant_arg, tenant_bound) = if attr
+        .columns
+        .iter()
+        .any(|column| column.injected)
+    {
+        (
+            format_ident!("create_for"),
+            quote! { , T },
+            quote! { tenant: &T, },
+            quote! {
+                ,
+                T: for<'q> ::sqlx::Encode<'q, ::sqlx::#database_ident> + ::sqlx::Type<::sqlx::#database_ident> + Sync
+            },
+        )
+    } else {
+        (format_ident!("create"), quote! {}, quote! {}, quote! {})
+    };
+    if reads_back_created_row(attr, return_type) {
+        quote! {
+            pub async fn #fn_name<'c, A #tenant_generic>(&self, #tenant_arg db: A) -> #function_output
+            where
+                A: ::sqlx::Acquire<'c, Database = ::sqlx::#database_ident>
+                #tenant_bound
+        }
+    } else {
+        let db_type_ident = attr.db_type().to_ident();
+        quote! {
+            pub async fn #fn_name<'e, E #tenant_generic>(&self, #tenant_arg db: E) -> #function_output
+            where
+                E: ::sqlx::#db_type_ident<'e>
+                #tenant_bound
+        }
+    }
+}
 
-        use super::*;
+/// The value of a column inserted by `create`, the `tenant` argument for the injected column of `create_for()`
+fn self_value(column: &Column) -> proc_macro2::TokenStream {
+    if column.injected {
+        return quote! { tenant };
+    }
+    let column_ident = &column.ident;
+    bind_value(column, quote! { &self.#column_ident })
+}
 
-        fn input(auto_increment: bool, soft_deletion: bool) -> Attr {
-            let mut primary_key = Column::new("id", parse_quote!(i64));
-            primary_key.set_primary_key();
-            if auto_increment {
-                primary_key.set_auto_increment();
-            };
-            let parsed_struct = ParsedStruct::new(
-                &format_ident!("Contact"),
-                Some("contact".to_string()),
-                Some(format_ident!("Self")),
+fn created_row_execution(attr: &Attr, query: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let select_str = match &attr.parsed_struct.returning {
+        Some(returning) => format!("SELECT {} FROM ", returning.sql(&attr.db_type())),
+        None => "SELECT * FROM ".to_string(),
+    };
+    let table_name = attr.parsed_struct.table_name.quoted(&attr.db_type());
+    let read_back_statement = match attr.db_type() {
+        // `last_insert_id()` is 0 when the insert has not generated an auto increment key
+        DbType::MySQL => {
+            let where_str = format!(
+                " WHERE {} = ",
+                quote_identifier(&attr.parsed_struct.created_key, &DbType::MySQL)
             );
-            Attr {
-                parsed_struct,
-                primary_key: Some(primary_key.clone()),
-                columns: vec![
-                    primary_key,
-                    Column::new("created_at", parse_quote!(DateTime<Utc>)),
-                    Column::new("updated_at", parse_quote!(DateTime<Utc>)),
-                    Column::new("last_name", parse_quote!(String)),
-                ],
-                operations: Operation::all(),
-                soft_deletion,
+            quote! {
+                let id = result.last_insert_id();
+                if id == 0 {
+                    return Err(::sqlx::Error::Protocol(
+                        "MySQL can only read back a record created with an auto increment key".to_string(),
+                    ));
+                }
+                qb.push(#where_str);
+                qb.push_bind(id);
             }
         }
+        // The `rowid` identifies the record whatever the primary key of the table
+        _ => quote! {
+            qb.push(" WHERE rowid = ");
+            qb.push_bind(result.last_insert_rowid());
+        },
+    };
+    quote! {
+        let mut conn = db.acquire().await?;
+        let result = #query
+        .execute(&mut *conn)
+        .await?;
+
+        let mut qb = ::sqlx::QueryBuilder::new(#select_str);
+        qb.push(#table_name);
+        #read_back_statement
+        qb.build_query_as()
+        .fetch_one(&mut *conn)
+        .await
+    }
+}
+
+fn update_return_type(attr: &Attr) -> ReturnType {
+    let self_ident = format_ident!("Self");
+    match (attr.supports_returning(), &attr.parsed_struct.return_object) {
+        (false, _) => ReturnType::None, // The entire row cannot be returned without `RETURNING`.
+        (_, ident) if ident == &self_ident => ReturnType::None,
+        (_, _) => ReturnType::EntireRow(attr.parsed_struct.return_object.clone()),
+    }
+}
+
+/// The value of a `json` column is wrapped in `sqlx::types::Json` and the one of a `transform` column is
+/// encoded by the `encode` function of the module, while a `None` is still bound as `NULL`
+fn bind_value(column: &Column, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let wrapper = match (&column.transform, column.json) {
+        (Some(transform), _) => quote! { #transform::encode },
+        (None, true) => quote! { ::sqlx::types::Json },
+        (None, false) => return value,
+    };
+    match column.is_nullable() {
+        true => quote! { #value.as_ref().map(#wrapper) },
+        false => quote! { #wrapper(#value) },
+    }
+}
+
+/// The primary key generated client side with `primary_key(uuid)`, when it is still nil
+fn new_uuid_primary_key(attr: &Attr) -> Option<proc_macro2::TokenStream> {
+    match attr.primary_key {
+        Some(ref primary_key) if primary_key.uuid => {
+            let pk_ident = &primary_key.ident;
+            Some(quote! {
+                let id = if self.#pk_ident.is_nil() {
+                    ::tiny_orm::uuid::Uuid::new_v4()
+                } else {
+                    self.#pk_ident
+                };
+            })
+        }
+        _ => None,
+    }
+}
+
+fn insert_statement(attr: &Attr, insert_into: &str) -> proc_macro2::TokenStream {
+    let table_name = attr.parsed_struct.table_name.quoted(&attr.db_type());
+    let new_uuid_primary_key = new_uuid_primary_key(attr);
+
+    let mut optional_values_quote = Vec::new();
+    let mut field_str_quote = Vec::new();
+    let mut field_values_quote = Vec::new();
+
+    for column in attr.columns.iter() {
+        if !column.is_inserted() {
+            continue;
+        }
+        if let Some(nextval) = column.nextval() {
+            let column_name = column.sql_name(&attr.db_type());
+            field_str_quote.push(quote! {
+                fields_str.push(#column_name);
+            });
+            field_values_quote.push(quote! {
+                separated.push(#nextval);
+            });
+            continue;
+        }
+        let column_ident = &column.ident;
+        let column_name = column.sql_name(&attr.db_type());
+        let column_type = &column._type;
+        // A column left out of the query is decided once, so that its name and its value cannot get out of sync
+        let optional_value = if column.use_set_options() {
+            Some(quote! { self.#column_ident.value_ref().ok() })
+        } else if column.is_optional_primary_key() {
+            // Left to the database when not set
+            Some(quote! { self.#column_ident.as_ref() })
+        } else if attr.parsed_struct.skip_defaults && !column.primary_key && !column.injected {
+            // Left to the database when equal to the default value
+            Some(quote! {
+                (self.#column_ident != <#column_type as ::core::default::Default>::default())
+                    .then_some(&self.#column_ident)
+            })
+        } else {
+            None
+        };
+
+        match optional_value {
+            Some(optional_value) => {
+                let value_ident = format_ident!("{}_value", column_ident);
+                let value = bind_value(column, quote! { v });
+                optional_values_quote.push(quote! {
+                    let #value_ident = #optional_value;
+                });
+                field_str_quote.push(quote! {
+                    if #value_ident.is_some() {
+                        fields_str.push(#column_name);
+                    }
+                });
+                field_values_quote.push(quote! {
+                    if let Some(v) = #value_ident {
+                        separated.push_bind(#value);
+                    }
+                });
+            }
+            None => {
+                let value = if column.uuid && attr.is_primary_key(column) {
+                    quote! { &id }
+                } else {
+                    self_value(column)
+                };
+                field_str_quote.push(quote! {
+                    fields_str.push(#column_name);
+                });
+                field_values_quote.push(quote! {
+                    separated.push_bind(#value);
+                });
+            }
+        }
+    }
+
+    quote! {
+        #new_uuid_primary_key
+        #(#optional_values_quote)*
+        let mut fields_str = Vec::new();
+
+        #(#field_str_quote)*
+
+        let mut qb = ::sqlx::QueryBuilder::new(#insert_into);
+        qb.push(#table_name);
+        qb.push(" (");
+        qb.push(fields_str.join(", "));
+        qb.push(") VALUES (");
+
+        let mut separated = qb.separated(", ");
+        #(#field_values_quote)*
+        separated.push_unseparated(")");
+    }
+}
+
+fn delete_return_type(attr: &Attr) -> ReturnType {
+    match (
+        attr.supports_returning(),
+        attr.parsed_struct.delete_returning,
+    ) {
+        (false, _) => ReturnType::None, // The deleted row cannot be returned without `RETURNING`.
+        (_, false) => ReturnType::None,
+        (_, true) => ReturnType::OptionalRow(attr.parsed_struct.return_object.clone()),
+    }
+}
+
+fn placeholder(db_type: &DbType, index: usize) -> String {
+    match db_type {
+        DbType::Postgres => format!("${index}"),
+        DbType::MySQL | DbType::Sqlite => "?".to_string(),
+    }
+}
+
+/// The columns of a create query are only known at compile time without `SetOption` nor `Option` primary key
+/// nor `skip_defaults`. A `uuid` primary key is generated before the query.
+fn has_static_create_query(attr: &Attr) -> bool {
+    !attr.parsed_struct.skip_defaults
+        && new_uuid_primary_key(attr).is_none()
+        && !attr
+            .columns
+            .iter()
+            .any(|column| column.use_set_options() || column.is_optional_primary_key())
+}
+
+fn static_create_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    let db_type = attr.db_type();
+
+    let return_type = create_return_type(attr);
+    let signature = create_signature(attr, &return_type);
+
+    let columns: Vec<&Column> = attr
+        .columns
+        .iter()
+        .filter(|column| column.is_inserted())
+        .collect();
+    let fields_str = columns
+        .iter()
+        .map(|column| column.sql_name(&db_type))
+        .collect::<Vec<String>>()
+        .join(", ");
+    // A key drawn from a sequence is not bound, so the placeholders only count the bound columns
+    let mut index = 0;
+    let values_str = columns
+        .iter()
+        .map(|column| {
+            column.nextval().unwrap_or_else(|| {
+                index += 1;
+                placeholder(&db_type, index)
+            })
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+    let sql = format!(
+        "INSERT INTO {} ({fields_str}) VALUES ({values_str}){}",
+        attr.parsed_struct.table_name.quoted(&db_type),
+        return_type.returning_sql(attr)
+    );
+
+    let binds = columns
+        .iter()
+        .filter(|column| column.nextval().is_none())
+        .map(|column| {
+            let value = self_value(column);
+            quote! { .bind(#value) }
+        });
+    let binds = quote! { #(#binds)* };
+    let query_execution = if reads_back_created_row(attr, &return_type) {
+        created_row_execution(attr, quote! { ::sqlx::query(SQL) #binds })
+    } else {
+        return_type.query_execution(
+            attr,
+            quote! { ::sqlx::query(SQL) #binds },
+            quote! { ::sqlx::query_as(SQL) #binds },
+        )
+    };
+
+    quote! {
+        #signature
+        {
+            // Same SQL for every call, so that the prepared statement is cached by sqlx
+            const SQL: &str = #sql;
+
+            #query_execution
+        }
+    }
+}
+
+pub fn create_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    if has_static_create_query(attr) {
+        return static_create_fn(attr);
+    }
+
+    let return_type = create_return_type(attr);
+    let signature = create_signature(attr, &return_type);
+    let insert_statement = insert_statement(attr, "INSERT INTO ");
+    // The key generated client side is already known, whatever the backend
+    let (returning_statement, query_builder_execution) = match new_uuid_primary_key(attr) {
+        Some(_)
+            if !attr.parsed_struct.return_nothing && !attr.parsed_struct.returning_generated =>
+        {
+            (
+                quote! {},
+                quote! {
+                    qb.build().execute(db).await.map(|_| id)
+                },
+            )
+        }
+        _ if reads_back_created_row(attr, &return_type) => (
+            quote! {},
+            created_row_execution(attr, quote! { qb.build() }),
+        ),
+        _ => (
+            return_type.clone().returning_statement(attr),
+            return_type.query_builder_execution(attr),
+        ),
+    };
+
+    quote! {
+        #signature
+        {
+            #insert_statement
+
+            #returning_statement
+
+            #query_builder_execution
+        }
+    }
+}
+
+/// Same as `create` with the injected column bound from the `tenant` argument, a field of the same name being ignored
+pub fn create_for_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    let Some(ref inject) = attr.parsed_struct.inject else {
+        return quote! {};
+    };
+    let mut columns: Vec<Column> = attr
+        .columns
+        .iter()
+        .filter(|column| column.name != inject.0)
+        .cloned()
+        .collect();
+    columns.push(inject.column());
+    create_fn(&Attr {
+        columns,
+        ..attr.clone()
+    })
+}
+
+/// `create` already returns the primary key when there is one, whatever the backend.
+/// This gives the same signature to generic code, even when the `create` of a struct changes.
+pub fn create_returning_id_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    let primary_key = match attr.primary_key {
+        Some(ref primary_key) if !attr.parsed_struct.return_nothing => primary_key,
+        _ => return quote! {},
+    };
+    let db_type_ident = attr.db_type().to_ident();
+    let function_output = ReturnType::PrimaryKey(primary_key.clone()).function_output();
+    let create_call = match attr.parsed_struct.returning_generated {
+        true => quote! { self.create(db).await.map(|generated| generated.0) },
+        false => quote! { self.create(db).await },
+    };
+
+    quote! {
+        pub async fn create_returning_id<'e, E>(&self, db: E) -> #function_output
+        where
+            E: ::sqlx::#db_type_ident<'e>
+        {
+            #create_call
+        }
+    }
+}
+
+pub fn create_if_not_exists_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    let db_type = attr.db_type();
+    let db_type_ident = db_type.to_ident();
+
+    let (insert_statement, on_conflict_statement) = match db_type {
+        DbType::MySQL => (insert_statement(attr, "INSERT IGNORE INTO "), quote! {}),
+        DbType::Postgres | DbType::Sqlite => (
+            insert_statement(attr, "INSERT INTO "),
+            quote! {
+                qb.push(" ON CONFLICT DO NOTHING");
+            },
+        ),
+    };
+
+    quote! {
+        pub async fn create_if_not_exists<'e, E>(&self, db: E) -> ::sqlx::Result<bool>
+        where
+            E: ::sqlx::#db_type_ident<'e>
+        {
+            #insert_statement
+
+            #on_conflict_statement
+
+            qb.build()
+            .execute(db)
+            .await
+            .map(|result| result.rows_affected() > 0)
+        }
+    }
+}
+
+/// The assignments of `ON CONFLICT DO UPDATE`, starting with a conflicting column set to itself
+/// which guarantees the record is always updated, even when no other column is
+fn upsert_set_quotes(
+    attr: &Attr,
+    conflict_columns: &[String],
+    inserted_value: impl Fn(&str) -> String,
+) -> Vec<proc_macro2::TokenStream> {
+    let conflict_column = quote_identifier(&conflict_columns[0], &attr.db_type());
+    let conflict_set_str = format!("{conflict_column} = {}", inserted_value(&conflict_column));
+    std::iter::once(quote! {
+        separated.push(#conflict_set_str);
+    })
+    .chain(
+        attr.columns
+            .iter()
+            .filter(|column| {
+                !attr.is_primary_key(column)
+                    && !column.db_default
+                    && !column.readonly
+                    && !conflict_columns.contains(&column.name)
+            })
+            .map(|column| {
+                let column_name = column.sql_name(&attr.db_type());
+                let set_str = format!("{column_name} = {}", inserted_value(&column_name));
+                if column.use_set_options() {
+                    let column_ident = &column.ident;
+                    quote! {
+                        if self.#column_ident.is_set() {
+                            separated.push(#set_str);
+                        }
+                    }
+                } else {
+                    quote! {
+                        separated.push(#set_str);
+                    }
+                }
+            }),
+    )
+    .collect()
+}
+
+pub fn upsert_with_outcome_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    let conflict_target = &attr.parsed_struct.conflict_target;
+    let primary_key = match attr.primary_key {
+        // An auto increment primary key is never part of the insert so it cannot conflict
+        Some(ref primary_key) if !primary_key.auto_increment || conflict_target.is_some() => {
+            primary_key
+        }
+        _ => return quote! {},
+    };
+    // Only Postgres can tell within the same query whether the row has been inserted
+    if attr.db_type() != DbType::Postgres {
+        return upsert_with_outcome_lookup_fn(attr, primary_key);
+    }
+    let db_type_ident = attr.db_type().to_ident();
+    let return_object = &attr.parsed_struct.return_object;
+    let insert_statement = insert_statement(attr, "INSERT INTO ");
+    let (on_conflict_str, conflict_columns) = match conflict_target {
+        Some(conflict_target) => (
+            format!(
+                " ON CONFLICT {} DO UPDATE SET ",
+                conflict_target.sql(&attr.db_type())
+            ),
+            conflict_target.columns.clone(),
+        ),
+        None => (
+            format!(
+                " ON CONFLICT ({}) DO UPDATE SET ",
+                primary_key.sql_name(&attr.db_type())
+            ),
+            vec![primary_key.name.clone()],
+        ),
+    };
+    // Setting a conflicting column to itself guarantees the record is always returned
+    let set_quotes = upsert_set_quotes(attr, &conflict_columns, |column| {
+        format!("EXCLUDED.{column}")
+    });
+    let returning_str = format!(
+        "{}, (xmax = 0) AS inserted",
+        ReturnType::EntireRow(return_object.clone()).returning_sql(attr)
+    );
+
+    quote! {
+        pub async fn upsert_with_outcome<'e, E>(&self, db: E) -> ::sqlx::Result<::tiny_orm::UpsertOutcome<#return_object>>
+        where
+            E: ::sqlx::#db_type_ident<'e>
+        {
+            #insert_statement
+
+            qb.push(#on_conflict_str);
+            let mut separated = qb.separated(", ");
+            #(#set_quotes)*
+            qb.push(#returning_str);
+
+            let row = qb.build().fetch_one(db).await?;
+            // `xmax` is only set when the row already existed before the query
+            let inserted: bool = ::sqlx::Row::try_get(&row, "inserted")?;
+            let record = <#return_object as ::sqlx::FromRow<_>>::from_row(&row)?;
+            if inserted {
+                Ok(::tiny_orm::UpsertOutcome::Inserted(record))
+            } else {
+                Ok(::tiny_orm::UpsertOutcome::Updated(record))
+            }
+        }
+    }
+}
+
+/// SQLite and MySQL cannot tell from the upsert itself whether the row has been inserted:
+/// the `rows_affected` of MySQL is also 1 for an existing row left unchanged, since sqlx sets `CLIENT_FOUND_ROWS`.
+/// The conflicting record is looked up first, within the same transaction than the upsert
+/// (with `FOR UPDATE` on MySQL so that a concurrent insert waits for it).
+fn upsert_with_outcome_lookup_fn(
+    attr: &Attr,
+    primary_key: &PrimaryKey,
+) -> proc_macro2::TokenStream {
+    let db_type = attr.db_type();
+    let database_ident = db_type.to_database_ident();
+    let return_object = &attr.parsed_struct.return_object;
+    let table_name = attr.parsed_struct.table_name.quoted(&attr.db_type());
+    let insert_statement = insert_statement(attr, "INSERT INTO ");
+
+    let conflict_target = &attr.parsed_struct.conflict_target;
+    let conflict_columns = match conflict_target {
+        Some(conflict_target) => conflict_target.columns.clone(),
+        None => vec![primary_key.name.clone()],
+    };
+    // The conflicting record is looked up by the values of the struct
+    let lookup_columns: Vec<&Column> = conflict_columns
+        .iter()
+        .filter_map(|name| attr.columns.iter().find(|column| &column.name == name))
+        .collect();
+    if lookup_columns.len() != conflict_columns.len() {
+        return quote! {};
+    }
+    let lookup_binds = lookup_columns.iter().enumerate().map(|(index, column)| {
+        let condition_str = match index {
+            0 => format!(" WHERE {} = ", column.sql_name(&db_type)),
+            _ => format!(" AND {} = ", column.sql_name(&db_type)),
+        };
+        let value = self_value(column);
+        quote! {
+            qb.push(#condition_str);
+            qb.push_bind(#value);
+        }
+    });
+    let lookup_binds = quote! { #(#lookup_binds)* };
+    let predicate_statement = match conflict_target
+        .as_ref()
+        .and_then(|target| target.predicate.as_ref())
+    {
+        Some(predicate) => {
+            let predicate_str = format!(" AND ({predicate})");
+            quote! { qb.push(#predicate_str); }
+        }
+        None => quote! {},
+    };
+    let lock_statement = match db_type {
+        DbType::MySQL => quote! { qb.push(" FOR UPDATE"); },
+        _ => quote! {},
+    };
+
+    let (on_conflict_str, set_quotes) = match db_type {
+        // MySQL updates the record on the conflict of any unique index
+        DbType::MySQL => (
+            " ON DUPLICATE KEY UPDATE ".to_string(),
+            upsert_set_quotes(attr, &conflict_columns, |column| {
+                format!("VALUES({column})")
+            }),
+        ),
+        _ => (
+            format!(
+                " ON CONFLICT {} DO UPDATE SET ",
+                match conflict_target {
+                    Some(conflict_target) => conflict_target.sql(&db_type),
+                    None => format!("({})", primary_key.sql_name(&db_type)),
+                }
+            ),
+            upsert_set_quotes(attr, &conflict_columns, |column| {
+                format!("EXCLUDED.{column}")
+            }),
+        ),
+    };
+    let record_query = if attr.supports_returning() {
+        let returning_str = ReturnType::EntireRow(return_object.clone()).returning_sql(attr);
+        quote! {
+            qb.push(#returning_str);
+            let record: #return_object = qb.build_query_as().fetch_one(&mut *tx).await?;
+        }
+    } else {
+        let select_str = match &attr.parsed_struct.returning {
+            Some(returning) => format!("SELECT {} FROM ", returning.sql(&attr.db_type())),
+            None => "SELECT * FROM ".to_string(),
+        };
+        quote! {
+            qb.build().execute(&mut *tx).await?;
+
+            let mut qb = ::sqlx::QueryBuilder::new(#select_str);
+            qb.push(#table_name);
+            #lookup_binds
+            let record: #return_object = qb.build_query_as().fetch_one(&mut *tx).await?;
+        }
+    };
+
+    quote! {
+        pub async fn upsert_with_outcome<'c, A>(&self, db: A) -> ::sqlx::Result<::tiny_orm::UpsertOutcome<#return_object>>
+        where
+            A: ::sqlx::Acquire<'c, Database = ::sqlx::#database_ident>
+        {
+            let mut tx = db.begin().await?;
+
+            let mut qb = ::sqlx::QueryBuilder::new("SELECT 1 FROM ");
+            qb.push(#table_name);
+            #lookup_binds
+            #predicate_statement
+            #lock_statement
+            let existed = qb.build().fetch_optional(&mut *tx).await?.is_some();
+
+            #insert_statement
+
+            qb.push(#on_conflict_str);
+            let mut separated = qb.separated(", ");
+            #(#set_quotes)*
+            #record_query
+            tx.commit().await?;
+
+            if existed {
+                Ok(::tiny_orm::UpsertOutcome::Updated(record))
+            } else {
+                Ok(::tiny_orm::UpsertOutcome::Inserted(record))
+            }
+        }
+    }
+}
+
+/// The maximum number of bind parameters of a single query
+fn max_bind_parameters(attr: &Attr) -> usize {
+    match attr.db_type() {
+        DbType::Postgres | DbType::MySQL => 65535,
+        // SQLite before 3.32 only accepts 999 parameters
+        DbType::Sqlite if attr.parsed_struct.sqlite_no_returning => 999,
+        DbType::Sqlite => 32766,
+    }
+}
+
+pub fn create_many_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    // Every record must bind the same columns, and the keys generated by the database are returned
+    let primary_key = match attr.primary_key {
+        Some(ref primary_key)
+            if has_static_create_query(attr)
+                && !attr.parsed_struct.return_nothing
+                && primary_key.auto_increment
+                && (attr.supports_returning() || primary_key.is_integer()) =>
+        {
+            primary_key
+        }
+        _ => return quote! {},
+    };
+    let columns: Vec<&Column> = attr
+        .columns
+        .iter()
+        .filter(|column| column.is_inserted())
+        .collect();
+    let bound_columns = columns
+        .iter()
+        .filter(|column| column.nextval().is_none())
+        .count();
+    if bound_columns == 0 {
+        return quote! {};
+    }
+    let database_ident = attr.db_type().to_database_ident();
+    let table_name = attr.parsed_struct.table_name.quoted(&attr.db_type());
+    let pk_type = primary_key.unwrapped_type();
+
+    let fields_str = format!(
+        " ({}) ",
+        columns
+            .iter()
+            .map(|column| column.sql_name(&attr.db_type()))
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+    let chunk_size = max_bind_parameters(attr) / bound_columns;
+    let binds = columns.iter().map(|column| match column.nextval() {
+        Some(nextval) => quote! { separated.push(#nextval); },
+        None => {
+            let column_ident = &column.ident;
+            let value = bind_value(column, quote! { &item.#column_ident });
+            quote! { separated.push_bind(#value); }
+        }
+    });
+
+    let (increment_query, ids_query) = match (attr.supports_returning(), attr.db_type()) {
+        (true, _) => {
+            let returning_str = format!(" RETURNING {}", primary_key.sql_name(&attr.db_type()));
+            (
+                quote! {},
+                quote! {
+                    qb.push(#returning_str);
+                    ids.extend(qb.build_query_scalar::<#pk_type>().fetch_all(&mut *tx).await?);
+                },
+            )
+        }
+        // MySQL gives the key of the first record inserted, the next ones being `auto_increment_increment` apart
+        (false, DbType::MySQL) => (
+            quote! {
+                let increment: u64 = ::sqlx::query_scalar("SELECT @@auto_increment_increment")
+                    .fetch_one(&mut *tx)
+                    .await?;
+            },
+            quote! {
+                let result = qb.build().execute(&mut *tx).await?;
+                let first_id = result.last_insert_id();
+                ids.extend((0..result.rows_affected()).map(|index| (first_id + index * increment) as #pk_type));
+            },
+        ),
+        // SQLite gives the key of the last record inserted
+        (false, _) => (
+            quote! {},
+            quote! {
+                let result = qb.build().execute(&mut *tx).await?;
+                let last_id = result.last_insert_rowid();
+                let first_id = last_id - result.rows_affected() as i64 + 1;
+                ids.extend((first_id..=last_id).map(|id| id as #pk_type));
+            },
+        ),
+    };
+
+    quote! {
+        pub async fn create_many<'c, A>(db: A, items: &[Self]) -> ::sqlx::Result<Vec<#pk_type>>
+        where
+            A: ::sqlx::Acquire<'c, Database = ::sqlx::#database_ident>
+        {
+            // Each query stays under the bind parameter limit of the database
+            const CHUNK_SIZE: usize = #chunk_size;
+            if items.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            let mut tx = db.begin().await?;
+            #increment_query
+            let mut ids: Vec<#pk_type> = Vec::with_capacity(items.len());
+            for chunk in items.chunks(CHUNK_SIZE) {
+                let mut qb = ::sqlx::QueryBuilder::new("INSERT INTO ");
+                qb.push(#table_name);
+                qb.push(#fields_str);
+                qb.push_values(chunk, |mut separated, item| {
+                    #(#binds)*
+                });
+                #ids_query
+            }
+            tx.commit().await?;
+
+            Ok(ids)
+        }
+    }
+}
+
+pub fn upsert_many_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    // Every record must bind the same columns, and the conflict needs the primary key or a `conflict_target`
+    let conflict_target = &attr.parsed_struct.conflict_target;
+    let primary_key = match attr.primary_key {
+        Some(ref primary_key)
+            if has_static_create_query(attr)
+                && (!primary_key.auto_increment || conflict_target.is_some()) =>
+        {
+            primary_key
+        }
+        _ => return quote! {},
+    };
+    let db_type = attr.db_type();
+    let database_ident = db_type.to_database_ident();
+    let table_name = attr.parsed_struct.table_name.quoted(&attr.db_type());
+
+    let columns: Vec<&Column> = attr
+        .columns
+        .iter()
+        .filter(|column| !column.auto_increment && !column.db_default && !column.readonly)
+        .collect();
+    let fields_str = format!(
+        " ({}) ",
+        columns
+            .iter()
+            .map(|column| column.sql_name(&attr.db_type()))
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+    let chunk_size = max_bind_parameters(attr) / columns.len();
+    let binds = columns.iter().map(|column| {
+        let column_ident = &column.ident;
+        let value = bind_value(column, quote! { &item.#column_ident });
+        quote! { separated.push_bind(#value); }
+    });
+
+    let conflict_columns = match conflict_target {
+        Some(conflict_target) => conflict_target.columns.clone(),
+        None => vec![primary_key.name.clone()],
+    };
+    let set_columns: Vec<String> = columns
+        .iter()
+        .filter(|column| !attr.is_primary_key(column) && !conflict_columns.contains(&column.name))
+        .map(|column| column.sql_name(&db_type))
+        .collect();
+    let pk_name = primary_key.sql_name(&db_type);
+    let on_conflict_str = match (&db_type, conflict_target) {
+        // MySQL updates the record on the conflict of any unique index
+        (DbType::MySQL, _) if set_columns.is_empty() => {
+            format!(" ON DUPLICATE KEY UPDATE {pk_name} = {pk_name}")
+        }
+        (DbType::MySQL, _) => format!(
+            " ON DUPLICATE KEY UPDATE {}",
+            set_columns
+                .iter()
+                .map(|column| format!("{column} = VALUES({column})"))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+        (_, Some(conflict_target)) if set_columns.is_empty() => {
+            format!(" ON CONFLICT {} DO NOTHING", conflict_target.sql(&db_type))
+        }
+        (_, None) if set_columns.is_empty() => {
+            format!(" ON CONFLICT ({pk_name}) DO NOTHING")
+        }
+        (_, conflict_target) => format!(
+            " ON CONFLICT {} DO UPDATE SET {}",
+            match conflict_target {
+                Some(conflict_target) => conflict_target.sql(&db_type),
+                None => format!("({pk_name})"),
+            },
+            set_columns
+                .iter()
+                .map(|column| format!("{column} = EXCLUDED.{column}"))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+    };
+
+    quote! {
+        pub async fn upsert_many<'c, A>(db: A, items: &[Self]) -> ::sqlx::Result<u64>
+        where
+            A: ::sqlx::Acquire<'c, Database = ::sqlx::#database_ident>
+        {
+            // Each query stays under the bind parameter limit of the database
+            const CHUNK_SIZE: usize = #chunk_size;
+            if items.is_empty() {
+                return Ok(0);
+            }
+
+            let mut tx = db.begin().await?;
+            let mut rows_affected = 0;
+            for chunk in items.chunks(CHUNK_SIZE) {
+                let mut qb = ::sqlx::QueryBuilder::new("INSERT INTO ");
+                qb.push(#table_name);
+                qb.push(#fields_str);
+                qb.push_values(chunk, |mut separated, item| {
+                    #(#binds)*
+                });
+                qb.push(#on_conflict_str);
+                rows_affected += qb.build().execute(&mut *tx).await?.rows_affected();
+            }
+            tx.commit().await?;
+
+            Ok(rows_affected)
+        }
+    }
+}
+
+pub fn save_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    // Only an auto increment or an `Option` primary key tells whether the record already exists
+    let primary_key = match attr.primary_key {
+        Some(ref primary_key)
+            if (primary_key.auto_increment || primary_key.is_optional_primary_key())
+                && !attr.parsed_struct.return_nothing =>
+        {
+            primary_key
+        }
+        _ => return quote! {},
+    };
+    let db_type_ident = attr.db_type().to_ident();
+    let pk_ident = &primary_key.ident;
+    let (is_new, created_pk) = if primary_key.is_optional_primary_key() {
+        (quote! { self.#pk_ident.is_none() }, quote! { Some(id) })
+    } else {
+        let pk_type = &primary_key._type;
+        (
+            quote! { self.#pk_ident == <#pk_type as ::core::default::Default>::default() },
+            quote! { id },
+        )
+    };
+    // With `returning_generated`, the primary key is the first of the generated columns
+    let created_id = match attr.parsed_struct.returning_generated {
+        true => quote! { self.create(db).await?.0 },
+        false => quote! { self.create(db).await? },
+    };
+
+    quote! {
+        pub async fn save<'e, E>(&mut self, db: E) -> ::sqlx::Result<bool>
+        where
+            E: ::sqlx::#db_type_ident<'e>
+        {
+            if #is_new {
+                let id = #created_id;
+                self.#pk_ident = #created_pk;
+                Ok(true)
+            } else {
+                self.update(db).await?;
+                Ok(false)
+            }
+        }
+    }
+}
+
+fn self_primary_key_bind(primary_key: &PrimaryKey) -> proc_macro2::TokenStream {
+    let pk_ident = &primary_key.ident;
+    if primary_key.is_optional_primary_key() {
+        // A record without a primary key cannot exist in the database
+        quote! {
+            match &self.#pk_ident {
+                Some(id) => qb.push_bind(id),
+                None => return Err(::sqlx::Error::RowNotFound),
+            };
+        }
+    } else {
+        quote! {
+            qb.push_bind(&self.#pk_ident);
+        }
+    }
+}
+
+pub fn update_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    let db_type_ident = attr.db_type().to_ident();
+
+    let return_type = update_return_type(attr);
+
+    let function_output = return_type.clone().function_output();
+    let query_builder_execution = return_type.clone().query_builder_execution(attr);
+    let returning_statement = return_type.clone().returning_statement(attr);
+
+    let table_name = attr.parsed_struct.table_name.quoted(&attr.db_type());
+    let (pk_name, pk_bind) = match attr.primary_key {
+        Some(ref pk) => (pk.sql_name(&attr.db_type()), self_primary_key_bind(pk)),
+        None => panic!("No primary key field found"),
+    };
+    let where_statement = where_statement(attr);
+    let mut fields_quote = Vec::new();
+
+    let updatable_columns: Vec<&Column> = attr
+        .columns
+        .iter()
+        .filter(|column| !column.auto_increment && !attr.is_primary_key(column) && !column.readonly)
+        .collect();
+
+    // Nothing to update when every `SetOption` is `NotSet`, so the `SET` clause would be empty
+    let empty_update_quote = if !updatable_columns.is_empty()
+        && updatable_columns
+            .iter()
+            .all(|column| column.use_set_options())
+    {
+        let not_set_quote = updatable_columns.iter().map(|column| {
+            let column_ident = &column.ident;
+            quote! { !self.#column_ident.is_set() }
+        });
+        let early_return = match return_type {
+            ReturnType::EntireRow(_) => {
+                let select_str = match &attr.parsed_struct.returning {
+                    Some(returning) => {
+                        format!("SELECT {} FROM ", returning.sql(&attr.db_type()))
+                    }
+                    None => "SELECT * FROM ".to_string(),
+                };
+                quote! {
+                    let mut qb = ::sqlx::QueryBuilder::new(#select_str);
+                    qb.push(#table_name);
+                    #where_statement
+                    qb.push(#pk_name);
+                    qb.push(" = ");
+                    #pk_bind
+
+                    return #query_builder_execution;
+                }
+            }
+            _ => quote! {
+                return Ok(());
+            },
+        };
+        quote! {
+            if #(#not_set_quote)&&* {
+                #early_return
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    for column in updatable_columns {
+        let column_ident = &column.ident;
+        let column_name = column.sql_name(&attr.db_type());
+
+        let value = bind_value(column, quote! { &self.#column_ident });
+        let quote = quote! {
+            separated.push(#column_name);
+            separated.push_unseparated(" = ");
+            separated.push_bind_unseparated(#value);
+        };
+
+        let str_quote = if column.use_set_options() && column.wraps_bind() {
+            let value = bind_value(column, quote! { v });
+            quote! {
+                if let SetOption::Set(v) = &self.#column_ident {
+                    separated.push(#column_name);
+                    separated.push_unseparated(" = ");
+                    separated.push_bind_unseparated(#value);
+                }
+            }
+        } else if column.use_set_options() {
+            quote! {
+                if self.#column_ident.is_set() {
+                    #quote
+                }
+            }
+        } else {
+            quote
+        };
+        fields_quote.push(str_quote);
+    }
+
+    quote! {
+        pub async fn update<'e, E>(&self, db: E) -> #function_output
+        where
+            E: ::sqlx::#db_type_ident<'e>
+        {
+            #empty_update_quote
+
+            let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
+            qb.push(#table_name);
+            qb.push(" SET ");
+
+            let mut separated = qb.separated(", ");
+            #(#fields_quote)*
+
+            #where_statement
+            qb.push(#pk_name);
+            qb.push(" = ");
+            #pk_bind
+
+            #returning_statement
+
+            #query_builder_execution
+        }
+    }
+}
+
+/// The current timestamp of the backend
+fn current_timestamp(db_type: DbType) -> &'static str {
+    match db_type {
+        DbType::Postgres => "NOW()",
+        DbType::MySQL => "CURRENT_TIMESTAMP",
+        DbType::Sqlite => "DATETIME('now')",
+    }
+}
+
+fn delete_statement(attr: &Attr) -> proc_macro2::TokenStream {
+    let table_name = attr.parsed_struct.table_name.quoted(&attr.db_type());
+    match attr.soft_deletion {
+        Some(ref soft_deletion) => {
+            let now = match soft_deletion.now {
+                Some(ref now) => now.as_str(),
+                None => current_timestamp(attr.db_type()),
+            };
+            let deleted = match soft_deletion.boolean {
+                true => "true",
+                false => now,
+            };
+            let set_str = match attr
+                .columns
+                .iter()
+                .find(|column| column.ident == "updated_at" && column.is_timestamp())
+            {
+                Some(updated_at) => format!(
+                    " SET {} = {deleted}, {} = {now} ",
+                    soft_deletion.column,
+                    updated_at.sql_name(&attr.db_type())
+                ),
+                None => format!(" SET {} = {deleted} ", soft_deletion.column),
+            };
+            quote! {
+                let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
+                qb.push(#table_name);
+                qb.push(#set_str);
+            }
+        }
+        None => quote! {
+            let mut qb = ::sqlx::QueryBuilder::new("DELETE FROM ");
+            qb.push(#table_name);
+        },
+    }
+}
+
+pub fn touch_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    let (primary_key, updated_at) = match (
+        &attr.primary_key,
+        attr.columns
+            .iter()
+            .find(|column| column.ident == "updated_at" && column.is_timestamp()),
+    ) {
+        (Some(primary_key), Some(updated_at)) => (primary_key, updated_at),
+        _ => return quote! {},
+    };
+    let db_type_ident = attr.db_type().to_ident();
+    let table_name = attr.parsed_struct.table_name.quoted(&attr.db_type());
+    let set_str = format!(
+        " SET {} = {} ",
+        updated_at.sql_name(&attr.db_type()),
+        current_timestamp(attr.db_type())
+    );
+    let pk_name = primary_key.sql_name(&attr.db_type());
+    let pk_bind = self_primary_key_bind(primary_key);
+    let where_statement = where_statement(attr);
+    quote! {
+        pub async fn touch<'e, E>(&self, db: E) -> ::sqlx::Result<()>
+        where
+            E: ::sqlx::#db_type_ident<'e>
+        {
+            let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
+            qb.push(#table_name);
+            qb.push(#set_str);
+            #where_statement
+            qb.push(#pk_name);
+            qb.push(" = ");
+            #pk_bind
+
+            qb.build()
+            .execute(db)
+            .await
+            .map(|_| ())
+        }
+    }
+}
+
+pub fn delete_all_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    let db_type_ident = attr.db_type().to_ident();
+    let delete_statement = delete_statement(attr);
+    let where_statement = list_where_statement(attr);
+    quote! {
+        pub async fn delete_all<'e, E>(db: E) -> ::sqlx::Result<u64>
+        where
+            E: ::sqlx::#db_type_ident<'e>
+        {
+            #delete_statement
+            #where_statement
+
+            qb.build()
+            .execute(db)
+            .await
+            .map(|result| result.rows_affected())
+        }
+    }
+}
+
+pub fn delete_by_fns(attr: &Attr) -> proc_macro2::TokenStream {
+    let db_type_ident = attr.db_type().to_ident();
+    let delete_statement = delete_statement(attr);
+    let where_statement = where_statement(attr);
+
+    let delete_by_fns = attr
+        .columns
+        .iter()
+        .filter(|column| column.filterable)
+        .map(|column| {
+            let fn_name = format_ident!("delete_by_{}", column.ident);
+            let column_name = column.sql_name(&attr.db_type());
+            let column_type = &column._type;
+            quote! {
+                pub async fn #fn_name<'e, E>(db: E, value: &#column_type) -> ::sqlx::Result<u64>
+                where
+                    E: ::sqlx::#db_type_ident<'e>
+                {
+                    #delete_statement
+                    #where_statement
+                    qb.push(#column_name);
+                    qb.push(" = ");
+                    qb.push_bind(value);
+
+                    qb.build()
+                    .execute(db)
+                    .await
+                    .map(|result| result.rows_affected())
+                }
+            }
+        });
+
+    quote! {
+        #(#delete_by_fns)*
+    }
+}
+
+pub fn bulk_update_fns(attr: &Attr) -> proc_macro2::TokenStream {
+    let db_type_ident = attr.db_type().to_ident();
+    let table_name = attr.parsed_struct.table_name.quoted(&attr.db_type());
+    let where_statement = where_statement(attr);
+
+    let bulk_update_fns = attr
+        .columns
+        .iter()
+        .filter(|column| column.bulk_updatable)
+        .map(|column| {
+            let (pk_name, pk_type) = match attr.primary_key {
+                Some(ref pk) => (pk.sql_name(&attr.db_type()), pk.unwrapped_type()),
+                None => panic!("No primary key field found which is mandatory for the 'bulk_updatable' columns"),
+            };
+            let fn_name = format_ident!("update_{}_by_ids", column.ident);
+            let column_name = column.sql_name(&attr.db_type());
+            let column_type = &column._type;
+            // A `SetOption` is bound as is
+            let value = match column.use_set_options() {
+                true => quote! { value },
+                false => bind_value(column, quote! { value }),
+            };
+            quote! {
+                pub async fn #fn_name<'e, E>(db: E, value: &#column_type, ids: &[#pk_type]) -> ::sqlx::Result<u64>
+                where
+                    E: ::sqlx::#db_type_ident<'e>
+                {
+                    if ids.is_empty() {
+                        return Ok(0);
+                    }
+                    let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
+                    qb.push(#table_name);
+                    qb.push(" SET ");
+                    qb.push(#column_name);
+                    qb.push(" = ");
+                    qb.push_bind(#value);
+
+                    #where_statement
+                    qb.push(#pk_name);
+                    qb.push(" IN (");
+                    let mut separated = qb.separated(", ");
+                    for id in ids {
+                        separated.push_bind(id);
+                    }
+                    separated.push_unseparated(")");
+
+                    qb.build()
+                    .execute(db)
+                    .await
+                    .map(|result| result.rows_affected())
+                }
+            }
+        });
+
+    quote! {
+        #(#bulk_update_fns)*
+    }
+}
+
+pub fn find_one_by_fns(attr: &Attr) -> proc_macro2::TokenStream {
+    let db_type_ident = attr.db_type().to_ident();
+    let table_name = attr.parsed_struct.table_name.quoted(&attr.db_type());
+    let where_statement = select_where_statement(attr);
+    let return_type = ReturnType::OptionalRow(attr.parsed_struct.return_object.clone());
+    let function_output = return_type.clone().function_output();
+    let query_builder_execution = return_type.query_builder_execution(attr);
+
+    let find_one_by_fns = attr
+        .columns
+        .iter()
+        .filter(|column| column.unique)
+        .map(|column| {
+            let fn_name = format_ident!("find_one_by_{}", column.ident);
+            let column_name = column.sql_name(&attr.db_type());
+            let column_type = &column._type;
+            quote! {
+                pub async fn #fn_name<'e, E>(db: E, value: &#column_type) -> #function_output
+                where
+                    E: ::sqlx::#db_type_ident<'e>
+                {
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push(#table_name);
+                    #where_statement
+                    qb.push(#column_name);
+                    qb.push(" = ");
+                    qb.push_bind(value);
+                    qb.push(" LIMIT 1");
+
+                    #query_builder_execution
+                }
+            }
+        });
+
+    quote! {
+        #(#find_one_by_fns)*
+    }
+}
+
+pub fn belongs_to_fns(attr: &Attr) -> proc_macro2::TokenStream {
+    let db_type_ident = attr.db_type().to_ident();
+
+    let belongs_to_fns = attr.parsed_struct.belongs_to.iter().map(|belongs_to| {
+        let fn_name = belongs_to.fn_name();
+        let target = &belongs_to.target;
+        let foreign_key = &belongs_to.foreign_key;
+        let is_optional = attr
+            .columns
+            .iter()
+            .find(|column| &column.ident == foreign_key)
+            .is_some_and(|column| column.option_inner_type().is_some());
+        // The target may not share the custom error of the struct
+        let map_err = attr
+            .parsed_struct
+            .error
+            .as_ref()
+            .map(|_| quote! { .map_err(::core::convert::Into::into) });
+        let get_by_id = if is_optional {
+            // No record is referenced without a foreign key
+            quote! {
+                match &self.#foreign_key {
+                    Some(id) => #target::get_by_id(db, id).await #map_err,
+                    None => Ok(None),
+                }
+            }
+        } else {
+            quote! {
+                #target::get_by_id(db, &self.#foreign_key).await #map_err
+            }
+        };
+        quote! {
+            pub async fn #fn_name<'e, E>(&self, db: E) -> ::sqlx::Result<Option<#target>>
+            where
+                E: ::sqlx::#db_type_ident<'e>
+            {
+                #get_by_id
+            }
+        }
+    });
+
+    quote! {
+        #(#belongs_to_fns)*
+    }
+}
+
+pub fn get_by_id_with_fns(attr: &Attr) -> proc_macro2::TokenStream {
+    // The accessor is only available on the struct itself
+    let return_object = &attr.parsed_struct.return_object;
+    if return_object != "Self" && return_object != &attr.parsed_struct.name {
+        return quote! {};
+    }
+
+    let database_ident = attr.db_type().to_database_ident();
+    let pk_type = match attr.lookup_column() {
+        Some(lookup_column) => lookup_column.unwrapped_type(),
+        None => return quote! {},
+    };
+
+    let get_by_id_with_fns = attr.parsed_struct.belongs_to.iter().map(|belongs_to| {
+        let accessor_name = belongs_to.fn_name();
+        let fn_name = format_ident!("get_by_id_with_{}", accessor_name);
+        let target = &belongs_to.target;
+        quote! {
+            pub async fn #fn_name<'c, A>(db: A, id: impl ::std::borrow::Borrow<#pk_type>) -> ::sqlx::Result<Option<(#return_object, Option<#target>)>>
+            where
+                A: ::sqlx::Acquire<'c, Database = ::sqlx::#database_ident>
+            {
+                let mut conn = db.acquire().await?;
+
+                let record = match Self::get_by_id(&mut *conn, id).await? {
+                    Some(record) => record,
+                    None => return Ok(None),
+                };
+                let related = record.#accessor_name(&mut *conn).await?;
+
+                Ok(Some((record, related)))
+            }
+        }
+    });
+
+    quote! {
+        #(#get_by_id_with_fns)*
+    }
+}
+
+pub fn has_many_fns(attr: &Attr) -> proc_macro2::TokenStream {
+    let db_type_ident = attr.db_type().to_ident();
+
+    let has_many_fns = attr.parsed_struct.has_many.iter().map(|has_many| {
+        let primary_key = match attr.primary_key {
+            Some(ref primary_key) => primary_key,
+            None => panic!(
+                "No primary key field found which is mandatory for the 'has_many' associations"
+            ),
+        };
+        let fn_name = has_many.fn_name();
+        let target = &has_many.target;
+        let table_name = has_many.table_name.quoted(&attr.db_type());
+        let where_str = match has_many.soft_deletion {
+            Some(ref soft_deletion) => format!(" WHERE {} AND ", soft_deletion.condition()),
+            None => " WHERE ".to_string(),
+        };
+        let foreign_key = &has_many.foreign_key;
+        let pk_ident = &primary_key.ident;
+        let pk_bind = if primary_key.is_optional_primary_key() {
+            // A record without a primary key cannot be referenced
+            quote! {
+                match &self.#pk_ident {
+                    Some(id) => qb.push_bind(id),
+                    None => return Ok(Vec::new()),
+                };
+            }
+        } else {
+            quote! {
+                qb.push_bind(&self.#pk_ident);
+            }
+        };
+        quote! {
+            pub async fn #fn_name<'e, E>(&self, db: E) -> ::sqlx::Result<Vec<#target>>
+            where
+                E: ::sqlx::#db_type_ident<'e>
+            {
+                let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                qb.push(#table_name);
+                qb.push(#where_str);
+                qb.push(#foreign_key);
+                qb.push(" = ");
+                #pk_bind
+
+                qb.build_query_as()
+                .fetch_all(db)
+                .await
+            }
+        }
+    });
+
+    quote! {
+        #(#has_many_fns)*
+    }
+}
+
+/// The `has_many` records of the struct with `cascade_delete`, soft deleted with `child_soft_deletion`
+fn cascade_delete_statements(attr: &Attr) -> Vec<proc_macro2::TokenStream> {
+    let Some(ref primary_key) = attr.primary_key else {
+        return Vec::new();
+    };
+    let pk_bind = self_primary_key_bind(primary_key);
+    attr.parsed_struct
+        .has_many
+        .iter()
+        .filter(|has_many| has_many.cascade_delete)
+        .map(|has_many| {
+            let table_name = has_many.table_name.quoted(&attr.db_type());
+            let (delete_str, where_str) = match has_many.soft_deletion {
+                Some(ref soft_deletion) => {
+                    let deleted = match soft_deletion.boolean {
+                        true => "true",
+                        false => current_timestamp(attr.db_type()),
+                    };
+                    (
+                        "UPDATE ",
+                        format!(
+                            " SET {} = {deleted} WHERE {} AND {} = ",
+                            soft_deletion.column,
+                            soft_deletion.condition(),
+                            has_many.foreign_key
+                        ),
+                    )
+                }
+                None => (
+                    "DELETE FROM ",
+                    format!(" WHERE {} = ", has_many.foreign_key),
+                ),
+            };
+            quote! {
+                let mut qb = ::sqlx::QueryBuilder::new(#delete_str);
+                qb.push(#table_name);
+                qb.push(#where_str);
+                #pk_bind
+                qb.build().execute(&mut *tx).await?;
+            }
+        })
+        .collect()
+}
+
+pub fn delete_fn(attr: &Attr) -> proc_macro2::TokenStream {
+    let db_type_ident = attr.db_type().to_ident();
+    let return_type = delete_return_type(attr);
+    let function_output = return_type.clone().function_output();
+    let query_builder_execution = return_type.clone().query_builder_execution(attr);
+    let returning_statement = return_type.returning_statement(attr);
+    let (pk_name, pk_bind) = match attr.primary_key {
+        Some(ref pk) => (pk.sql_name(&attr.db_type()), self_primary_key_bind(pk)),
+        None => panic!("No primary key field found"),
+    };
+    let delete_statement = delete_statement(attr);
+    let where_statement = where_statement(attr);
+    let cascade_delete_statements = cascade_delete_statements(attr);
+    if !cascade_delete_statements.is_empty() {
+        let database_ident = attr.db_type().to_database_ident();
+        // The children are deleted first, in the same transaction as the struct
+        return quote! {
+            pub async fn delete<'c, A>(&self, db: A) -> #function_output
+            where
+                A: ::sqlx::Acquire<'c, Database = ::sqlx::#database_ident>
+            {
+                let mut tx = db.begin().await?;
+                #(#cascade_delete_statements)*
+
+                let result = {
+                    let db = &mut *tx;
+                    #delete_statement
+                    #where_statement
+                    qb.push(#pk_name);
+                    qb.push(" = ");
+                    #pk_bind
+
+                    #returning_statement
+
+                    #query_builder_execution
+                }?;
+                tx.commit().await?;
+
+                Ok(result)
+            }
+        };
+    }
+    quote! {
+        pub async fn delete<'e, E>(&self, db: E) -> #function_output
+        where
+            E: ::sqlx::#db_type_ident<'e>
+        {
+            #delete_statement
+            #where_statement
+            qb.push(#pk_name);
+            qb.push(" = ");
+            #pk_bind
+
+            #returning_statement
+
+            #query_builder_execution
+        }
+    }
+}
+
+pub fn sql_fns(attr: &Attr) -> proc_macro2::TokenStream {
+    let database_ident = attr.db_type().to_database_ident();
+    let table_name = attr.parsed_struct.table_name.quoted(&attr.db_type());
+    // Values are never sent to the database, any bindable type would produce the same placeholder
+    let placeholder = quote! { ::std::option::Option::<i32>::None };
+    let to_sql = quote! {
+        ::sqlx::QueryBuilder::<::sqlx::#database_ident>::sql(&qb).to_string()
+    };
+    let mut sql_fns = Vec::new();
+
+    if attr.operations.contains(&Operation::Get) {
+        let select_statement = select_by_id_statement(attr, "get");
+        sql_fns.push(quote! {
+            pub fn get_by_id_sql() -> String {
+                let id = #placeholder;
+                #select_statement
+                #to_sql
+            }
+        });
+    }
+
+    if attr.operations.contains(&Operation::List) {
+        let where_statement = select_list_where_statement(attr);
+        let order_by_statement = order_by_statement(attr);
+        sql_fns.push(quote! {
+            pub fn list_all_sql() -> String {
+                let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                qb.push(#table_name);
+                #where_statement
+                #order_by_statement
+                #to_sql
+            }
+        });
+    }
+
+    if attr.operations.contains(&Operation::Create) {
+        let columns: Vec<&Column> = attr
+            .columns
+            .iter()
+            .filter(|column| column.is_inserted())
+            .collect();
+        let fields_str = columns
+            .iter()
+            .map(|column| column.sql_name(&attr.db_type()))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let values_quote = columns.iter().map(|column| match column.nextval() {
+            Some(nextval) => quote! {
+                separated.push(#nextval);
+            },
+            None => quote! {
+                separated.push_bind(#placeholder);
+            },
+        });
+        let returning_statement = create_return_type(attr).returning_statement(attr);
+        sql_fns.push(quote! {
+            pub fn create_sql() -> String {
+                let mut qb = ::sqlx::QueryBuilder::new("INSERT INTO ");
+                qb.push(#table_name);
+                qb.push(" (");
+                qb.push(#fields_str);
+                qb.push(") VALUES (");
+
+                let mut separated = qb.separated(", ");
+                #(#values_quote)*
+                separated.push_unseparated(")");
+
+                #returning_statement
+                #to_sql
+            }
+        });
+    }
+
+    if attr.operations.contains(&Operation::Update) {
+        let pk_name = match attr.primary_key {
+            Some(ref pk) => pk.sql_name(&attr.db_type()),
+            None => panic!("No primary key field found"),
+        };
+        let fields_quote = attr
+            .columns
+            .iter()
+            .filter(|column| {
+                !column.auto_increment && !attr.is_primary_key(column) && !column.readonly
+            })
+            .map(|column| {
+                let column_name = column.sql_name(&attr.db_type());
+                quote! {
+                    separated.push(#column_name);
+                    separated.push_unseparated(" = ");
+                    separated.push_bind_unseparated(#placeholder);
+                }
+            });
+        let where_statement = where_statement(attr);
+        let returning_statement = update_return_type(attr).returning_statement(attr);
+        sql_fns.push(quote! {
+            pub fn update_sql() -> String {
+                let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
+                qb.push(#table_name);
+                qb.push(" SET ");
+
+                let mut separated = qb.separated(", ");
+                #(#fields_quote)*
+
+                #where_statement
+                qb.push(#pk_name);
+                qb.push(" = ");
+                qb.push_bind(#placeholder);
+
+                #returning_statement
+                #to_sql
+            }
+        });
+    }
+
+    if attr.operations.contains(&Operation::Delete) {
+        let pk_name = match attr.primary_key {
+            Some(ref pk) => pk.sql_name(&attr.db_type()),
+            None => panic!("No primary key field found"),
+        };
+        let delete_statement = delete_statement(attr);
+        let where_statement = where_statement(attr);
+        let returning_statement = delete_return_type(attr).returning_statement(attr);
+        sql_fns.push(quote! {
+            pub fn delete_sql() -> String {
+                #delete_statement
+                #where_statement
+                qb.push(#pk_name);
+                qb.push(" = ");
+                qb.push_bind(#placeholder);
+                #returning_statement
+                #to_sql
+            }
+        });
+    }
+
+    quote! {
+        #(#sql_fns)*
+    }
+}
+
+pub fn set_option_builder_fns(attr: &Attr) -> proc_macro2::TokenStream {
+    let set_option_columns: Vec<(&Column, &syn::Type)> = attr
+        .columns
+        .iter()
+        .filter(|column| !attr.is_primary_key(column))
+        .filter_map(|column| Some((column, column.set_option_inner_type()?)))
+        .collect();
+    if set_option_columns.is_empty() {
+        return quote! {};
+    }
+
+    let setter_fns = set_option_columns.iter().map(|(column, inner_type)| {
+        let column_ident = &column.ident;
+        let fn_name = format_ident!("with_{}", column.ident);
+        quote! {
+            pub fn #fn_name(mut self, value: impl Into<#inner_type>) -> Self {
+                self.#column_ident = ::tiny_orm::SetOption::Set(value.into());
+                self
+            }
+        }
+    });
+
+    // `new()` can only be generated when all the other columns can be left as `NotSet`
+    let new_fn = match attr.primary_key {
+        Some(ref pk) if set_option_columns.len() + 1 == attr.columns.len() => {
+            let pk_ident = &pk.ident;
+            let pk_type = &pk._type;
+            let not_set_fields = set_option_columns.iter().map(|(column, _)| {
+                let column_ident = &column.ident;
+                quote! { #column_ident: ::tiny_orm::SetOption::NotSet }
+            });
+            let skipped_fields = attr.parsed_struct.skipped_fields.iter().map(|field| {
+                quote! { #field: ::core::default::Default::default() }
+            });
+            let not_set_fields = not_set_fields.chain(skipped_fields);
+            quote! {
+                pub fn new(#pk_ident: #pk_type) -> Self {
+                    Self {
+                        #pk_ident,
+                        #(#not_set_fields),*
+                    }
+                }
+            }
+        }
+        _ => quote! {},
+    };
+
+    quote! {
+        #new_fn
+        #(#setter_fns)*
+    }
+}
+
+/// The fields read from a `row`, the json values being unwrapped and the `transform` ones decoded,
+/// while the skipped fields get their default value
+fn row_fields(columns: &[Column], skipped_fields: &[syn::Ident]) -> Vec<proc_macro2::TokenStream> {
+    let column_fields = columns.iter().map(|column| {
+        let column_ident = &column.ident;
+        let column_name = &column.name;
+        let value = match (&column.transform, column.json, column.is_nullable()) {
+            (Some(transform), _, true) => quote! {
+                ::sqlx::Row::try_get::<Option<_>, _>(row, #column_name)?.map(#transform::decode)
+            },
+            (Some(transform), _, false) => quote! {
+                #transform::decode(::sqlx::Row::try_get(row, #column_name)?)
+            },
+            (None, true, true) => quote! {
+                ::sqlx::Row::try_get::<Option<::sqlx::types::Json<_>>, _>(row, #column_name)?.map(|value| value.0)
+            },
+            (None, true, false) => quote! {
+                ::sqlx::Row::try_get::<::sqlx::types::Json<_>, _>(row, #column_name)?.0
+            },
+            (None, false, _) => quote! {
+                ::sqlx::Row::try_get(row, #column_name)?
+            },
+        };
+        quote! { #column_ident: #value }
+    });
+    let skipped_fields = skipped_fields.iter().map(|field| {
+        quote! { #field: ::core::default::Default::default() }
+    });
+    column_fields.chain(skipped_fields).collect()
+}
+
+/// Reads every column by its name, so that `FromRow` and the queries always agree on the column names
+pub fn from_row_impl(attr: &Attr) -> proc_macro2::TokenStream {
+    let struct_name = &attr.parsed_struct.name;
+    let database_ident = attr.db_type().to_database_ident();
+
+    let fields = row_fields(&attr.columns, &attr.parsed_struct.skipped_fields);
+
+    quote! {
+        impl<'r> ::sqlx::FromRow<'r, <::sqlx::#database_ident as ::sqlx::Database>::Row> for #struct_name {
+            fn from_row(row: &'r <::sqlx::#database_ident as ::sqlx::Database>::Row) -> ::sqlx::Result<Self> {
+                Ok(Self {
+                    #(#fields),*
+                })
+            }
+        }
+    }
+}
+
+pub fn tiny_table_impl(attr: &Attr) -> proc_macro2::TokenStream {
+    let struct_name = &attr.parsed_struct.name;
+    let (impl_generics, ty_generics, where_clause) = attr.parsed_struct.generics.split_for_impl();
+    let database_ident = attr.db_type().to_database_ident();
+    let return_object = &attr.parsed_struct.return_object;
+    let pk_type = match attr.primary_key {
+        Some(ref pk) => pk.unwrapped_type(),
+        None => panic!("No primary key field found which is mandatory for the 'TinyTable' trait"),
+    };
+    let created_type = create_return_type(attr).output_type();
+    let updated_type = update_return_type(attr).output_type();
+    let delete_call = match delete_return_type(attr) {
+        ReturnType::None => quote! { Self::delete(self, db) },
+        _ => quote! { async move { Self::delete(self, db).await.map(|_| ()) } },
+    };
+
+    quote! {
+        impl #impl_generics ::tiny_orm::TinyTable for #struct_name #ty_generics #where_clause {
+            type Database = ::sqlx::#database_ident;
+            type PrimaryKey = #pk_type;
+            type ReturnObject = #return_object;
+            type Created = #created_type;
+            type Updated = #updated_type;
+
+            fn table_name() -> &'static str {
+                Self::TABLE_NAME
+            }
+
+            fn get_by_id<'e, E>(
+                db: E,
+                id: &Self::PrimaryKey,
+            ) -> impl ::std::future::Future<Output = ::sqlx::Result<Option<Self::ReturnObject>>> + Send
+            where
+                E: ::sqlx::Executor<'e, Database = Self::Database>
+            {
+                Self::get_by_id(db, id)
+            }
+
+            fn create<'e, E>(&self, db: E) -> impl ::std::future::Future<Output = ::sqlx::Result<Self::Created>> + Send
+            where
+                E: ::sqlx::Executor<'e, Database = Self::Database>
+            {
+                Self::create(self, db)
+            }
+
+            fn update<'e, E>(&self, db: E) -> impl ::std::future::Future<Output = ::sqlx::Result<Self::Updated>> + Send
+            where
+                E: ::sqlx::Executor<'e, Database = Self::Database>
+            {
+                Self::update(self, db)
+            }
+
+            fn delete<'e, E>(&self, db: E) -> impl ::std::future::Future<Output = ::sqlx::Result<()>> + Send
+            where
+                E: ::sqlx::Executor<'e, Database = Self::Database>
+            {
+                #delete_call
+            }
+        }
+    }
+}
+
+/// Return the custom `error` of the struct instead of `sqlx::Error`, only in the signature of the `delegating` methods which get it from the methods they call
+pub fn custom_error_fns(
+    attr: &Attr,
+    fns: proc_macro2::TokenStream,
+    delegating: bool,
+) -> proc_macro2::TokenStream {
+    let Some(ref error) = attr.parsed_struct.error else {
+        return fns;
+    };
+    let struct_name = &attr.parsed_struct.name;
+    let mut item_impl: syn::ItemImpl = syn::parse2(quote! { impl #struct_name { #fns } })
+        .expect("The generated methods should be valid");
+
+    for item in item_impl.items.iter_mut() {
+        let syn::ImplItem::Fn(function) = item else {
+            continue;
+        };
+        let syn::ReturnType::Type(_, ref output) = function.sig.output else {
+            continue;
+        };
+        let Some(output_type) = sqlx_result_type(output) else {
+            continue;
+        };
+        if !delegating {
+            let block = &function.block;
+            function.block = syn::parse_quote! {
+                {
+                    let result: ::sqlx::Result<#output_type> = async move #block.await;
+                    result.map_err(::core::convert::From::from)
+                }
+            };
+        }
+        function.sig.output = syn::parse_quote! { -> ::std::result::Result<#output_type, #error> };
+    }
+
+    let items = item_impl.items;
+    quote! { #(#items)* }
+}
+
+/// The `T` of a `::sqlx::Result<T>` type
+fn sqlx_result_type(_type: &syn::Type) -> Option<syn::Type> {
+    let syn::Type::Path(type_path) = _type else {
+        return None;
+    };
+    let mut segments = type_path.path.segments.iter();
+    match (segments.next(), segments.next(), segments.next()) {
+        (Some(sqlx), Some(result), None) if sqlx.ident == "sqlx" && result.ident == "Result" => {
+            match &result.arguments {
+                syn::PathArguments::AngleBracketed(arguments) => match arguments.args.first() {
+                    Some(syn::GenericArgument::Type(output_type)) => Some(output_type.clone()),
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Warn when the result of a generated `async` write method is ignored
+pub fn must_use_fns(attr: &Attr, fns: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let struct_name = &attr.parsed_struct.name;
+    let mut item_impl: syn::ItemImpl = syn::parse2(quote! { impl #struct_name { #fns } })
+        .expect("The generated methods should be valid");
+
+    for item in item_impl.items.iter_mut() {
+        let syn::ImplItem::Fn(function) = item else {
+            continue;
+        };
+        if function.sig.asyncness.is_none() {
+            continue;
+        }
+        function
+            .attrs
+            .push(syn::parse_quote! { #[must_use = "database result should be checked"] });
+    }
+
+    let items = item_impl.items;
+    quote! { #(#items)* }
+}
+
+/// Runs the body of every async method of the `impl` block in a `tiny_orm.<method>` span
+/// which records the table name. Only used with the `tracing` feature.
+pub fn instrument_fns(
+    attr: &Attr,
+    item_impl: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let mut item_impl: syn::ItemImpl =
+        syn::parse2(item_impl).expect("The generated impl block should be valid");
+    let table_name = attr.parsed_struct.table_name.to_string();
+
+    for item in item_impl.items.iter_mut() {
+        let syn::ImplItem::Fn(function) = item else {
+            continue;
+        };
+        if function.sig.asyncness.is_none() {
+            continue;
+        }
+        let span_name = format!("tiny_orm.{}", function.sig.ident);
+        let block = &function.block;
+        function.block = syn::parse_quote! {
+            {
+                let span = ::tiny_orm::tracing::debug_span!(#span_name, table = #table_name);
+                ::tiny_orm::tracing::Instrument::instrument(async move #block, span).await
+            }
+        };
+    }
+
+    quote! { #item_impl }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database;
+    use syn::Ident;
+
+    pub fn clean_tokens(tokens: proc_macro2::TokenStream) -> String {
+        tokens.to_string().replace([' ', '\n'], "")
+    }
+
+    #[cfg(not(feature = "mysql"))]
+    fn values_str(count: usize) -> String {
+        (1..=count)
+            .map(|index| placeholder(&database::db_type(), index))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    #[cfg(feature = "mysql")]
+    fn db_ident() -> Ident {
+        format_ident!("MySqlExecutor")
+    }
+    #[cfg(feature = "postgres")]
+    fn db_ident() -> Ident {
+        format_ident!("PgExecutor")
+    }
+    #[cfg(feature = "sqlite")]
+    fn db_ident() -> Ident {
+        format_ident!("SqliteExecutor")
+    }
+
+    mod simple_attr {
+        use quote::format_ident;
+        use syn::parse_quote;
+
+        use crate::types::{
+            BelongsTo, Column, ConflictTarget, HasMany, Inject, Operation, OrderBy, ParsedStruct,
+            Projection, RenameAll, Returning, Scope, SoftDeletion, TableName,
+        };
+
+        use super::*;
+
+        fn input(auto_increment: bool, soft_deletion: bool) -> Attr {
+            let mut primary_key = Column::new("id", parse_quote!(i64));
+            primary_key.set_primary_key();
+            if auto_increment {
+                primary_key.set_auto_increment();
+            };
+            let parsed_struct = ParsedStruct::new(
+                &format_ident!("Contact"),
+                Some("contact".to_string()),
+                Some(format_ident!("Self")),
+            );
+            Attr {
+                parsed_struct,
+                primary_key: Some(primary_key.clone()),
+                lookup_key: None,
+                cursor: None,
+                columns: vec![
+                    primary_key,
+                    Column::new("created_at", parse_quote!(DateTime<Utc>)),
+                    Column::new("updated_at", parse_quote!(DateTime<Utc>)),
+                    Column::new("last_name", parse_quote!(String)),
+                ],
+                operations: Operation::all(),
+                soft_deletion: soft_deletion.then(SoftDeletion::default),
+            }
+        }
+
+        #[test]
+        fn test_table_name() {
+            let generated = clean_tokens(get_table_name(&input(false, false)));
+            let expected = clean_tokens(quote! {
+                pub const TABLE_NAME: &'static str = "contact";
+
+                pub fn table_name() -> &'static str {
+                    Self::TABLE_NAME
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_table_name_with_a_custom_function_name() {
+            let mut attr = input(false, false);
+            attr.parsed_struct.table_name_fn = Some(format_ident!("orm_table"));
+            let generated = clean_tokens(get_table_name(&attr));
+            let expected = clean_tokens(quote! {
+                pub const TABLE_NAME: &'static str = "contact";
+
+                pub fn orm_table() -> &'static str {
+                    Self::TABLE_NAME
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_track_env_vars() {
+            let mut attr = input(false, false);
+            assert!(track_env_vars(&attr).is_empty());
+
+            attr.parsed_struct.env_vars = vec![parse_quote!("TABLE_NAME")];
+            let generated = clean_tokens(track_env_vars(&attr));
+            let expected = clean_tokens(quote! {
+                const _: &str = ::core::env!("TABLE_NAME");
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_table_exists_method() {
+            for (db_type, db_ident, catalog_str) in [
+                (
+                    DbType::Postgres,
+                    format_ident!("PgExecutor"),
+                    "SELECT COUNT(*) FROM information_schema.tables WHERE table_schema = current_schema() AND table_name = ",
+                ),
+                (
+                    DbType::MySQL,
+                    format_ident!("MySqlExecutor"),
+                    "SELECT COUNT(*) FROM information_schema.tables WHERE table_schema = DATABASE() AND table_name = ",
+                ),
+                (
+                    DbType::Sqlite,
+                    format_ident!("SqliteExecutor"),
+                    "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ",
+                ),
+            ] {
+                let mut attr = input(false, false);
+                attr.parsed_struct.database = Some(db_type);
+                let generated = clean_tokens(table_exists_fn(&attr));
+
+                let expected = clean_tokens(quote! {
+                    pub async fn table_exists<'e, E>(db: E) -> ::sqlx::Result<bool>
+                    where
+                        E: ::sqlx::#db_ident<'e>
+                    {
+                        let mut qb = ::sqlx::QueryBuilder::new(#catalog_str);
+                        qb.push_bind("contact");
+
+                        qb.build_query_scalar()
+                        .fetch_one(db)
+                        .await
+                        .map(|count: i64| count > 0)
+                    }
+                });
+                assert_eq!(generated, expected);
+            }
+        }
+
+        #[test]
+        fn test_generate_table_exists_method_with_qualified_table_name() {
+            for (db_type, catalog_str) in [
+                (
+                    DbType::Postgres,
+                    "SELECT COUNT(*) FROM information_schema.tables WHERE table_schema = 'billing' AND table_name = ",
+                ),
+                (
+                    DbType::MySQL,
+                    "SELECT COUNT(*) FROM information_schema.tables WHERE table_schema = 'billing' AND table_name = ",
+                ),
+                (
+                    DbType::Sqlite,
+                    "SELECT COUNT(*) FROM billing.sqlite_master WHERE type = 'table' AND name = ",
+                ),
+            ] {
+                let mut attr = input(false, false);
+                attr.parsed_struct.database = Some(db_type);
+                attr.parsed_struct.table_name = TableName::new("billing.invoice");
+                let generated = clean_tokens(table_exists_fn(&attr));
+                assert!(generated.contains(&clean_tokens(quote! {
+                    let mut qb = ::sqlx::QueryBuilder::new(#catalog_str);
+                    qb.push_bind("invoice");
+                })));
+            }
+        }
+
+        #[test]
+        fn test_qualified_table_name_is_quoted_per_backend() {
+            for (db_type, quoted) in [
+                (DbType::Postgres, r#""billing"."invoice""#),
+                (DbType::MySQL, "`billing`.`invoice`"),
+                (DbType::Sqlite, r#""billing"."invoice""#),
+            ] {
+                let mut attr = input(false, false);
+                attr.parsed_struct.database = Some(db_type);
+                attr.parsed_struct.table_name = TableName::new("billing.invoice");
+
+                // The table name itself is not SQL and stays unquoted
+                let generated = clean_tokens(get_table_name(&attr));
+                assert!(generated.contains(&clean_tokens(quote! {
+                    pub const TABLE_NAME: &'static str = "billing.invoice";
+                })));
+                let generated = clean_tokens(list_all_fn(&attr));
+                assert!(generated.contains(&clean_tokens(quote! {
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push(#quoted);
+                })));
+                let generated = clean_tokens(delete_fn(&attr));
+                assert!(generated.contains(&clean_tokens(quote! {
+                    qb.push(#quoted);
+                })));
+                let generated = create_fn(&attr).to_string();
+                let insert_str = format!("{:?}", format!("INSERT INTO {quoted} ("));
+                assert!(generated.contains(insert_str.trim_end_matches('"')));
+            }
+        }
+
+        #[test]
+        fn test_columns() {
+            let generated = clean_tokens(get_columns(&input(false, false)));
+            let expected = clean_tokens(quote! {
+                pub const COLUMNS: &'static [&'static str] = &["id", "created_at", "updated_at", "last_name"];
+
+                pub fn columns() -> &'static [&'static str] {
+                    Self::COLUMNS
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_operations() {
+            let mut attr = input(false, false);
+            // Same as `exclude = "create,update"`
+            attr.operations = vec![Operation::Delete, Operation::Get, Operation::List];
+            let generated = clean_tokens(get_operations(&attr));
+            let expected = clean_tokens(quote! {
+                pub const OPERATIONS: &'static [&'static str] = &["get", "list", "delete"];
+
+                pub fn operations() -> &'static [&'static str] {
+                    Self::OPERATIONS
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_primary_key_name() {
+            let generated = clean_tokens(get_primary_key_name(&input(false, false)));
+            let expected = clean_tokens(quote! {
+                pub const PRIMARY_KEY_NAME: Option<&'static str> = Some("id");
+
+                pub fn primary_key_name() -> Option<&'static str> {
+                    Self::PRIMARY_KEY_NAME
+                }
+            });
+            assert_eq!(generated, expected);
+
+            let generated = clean_tokens(get_primary_key_name(&renamed_input()));
+            assert!(generated.starts_with(&clean_tokens(quote! {
+                pub const PRIMARY_KEY_NAME: Option<&'static str> = Some("contact_id");
+            })));
+
+            let mut attr = input(false, false);
+            attr.primary_key = None;
+            let generated = clean_tokens(get_primary_key_name(&attr));
+            assert!(generated.starts_with(&clean_tokens(quote! {
+                pub const PRIMARY_KEY_NAME: Option<&'static str> = None;
+            })));
+        }
+
+        #[test]
+        fn test_columns_with_renamed_columns() {
+            let generated = clean_tokens(get_columns(&renamed_input()));
+            assert!(generated.starts_with(&clean_tokens(quote! {
+                pub const COLUMNS: &'static [&'static str] = &["contact_id", "createdAt", "updatedAt", "lastName"];
+            })));
+        }
+
+        #[test]
+        fn test_generate_get_by_method() {
+            let db_ident = db_ident();
+            let database_ident = database::db_type().to_database_ident();
+            let generated = clean_tokens(get_by_fn(&input(false, true)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn get_by<'e, E, V>(db: E, column: &str, value: V) -> ::sqlx::Result<Vec<Self>>
+                where
+                    E: ::sqlx::#db_ident<'e>,
+                    V: 'e + ::sqlx::Encode<'e, ::sqlx::#database_ident> + ::sqlx::Type<::sqlx::#database_ident> + Send
+                {
+                    const SQL_COLUMNS: &[&str] = &["id", "created_at", "updated_at", "last_name"];
+                    let Some(index) = Self::COLUMNS.iter().position(|name| *name == column) else {
+                        return Err(::sqlx::Error::ColumnNotFound(column.to_string()));
+                    };
+
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push("contact");
+                    qb.push(" WHERE deleted_at IS NULL AND ");
+                    qb.push(SQL_COLUMNS[index]);
+                    qb.push(" = ");
+                    qb.push_bind(value);
+
+                    qb.build_query_as()
+                    .fetch_all(db)
+                    .await
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_table_name_for_create_and_update_structs() {
+            for struct_name in ["NewContact", "UpdateContact"] {
+                let mut attr = input(false, false);
+                attr.parsed_struct = ParsedStruct::new(&format_ident!("{struct_name}"), None, None);
+                let generated = clean_tokens(get_table_name(&attr));
+                assert!(generated.contains(&clean_tokens(quote! {
+                    pub const TABLE_NAME: &'static str = "contact";
+                })));
+            }
+        }
+
+        #[test]
+        fn test_generate_sql_fns() {
+            let database_ident = database::db_type().to_database_ident();
+            let generated = clean_tokens(sql_fns(&input(false, false)));
+
+            assert!(generated.starts_with(&clean_tokens(quote! {
+                pub fn get_by_id_sql() -> String {
+                    let id = ::std::option::Option::<i32>::None;
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push("contact");
+                    qb.push(" WHERE ");
+                    qb.push("id");
+                    qb.push(" = ");
+                    qb.push_bind(id);
+                    ::sqlx::QueryBuilder::<::sqlx::#database_ident>::sql(&qb).to_string()
+                }
+            })));
+            for sql_fn in ["list_all_sql", "create_sql", "update_sql", "delete_sql"] {
+                assert!(generated.contains(&format!("pubfn{sql_fn}()->String")));
+            }
+        }
+
+        #[test]
+        fn test_generate_sql_fns_only_for_enabled_operations() {
+            let mut attr = input(false, false);
+            attr.operations = vec![Operation::Create];
+            let generated = clean_tokens(sql_fns(&attr));
+
+            assert!(generated.starts_with("pubfncreate_sql()->String"));
+            assert!(!generated.contains("get_by_id_sql"));
+            assert!(!generated.contains("delete_sql"));
+        }
+
+        #[test]
+        fn test_generate_tiny_table_impl() {
+            let database_ident = database::db_type().to_database_ident();
+            let generated = clean_tokens(tiny_table_impl(&input(false, false)));
+
+            assert!(generated.starts_with(&clean_tokens(quote! {
+                impl ::tiny_orm::TinyTable for Contact
+            })));
+            assert!(generated.contains(&clean_tokens(quote! {
+                type Database = ::sqlx::#database_ident;
+                type PrimaryKey = i64;
+                type ReturnObject = Self;
+                type Created = i64;
+                type Updated = ();
+            })));
+            assert!(generated.contains(&clean_tokens(quote! { Self::get_by_id(db, id) })));
+            assert!(generated.contains(&clean_tokens(quote! { Self::create(self, db) })));
+            assert!(generated.contains(&clean_tokens(quote! { Self::update(self, db) })));
+            assert!(generated.contains(&clean_tokens(quote! { Self::delete(self, db) })));
+        }
+
+        #[cfg(not(feature = "mysql"))]
+        #[test]
+        fn test_generate_create_method() {
+            let db_ident = db_ident();
+            let generated = clean_tokens(create_fn(&input(false, false)));
+            let sql = format!(
+                "INSERT INTO contact (id, created_at, updated_at, last_name) VALUES ({}) RETURNING id",
+                values_str(4)
+            );
+
+            let expected = clean_tokens(quote! {
+                pub async fn create<'e, E>(&self, db: E) -> ::sqlx::Result<i64>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    const SQL: &str = #sql;
+
+                    ::sqlx::query(SQL)
+                    .bind(&self.id)
+                    .bind(&self.created_at)
+                    .bind(&self.updated_at)
+                    .bind(&self.last_name)
+                    .fetch_one(db)
+                    .await
+                    .map(|row| ::sqlx::Row::get(&row, 0))
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_create_method_returning_nothing() {
+            for db_type in [DbType::Postgres, DbType::MySQL, DbType::Sqlite] {
+                let mut attr = input(false, false);
+                attr.parsed_struct.database = Some(db_type.clone());
+                attr.parsed_struct.return_nothing = true;
+                let generated = clean_tokens(create_fn(&attr));
+                let sql = format!(
+                    "INSERT INTO contact (id, created_at, updated_at, last_name) VALUES ({})",
+                    (1..=4)
+                        .map(|index| placeholder(&db_type, index))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                );
+                assert!(generated.contains(&clean_tokens(quote! { -> ::sqlx::Result<()> })));
+                assert!(generated.contains(&clean_tokens(quote! { const SQL: &str = #sql; })));
+                assert!(generated.contains(&clean_tokens(quote! {
+                    .bind(&self.last_name)
+                    .execute(db)
+                    .await
+                    .map(|_| ())
+                })));
+                assert!(create_returning_id_fn(&attr).is_empty());
+                assert!(save_fn(&attr).is_empty());
+            }
+        }
+
+        #[cfg(feature = "sqlite")]
+        #[test]
+        fn test_generate_create_if_not_exists_method() {
+            let generated = clean_tokens(create_if_not_exists_fn(&input(false, false)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn create_if_not_exists<'e, E>(&self, db: E) -> ::sqlx::Result<bool>
+                where
+                    E: ::sqlx::SqliteExecutor<'e>
+                {
+                    let mut fields_str = Vec::new();
+                    fields_str.push("id");
+                    fields_str.push("created_at");
+                    fields_str.push("updated_at");
+                    fields_str.push("last_name");
+
+                    let mut qb = ::sqlx::QueryBuilder::new("INSERT INTO ");
+                    qb.push("contact");
+                    qb.push(" (");
+                    qb.push(fields_str.join(", "));
+                    qb.push(") VALUES (");
+
+                    let mut separated = qb.separated(", ");
+                    separated.push_bind(&self.id);
+                    separated.push_bind(&self.created_at);
+                    separated.push_bind(&self.updated_at);
+                    separated.push_bind(&self.last_name);
+                    separated.push_unseparated(")");
+
+                    qb.push(" ON CONFLICT DO NOTHING");
+
+                    qb.build()
+                    .execute(db)
+                    .await
+                    .map(|result| result.rows_affected() > 0)
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[cfg(feature = "postgres")]
+        #[test]
+        fn test_generate_create_if_not_exists_method() {
+            let generated = clean_tokens(create_if_not_exists_fn(&input(false, false)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn create_if_not_exists<'e, E>(&self, db: E) -> ::sqlx::Result<bool>
+                where
+                    E: ::sqlx::PgExecutor<'e>
+                {
+                    let mut fields_str = Vec::new();
+                    fields_str.push("id");
+                    fields_str.push("created_at");
+                    fields_str.push("updated_at");
+                    fields_str.push("last_name");
+
+                    let mut qb = ::sqlx::QueryBuilder::new("INSERT INTO ");
+                    qb.push("contact");
+                    qb.push(" (");
+                    qb.push(fields_str.join(", "));
+                    qb.push(") VALUES (");
+
+                    let mut separated = qb.separated(", ");
+                    separated.push_bind(&self.id);
+                    separated.push_bind(&self.created_at);
+                    separated.push_bind(&self.updated_at);
+                    separated.push_bind(&self.last_name);
+                    separated.push_unseparated(")");
+
+                    qb.push(" ON CONFLICT DO NOTHING");
+
+                    qb.build()
+                    .execute(db)
+                    .await
+                    .map(|result| result.rows_affected() > 0)
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[cfg(feature = "mysql")]
+        #[test]
+        fn test_generate_create_if_not_exists_method() {
+            let generated = clean_tokens(create_if_not_exists_fn(&input(false, false)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn create_if_not_exists<'e, E>(&self, db: E) -> ::sqlx::Result<bool>
+                where
+                    E: ::sqlx::MySqlExecutor<'e>
+                {
+                    let mut fields_str = Vec::new();
+                    fields_str.push("id");
+                    fields_str.push("created_at");
+                    fields_str.push("updated_at");
+                    fields_str.push("last_name");
+
+                    let mut qb = ::sqlx::QueryBuilder::new("INSERT IGNORE INTO ");
+                    qb.push("contact");
+                    qb.push(" (");
+                    qb.push(fields_str.join(", "));
+                    qb.push(") VALUES (");
+
+                    let mut separated = qb.separated(", ");
+                    separated.push_bind(&self.id);
+                    separated.push_bind(&self.created_at);
+                    separated.push_bind(&self.updated_at);
+                    separated.push_bind(&self.last_name);
+                    separated.push_unseparated(")");
+
+                    qb.build()
+                    .execute(db)
+                    .await
+                    .map(|result| result.rows_affected() > 0)
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_create_method_without_db_default_columns() {
+            let mut attr = input(false, false);
+            attr.columns[1].set_db_default();
+            attr.columns[2].set_db_default();
+
+            let generated = clean_tokens(create_fn(&attr));
+            assert!(generated.contains("INSERTINTOcontact(id,last_name)VALUES"));
+            assert!(!generated.contains("created_at"));
+            assert!(!generated.contains("updated_at"));
+
+            let generated = clean_tokens(create_if_not_exists_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                let mut fields_str = Vec::new();
+                fields_str.push("id");
+                fields_str.push("last_name");
+            })));
+            assert!(generated.contains(&clean_tokens(quote! {
+                let mut separated = qb.separated(", ");
+                separated.push_bind(&self.id);
+                separated.push_bind(&self.last_name);
+                separated.push_unseparated(")");
+            })));
+            assert!(!generated.contains("created_at"));
+            assert!(!generated.contains("updated_at"));
+
+            let generated = clean_tokens(sql_fns(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push("id, last_name");
+            })));
+        }
+
+        #[test]
+        fn test_generate_methods_without_readonly_columns() {
+            let mut attr = input(false, false);
+            attr.columns[3].set_readonly();
+
+            let generated = clean_tokens(get_by_id_fn(&attr));
+            assert!(generated.contains("SELECT*FROM"));
+
+            for generated in [create_fn(&attr), update_fn(&attr), sql_fns(&attr)] {
+                assert!(!clean_tokens(generated).contains("last_name"));
+            }
+        }
+
+        #[cfg(feature = "mysql")]
+        #[test]
+        fn test_generate_create_method() {
+            let generated = clean_tokens(create_fn(&input(false, false)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn create<'e, E>(&self, db: E) -> ::sqlx::Result<i64>
+                where
+                    E: ::sqlx::MySqlExecutor<'e>
+                {
+                    const SQL: &str = "INSERT INTO contact (id, created_at, updated_at, last_name) VALUES (?, ?, ?, ?)";
+
+                    let result = ::sqlx::query(SQL)
+                    .bind(&self.id)
+                    .bind(&self.created_at)
+                    .bind(&self.updated_at)
+                    .bind(&self.last_name)
+                    .execute(db)
+                    .await?;
+                    if result.rows_affected() == 0 {
+                        return Err(::sqlx::Error::RowNotFound);
+                    }
+
+                    Ok(self.id.clone())
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[cfg(feature = "mysql")]
+        #[test]
+        fn test_generate_create_method_without_inserted_row() {
+            let generated = clean_tokens(create_fn(&input(false, false)));
+            let not_found = clean_tokens(quote! {
+                if result.rows_affected() == 0 {
+                    return Err(::sqlx::Error::RowNotFound);
+                }
+            });
+            let output = clean_tokens(quote! { Ok(self.id.clone()) });
+
+            let not_found_index = generated.find(&not_found).unwrap();
+            assert!(not_found_index < generated.find(&output).unwrap());
+        }
+
+        #[cfg(not(feature = "mysql"))]
+        #[test]
+        fn test_generate_create_method_with_auto_primary_key() {
+            let db_ident = db_ident();
+            let generated = clean_tokens(create_fn(&input(true, false)));
+            let sql = format!(
+                "INSERT INTO contact (created_at, updated_at, last_name) VALUES ({}) RETURNING id",
+                values_str(3)
+            );
+
+            let expected = clean_tokens(quote! {
+                pub async fn create<'e, E>(&self, db: E) -> ::sqlx::Result<i64>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    const SQL: &str = #sql;
+
+                    ::sqlx::query(SQL)
+                    .bind(&self.created_at)
+                    .bind(&self.updated_at)
+                    .bind(&self.last_name)
+                    .fetch_one(db)
+                    .await
+                    .map(|row| ::sqlx::Row::get(&row, 0))
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[cfg(feature = "mysql")]
+        #[test]
+        fn test_generate_create_method_with_auto_primary_key() {
+            let generated = clean_tokens(create_fn(&input(true, false)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn create<'e, E>(&self, db: E) -> ::sqlx::Result<i64>
+                where
+                    E: ::sqlx::MySqlExecutor<'e>
+                {
+                    const SQL: &str = "INSERT INTO contact (created_at, updated_at, last_name) VALUES (?, ?, ?)";
+
+                    ::sqlx::query(SQL)
+                    .bind(&self.created_at)
+                    .bind(&self.updated_at)
+                    .bind(&self.last_name)
+                    .execute(db)
+                    .await
+                    .map(|result|result.last_insert_id()as_)
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_create_for_method() {
+            let mut attr = input(true, false);
+            attr.parsed_struct.database = Some(DbType::Postgres);
+            attr.parsed_struct.inject = Some(Inject::new("tenant_id").unwrap());
+            let generated = clean_tokens(create_for_fn(&attr));
+
+            let expected = clean_tokens(quote! {
+                pub async fn create_for<'e, E, T>(&self, tenant: &T, db: E) -> ::sqlx::Result<i64>
+                where
+                    E: ::sqlx::PgExecutor<'e>,
+                    T: for<'q> ::sqlx::Encode<'q, ::sqlx::Postgres> + ::sqlx::Type<::sqlx::Postgres> + Sync
+                {
+                    const SQL: &str = "INSERT INTO contact (created_at, updated_at, last_name, tenant_id) VALUES ($1, $2, $3, $4) RETURNING id";
+
+                    ::sqlx::query(SQL)
+                    .bind(&self.created_at)
+                    .bind(&self.updated_at)
+                    .bind(&self.last_name)
+                    .bind(tenant)
+                    .fetch_one(db)
+                    .await
+                    .map(|row| ::sqlx::Row::get(&row, 0))
+                }
+            });
+            assert_eq!(generated, expected);
+
+            // A field of the injected column is replaced by the argument
+            attr.columns
+                .push(Column::new("tenant_id", parse_quote!(i64)));
+            let generated = clean_tokens(create_for_fn(&attr));
+            assert!(generated.contains(".bind(tenant)"));
+            assert!(!generated.contains("self.tenant_id"));
+            assert!(clean_tokens(create_fn(&attr)).contains(".bind(&self.tenant_id)"));
+
+            // Only the columns known at runtime are pushed one by one
+            attr.parsed_struct.skip_defaults = true;
+            let generated = clean_tokens(create_for_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                fields_str.push("tenant_id");
+            })));
+            assert!(generated.contains(&clean_tokens(quote! {
+                separated.push_bind(tenant);
+            })));
+        }
+
+        #[test]
+        fn test_no_create_for_method_without_inject() {
+            assert!(create_for_fn(&input(true, false)).is_empty());
+        }
+
+        fn sequence_input() -> Attr {
+            let mut attr = input(false, false);
+            attr.columns[0].set_sequence("contact_id_seq").unwrap();
+            attr.primary_key = Some(attr.columns[0].clone());
+            attr
+        }
+
+        #[cfg(feature = "postgres")]
+        #[test]
+        fn test_generate_create_method_with_sequence_primary_key() {
+            let generated = clean_tokens(create_fn(&sequence_input()));
+
+            let expected = clean_tokens(quote! {
+                pub async fn create<'e, E>(&self, db: E) -> ::sqlx::Result<i64>
+                where
+                    E: ::sqlx::PgExecutor<'e>
+                {
+                    const SQL: &str = "INSERT INTO contact (id, created_at, updated_at, last_name) VALUES (nextval('contact_id_seq'), $1, $2, $3) RETURNING id";
+
+                    ::sqlx::query(SQL)
+                    .bind(&self.created_at)
+                    .bind(&self.updated_at)
+                    .bind(&self.last_name)
+                    .fetch_one(db)
+                    .await
+                    .map(|row| ::sqlx::Row::get(&row, 0))
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[cfg(feature = "postgres")]
+        #[test]
+        fn test_generate_create_many_method_with_sequence_primary_key() {
+            let generated = clean_tokens(create_many_fn(&sequence_input()));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" (id, created_at, updated_at, last_name) ");
+                qb.push_values(chunk, |mut separated, item| {
+                    separated.push("nextval('contact_id_seq')");
+                    separated.push_bind(&item.created_at);
+                    separated.push_bind(&item.updated_at);
+                    separated.push_bind(&item.last_name);
+                });
+                qb.push(" RETURNING id");
+            })));
+        }
+
+        #[test]
+        fn test_sequence_primary_key_is_rejected_outside_postgres() {
+            let mut attr = sequence_input();
+            attr.parsed_struct.database = Some(DbType::Postgres);
+            assert!(crate::generate_impl(&attr).is_ok());
+
+            for db_type in [DbType::MySQL, DbType::Sqlite] {
+                attr.parsed_struct.database = Some(db_type);
+                let error = crate::generate_impl(&attr).unwrap_err();
+                assert_eq!(
+                    error.to_string(),
+                    "`primary_key(sequence)` is only supported by Postgres"
+                );
+            }
+        }
+
+        fn returning_generated_input() -> Attr {
+            let mut attr = input(true, false);
+            attr.parsed_struct.returning_generated = true;
+            attr.parsed_struct.database = Some(DbType::Postgres);
+            attr.columns[1].set_db_default();
+            attr.columns[2].set_db_default();
+            attr
+        }
+
+        #[test]
+        fn test_generate_create_method_returning_generated_columns() {
+            let generated = clean_tokens(create_fn(&returning_generated_input()));
+
+            let expected = clean_tokens(quote! {
+                pub async fn create<'e, E>(&self, db: E) -> ::sqlx::Result<(i64, DateTime<Utc>, DateTime<Utc>,)>
+                where
+                    E: ::sqlx::PgExecutor<'e>
+                {
+                    const SQL: &str = "INSERT INTO contact (last_name) VALUES ($1) RETURNING id, created_at, updated_at";
+
+                    ::sqlx::query_as(SQL)
+                    .bind(&self.last_name)
+                    .fetch_one(db)
+                    .await
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_save_method_returning_generated_columns() {
+            let generated = clean_tokens(save_fn(&returning_generated_input()));
+            assert!(generated.contains(&clean_tokens(quote! {
+                let id = self.create(db).await?.0;
+            })));
+        }
+
+        #[test]
+        fn test_returning_generated_is_rejected_without_returning() {
+            let mut attr = returning_generated_input();
+            assert!(crate::generate_impl(&attr).is_ok());
+
+            attr.parsed_struct.database = Some(DbType::MySQL);
+            let error = crate::generate_impl(&attr).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "`returning_generated` requires `RETURNING`, which is not supported by MySQL or `sqlite-no-returning`"
+            );
+        }
+
+        fn sqlite_no_returning_input(auto_increment: bool) -> Attr {
+            let mut attr = input(auto_increment, false);
+            attr.parsed_struct.database = Some(DbType::Sqlite);
+            attr.parsed_struct.sqlite_no_returning = true;
+            attr
+        }
+
+        #[test]
+        fn test_generate_create_method_with_auto_primary_key_without_returning() {
+            let generated = clean_tokens(create_fn(&sqlite_no_returning_input(true)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn create<'e, E>(&self, db: E) -> ::sqlx::Result<i64>
+                where
+                    E: ::sqlx::SqliteExecutor<'e>
+                {
+                    const SQL: &str = "INSERT INTO contact (created_at, updated_at, last_name) VALUES (?, ?, ?)";
+
+                    ::sqlx::query(SQL)
+                    .bind(&self.created_at)
+                    .bind(&self.updated_at)
+                    .bind(&self.last_name)
+                    .execute(db)
+                    .await
+                    .map(|result|result.last_insert_rowid()as_)
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[cfg(feature = "sqlite-no-returning")]
+        #[test]
+        fn test_create_with_auto_primary_key_uses_last_insert_rowid() {
+            let input: syn::DeriveInput = parse_quote! {
+                struct NewContact {
+                    #[tiny_orm(primary_key(auto))]
+                    id: i64,
+                    last_name: String,
+                }
+            };
+
+            let attr = Attr::parse(input).unwrap();
+            let generated = create_fn(&attr).to_string();
+            assert!(generated.contains("last_insert_rowid"));
+            assert!(!generated.contains("RETURNING"));
+        }
+
+        #[test]
+        fn test_generate_create_method_with_primary_key_without_returning() {
+            let generated = clean_tokens(create_fn(&sqlite_no_returning_input(false)));
+            assert!(!generated.contains("RETURNING"));
+            assert!(generated.contains(&clean_tokens(quote! {
+                Ok(self.id.clone())
+            })));
+        }
+
+        #[test]
+        fn test_generate_delete_method_with_delete_returning_without_returning() {
+            let mut attr = sqlite_no_returning_input(false);
+            attr.parsed_struct.delete_returning = true;
+            let generated = clean_tokens(delete_fn(&attr));
+            assert!(!generated.contains("RETURNING"));
+            assert!(generated.contains(&clean_tokens(quote! {
+                -> ::sqlx::Result<()>
+            })));
+        }
+
+        #[test]
+        fn test_generate_create_method_without_primary_key_without_returning() {
+            let mut attr = sqlite_no_returning_input(false);
+            attr.primary_key = None;
+            attr.columns.remove(0);
+            let generated = clean_tokens(create_fn(&attr));
+
+            let expected = clean_tokens(quote! {
+                pub async fn create<'c, A>(&self, db: A) -> ::sqlx::Result<Self>
+                where
+                    A: ::sqlx::Acquire<'c, Database = ::sqlx::Sqlite>
+                {
+                    const SQL: &str = "INSERT INTO contact (created_at, updated_at, last_name) VALUES (?, ?, ?)";
+
+                    let mut conn = db.acquire().await?;
+                    let result = ::sqlx::query(SQL)
+                    .bind(&self.created_at)
+                    .bind(&self.updated_at)
+                    .bind(&self.last_name)
+                    .execute(&mut *conn)
+                    .await?;
+
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push("contact");
+                    qb.push(" WHERE rowid = ");
+                    qb.push_bind(result.last_insert_rowid());
+                    qb.build_query_as()
+                    .fetch_one(&mut *conn)
+                    .await
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_get_by_id_method() {
+            let db_ident = db_ident();
+            let generated = clean_tokens(get_by_id_fn(&input(false, false)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn get_by_id<'e, E>(db: E, id: impl ::std::borrow::Borrow<i64>) -> ::sqlx::Result<Option<Self>>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let id = ::std::borrow::Borrow::<i64>::borrow(&id);
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push("contact");
+                    qb.push(" WHERE ");
+                    qb.push("id");
+                    qb.push(" = ");
+                    qb.push_bind(id);
+
+                    qb.build_query_as()
+                    .fetch_optional(db)
+                    .await
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_get_by_id_method_with_lookup_key() {
+            let db_ident = db_ident();
+            let mut attr = input(false, false);
+            let slug = Column::new("slug", parse_quote!(String));
+            attr.columns.push(slug.clone());
+            attr.lookup_key = Some(slug);
+            let generated = clean_tokens(get_by_id_fn(&attr));
+
+            let expected = clean_tokens(quote! {
+                pub async fn get_by_id<'e, E>(db: E, id: impl ::std::borrow::Borrow<String>) -> ::sqlx::Result<Option<Self>>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let id = ::std::borrow::Borrow::<String>::borrow(&id);
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push("contact");
+                    qb.push(" WHERE ");
+                    qb.push("slug");
+                    qb.push(" = ");
+                    qb.push_bind(id);
+
+                    qb.build_query_as()
+                    .fetch_optional(db)
+                    .await
+                }
+            });
+            assert_eq!(generated, expected);
+
+            // The write queries still use the primary key
+            let generated = clean_tokens(update_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" WHERE ");
+                qb.push("id");
+                qb.push(" = ");
+                qb.push_bind(&self.id);
+            })));
+        }
+
+        #[test]
+        fn test_generate_get_by_id_or_err_method() {
+            let db_ident = db_ident();
+            let generated = clean_tokens(get_by_id_or_err_fn(&input(false, false)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn get_by_id_or_err<'e, E>(db: E, id: impl ::std::borrow::Borrow<i64>) -> ::sqlx::Result<Self>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let id = ::std::borrow::Borrow::<i64>::borrow(&id);
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push("contact");
+                    qb.push(" WHERE ");
+                    qb.push("id");
+                    qb.push(" = ");
+                    qb.push_bind(id);
+
+                    qb.build_query_as()
+                    .fetch_one(db)
+                    .await
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_get_by_id_method_with_soft_deletion() {
+            let db_ident = db_ident();
+            let generated = clean_tokens(get_by_id_fn(&input(false, true)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn get_by_id<'e, E>(db: E, id: impl ::std::borrow::Borrow<i64>) -> ::sqlx::Result<Option<Self>>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let id = ::std::borrow::Borrow::<i64>::borrow(&id);
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push("contact");
+                    qb.push(" WHERE deleted_at IS NULL AND ");
+                    qb.push("id");
+                    qb.push(" = ");
+                    qb.push_bind(id);
+
+                    qb.build_query_as()
+                    .fetch_optional(db)
+                    .await
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_list_all_method() {
+            let db_ident = db_ident();
+            let generated = clean_tokens(list_all_fn(&input(false, false)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn list_all<'e, E>(db: E) -> ::sqlx::Result<Vec<Self>>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push("contact");
+
+                    qb.build_query_as()
+                    .fetch_all(db)
+                    .await
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_list_page_method() {
+            let database_ident = database::db_type().to_database_ident();
+            let generated = clean_tokens(list_page_fn(&input(false, true)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn list_page<'c, A>(db: A, limit: i64, offset: i64) -> ::sqlx::Result<::tiny_orm::Page<Self>>
+                where
+                    A: ::sqlx::Acquire<'c, Database = ::sqlx::#database_ident>
+                {
+                    let mut conn = db.acquire().await?;
+
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT COUNT(*) FROM ");
+                    qb.push("contact");
+                    qb.push("WHERE deleted_at IS NULL ");
+                    let total: i64 = qb.build_query_scalar().fetch_one(&mut *conn).await?;
+
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push("contact");
+                    qb.push("WHERE deleted_at IS NULL ");
+                    qb.push(" LIMIT ");
+                    qb.push_bind(limit);
+                    qb.push(" OFFSET ");
+                    qb.push_bind(offset);
+                    let items = qb.build_query_as().fetch_all(&mut *conn).await?;
+
+                    Ok(::tiny_orm::Page::new(items, total, limit, offset))
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_list_all_ordered_method() {
+            let db_ident = db_ident();
+            let generated = clean_tokens(list_all_ordered_fn(&input(false, true)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn list_all_ordered<'e, E>(db: E, order: &[(&str, ::tiny_orm::Order)]) -> ::sqlx::Result<Vec<Self>>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push("contact");
+                    qb.push("WHERE deleted_at IS NULL ");
+                    const SQL_COLUMNS: &[&str] = &["id", "created_at", "updated_at", "last_name"];
+                    for (index, (column, direction)) in order.iter().enumerate() {
+                        // Only the known columns are pushed in the query
+                        let Some(position) = Self::COLUMNS.iter().position(|name| name == column) else {
+                            return Err(::sqlx::Error::ColumnNotFound(column.to_string()));
+                        };
+                        qb.push(if index == 0 { " ORDER BY " } else { ", " });
+                        qb.push(SQL_COLUMNS[position]);
+                        qb.push(" ");
+                        qb.push(direction.as_sql());
+                    }
+
+                    qb.build_query_as()
+                    .fetch_all(db)
+                    .await
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_list_all_method_with_default_order() {
+            let db_ident = db_ident();
+            let mut attr = input(false, false);
+            attr.parsed_struct.order_by = Some(OrderBy::new("created_at DESC, id").unwrap());
+            let generated = clean_tokens(list_all_fn(&attr));
+
+            let expected = clean_tokens(quote! {
+                pub async fn list_all<'e, E>(db: E) -> ::sqlx::Result<Vec<Self>>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push("contact");
+                    qb.push(" ORDER BY created_at DESC, id ASC");
+
+                    qb.build_query_as()
+                    .fetch_all(db)
+                    .await
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_list_all_ordered_method_overrides_the_default_order() {
+            let db_ident = db_ident();
+            let mut attr = input(false, true);
+            attr.parsed_struct.order_by = Some(OrderBy::new("created_at DESC").unwrap());
+            let generated = clean_tokens(list_all_ordered_fn(&attr));
+
+            let expected = clean_tokens(quote! {
+                pub async fn list_all_ordered<'e, E>(db: E, order: &[(&str, ::tiny_orm::Order)]) -> ::sqlx::Result<Vec<Self>>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push("contact");
+                    qb.push("WHERE deleted_at IS NULL ");
+                    const SQL_COLUMNS: &[&str] = &["id", "created_at", "updated_at", "last_name"];
+                    for (index, (column, direction)) in order.iter().enumerate() {
+                        // Only the known columns are pushed in the query
+                        let Some(position) = Self::COLUMNS.iter().position(|name| name == column) else {
+                            return Err(::sqlx::Error::ColumnNotFound(column.to_string()));
+                        };
+                        qb.push(if index == 0 { " ORDER BY " } else { ", " });
+                        qb.push(SQL_COLUMNS[position]);
+                        qb.push(" ");
+                        qb.push(direction.as_sql());
+                    }
+                    if order.is_empty() {
+                        qb.push(" ORDER BY created_at DESC");
+                    }
+
+                    qb.build_query_as()
+                    .fetch_all(db)
+                    .await
+                }
+            });
+
+            assert_eq!(generated, expected);
+            // The runtime order and the default order are exclusive branches, each with a single `ORDER BY`
+            assert_eq!(generated.matches("ORDERBY").count(), 2);
+            assert!(generated.contains(&clean_tokens(quote! {
+                if order.is_empty() {
+                    qb.push(" ORDER BY created_at DESC");
+                }
+            })));
+        }
+
+        #[test]
+        fn test_generate_list_projection_fns() {
+            let db_ident = db_ident();
+            let mut attr = input(false, true);
+            attr.parsed_struct.projections =
+                vec![Projection::new(format_ident!("ContactSummary"), "id, last_name").unwrap()];
+            let generated = clean_tokens(list_projection_fns(&attr));
+
+            let expected = clean_tokens(quote! {
+                pub async fn list_all_summary<'e, E>(db: E) -> ::sqlx::Result<Vec<ContactSummary>>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT id, last_name FROM ");
+                    qb.push("contact");
+                    qb.push(" WHERE deleted_at IS NULL ");
+
+                    qb.build_query_as::<ContactSummary>()
+                    .fetch_all(db)
+                    .await
+                }
+            });
+            assert_eq!(generated, expected);
+
+            assert!(list_projection_fns(&input(false, false)).is_empty());
+        }
+
+        #[test]
+        fn test_generate_list_page_method_with_default_order() {
+            let mut attr = input(false, false);
+            attr.parsed_struct.order_by = Some(OrderBy::new("last_name").unwrap());
+            let generated = clean_tokens(list_page_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" ORDER BY last_name ASC");
+                qb.push(" LIMIT ");
+            })));
+            // Only the page is ordered, not the count
+            assert_eq!(generated.matches("ORDERBY").count(), 1);
+        }
+
+        #[test]
+        fn test_generate_list_all_with_deleted_method() {
+            let db_ident = db_ident();
+            let generated = clean_tokens(list_all_with_deleted_fn(&input(false, true)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn list_all_with_deleted<'e, E>(db: E) -> ::sqlx::Result<Vec<Self>>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push("contact");
+
+                    qb.build_query_as()
+                    .fetch_all(db)
+                    .await
+                }
+            });
+            assert_eq!(generated, expected);
+
+            // The `scope` still applies
+            let mut attr = input(false, true);
+            attr.parsed_struct.scope = Some(Scope::new("archived = false").unwrap());
+            let generated = clean_tokens(list_all_with_deleted_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" WHERE (archived = false) ");
+            })));
+        }
+
+        #[test]
+        fn test_generate_get_by_id_with_deleted_method() {
+            let db_ident = db_ident();
+            let generated = clean_tokens(get_by_id_with_deleted_fn(&input(false, true)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn get_by_id_with_deleted<'e, E>(db: E, id: impl ::std::borrow::Borrow<i64>) -> ::sqlx::Result<Option<Self>>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let id = ::std::borrow::Borrow::<i64>::borrow(&id);
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push("contact");
+                    qb.push(" WHERE ");
+                    qb.push("id");
+                    qb.push(" = ");
+                    qb.push_bind(id);
+
+                    qb.build_query_as()
+                    .fetch_optional(db)
+                    .await
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_no_with_deleted_methods_without_soft_deletion() {
+            assert!(get_by_id_with_deleted_fn(&input(false, false)).is_empty());
+            assert!(list_all_with_deleted_fn(&input(false, false)).is_empty());
+        }
+
+        #[test]
+        fn test_generate_get_variant_by_id_method() {
+            let db_ident = db_ident();
+            let attr = Attr::parse(parse_quote! {
+                #[tiny_orm(table_name = "shape", soft_deletion)]
+                enum Shape {
+                    Circle { id: i64, radius: f64 },
+                    RightSquare { id: i64, #[tiny_orm(json)] sides: Vec<f64> },
+                }
+            })
+            .unwrap();
+            let generated = clean_tokens(get_variant_by_id_fn(&attr));
+
+            let expected = clean_tokens(quote! {
+                pub async fn get_by_id<'e, E>(db: E, id: impl ::std::borrow::Borrow<i64>) -> ::sqlx::Result<Option<Self>>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let id = ::std::borrow::Borrow::<i64>::borrow(&id);
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push("shape");
+                    qb.push(" WHERE deleted_at IS NULL AND ");
+                    qb.push("id");
+                    qb.push(" = ");
+                    qb.push_bind(id);
+
+                    let Some(row) = qb.build().fetch_optional(db).await? else {
+                        return Ok(None);
+                    };
+                    let row = &row;
+                    let kind: String = ::sqlx::Row::try_get(row, "kind")?;
+                    Ok(Some(match kind.as_str() {
+                        "circle" => Self::Circle {
+                            id: ::sqlx::Row::try_get(row, "id")?,
+                            radius: ::sqlx::Row::try_get(row, "radius")?
+                        },
+                        "right_square" => Self::RightSquare {
+                            id: ::sqlx::Row::try_get(row, "id")?,
+                            sides: ::sqlx::Row::try_get::<::sqlx::types::Json<_>, _>(row, "sides")?.0
+                        },
+                        _ => {
+                            return Err(::sqlx::Error::ColumnDecode {
+                                index: "kind".to_string(),
+                                source: format!("no variant matches '{kind}'").into(),
+                            })
+                        }
+                    }))
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_is_deleted_method() {
+            let mut attr = input(false, true);
+            attr.columns.push(Column::new(
+                "deleted_at",
+                parse_quote!(Option<DateTime<Utc>>),
+            ));
+            let generated = clean_tokens(is_deleted_fn(&attr));
+            let expected = clean_tokens(quote! {
+                pub fn is_deleted(&self) -> bool {
+                    self.deleted_at.is_some()
+                }
+            });
+            assert_eq!(generated, expected);
+
+            attr.soft_deletion = None;
+            assert!(is_deleted_fn(&attr).is_empty());
+        }
+
+        #[test]
+        fn test_no_is_deleted_method_without_soft_deletion_field() {
+            assert!(is_deleted_fn(&input(false, true)).is_empty());
+
+            let mut attr = input(false, true);
+            attr.columns
+                .push(Column::new("deleted_at", parse_quote!(DateTime<Utc>)));
+            assert!(is_deleted_fn(&attr).is_empty());
+        }
+
+        #[test]
+        fn test_generate_list_after_method() {
+            let db_ident = db_ident();
+            let generated = clean_tokens(list_after_fn(&input(false, true)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn list_after<'e, E>(db: E, after: &i64, limit: i64) -> ::sqlx::Result<Vec<Self>>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push("contact");
+                    qb.push(" WHERE deleted_at IS NULL AND ");
+                    qb.push("id > ");
+                    qb.push_bind(after);
+                    qb.push(" ORDER BY id LIMIT ");
+                    qb.push_bind(limit);
+
+                    qb.build_query_as()
+                    .fetch_all(db)
+                    .await
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_list_after_method_with_cursor() {
+            let mut attr = input(false, false);
+            attr.cursor = Some(attr.columns[1].clone());
+            let generated = clean_tokens(list_after_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                pub async fn list_after<'e, E>(db: E, after: &DateTime<Utc>, limit: i64)
+            })));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" WHERE ");
+                qb.push("created_at > ");
+                qb.push_bind(after);
+                qb.push(" ORDER BY created_at LIMIT ");
+            })));
+        }
+
+        #[test]
+        fn test_no_list_after_method_without_primary_key_nor_cursor() {
+            let mut attr = input(false, false);
+            attr.primary_key = None;
+            assert!(list_after_fn(&attr).is_empty());
+        }
+
+        #[test]
+        fn test_generate_list_all_method_with_soft_deletion() {
+            let db_ident = db_ident();
+            let generated = clean_tokens(list_all_fn(&input(false, true)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn list_all<'e, E>(db: E) -> ::sqlx::Result<Vec<Self>>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push("contact");
+                    qb.push("WHERE deleted_at IS NULL ");
+
+                    qb.build_query_as()
+                    .fetch_all(db)
+                    .await
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_filter_struct() {
+            let mut attr = input(false, false);
+            attr.parsed_struct.vis = parse_quote!(pub);
+            attr.columns[3]._type = parse_quote!(SetOption<Option<String>>);
+            let generated = clean_tokens(filter_struct(&attr));
+
+            let expected = clean_tokens(quote! {
+                #[doc = "The filter of `Contact::list_matching()`, every field which is set must match"]
+                #[derive(Default)]
+                pub struct ContactFilter {
+                    pub id: Option<i64>,
+                    pub created_at: Option<DateTime<Utc>>,
+                    pub updated_at: Option<DateTime<Utc>>,
+                    pub last_name: Option<String>,
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_list_matching_method() {
+            let db_ident = db_ident();
+            let generated = clean_tokens(list_matching_fn(&input(false, false)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn list_matching<'e, E>(db: E, filter: &ContactFilter) -> ::sqlx::Result<Vec<Self>>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push("contact");
+                    if filter.id.is_some() || filter.created_at.is_some() || filter.updated_at.is_some() || filter.last_name.is_some() {
+                        qb.push(" WHERE ");
+                    }
+                    let mut separated = qb.separated(" AND ");
+                    if let Some(value) = &filter.id {
+                        separated.push("id = ");
+                        separated.push_bind_unseparated(value);
+                    }
+                    if let Some(value) = &filter.created_at {
+                        separated.push("created_at = ");
+                        separated.push_bind_unseparated(value);
+                    }
+                    if let Some(value) = &filter.updated_at {
+                        separated.push("updated_at = ");
+                        separated.push_bind_unseparated(value);
+                    }
+                    if let Some(value) = &filter.last_name {
+                        separated.push("last_name = ");
+                        separated.push_bind_unseparated(value);
+                    }
+
+                    qb.build_query_as()
+                    .fetch_all(db)
+                    .await
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_list_matching_method_with_scope_and_soft_deletion() {
+            let mut attr = input(false, true);
+            attr.parsed_struct.scope = Some(Scope::new("archived = false").unwrap());
+            let generated = clean_tokens(list_matching_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push("contact");
+                qb.push(" WHERE ");
+                let mut separated = qb.separated(" AND ");
+                separated.push("deleted_at IS NULL");
+                separated.push("(archived = false)");
+                if let Some(value) = &filter.id {
+                    separated.push("id = ");
+                    separated.push_bind_unseparated(value);
+                }
+            })));
+        }
+
+        #[test]
+        fn test_generate_get_by_id_method_with_scope_and_soft_deletion() {
+            let mut attr = input(false, true);
+            attr.parsed_struct.scope = Some(Scope::new("archived = false").unwrap());
+            let generated = clean_tokens(get_by_id_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push("contact");
+                qb.push(" WHERE deleted_at IS NULL AND (archived = false) AND ");
+                qb.push("id");
+            })));
+
+            // The `UPDATE` and `DELETE` queries are not scoped
+            let generated = clean_tokens(update_fn(&attr));
+            assert!(!generated.contains("archived"));
+            let generated = clean_tokens(delete_fn(&attr));
+            assert!(!generated.contains("archived"));
+        }
+
+        #[test]
+        fn test_generate_list_all_method_with_scope() {
+            let mut attr = input(false, false);
+            attr.parsed_struct.scope = Some(Scope::new("archived = false").unwrap());
+            let generated = clean_tokens(list_all_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push("contact");
+                qb.push(" WHERE (archived = false) ");
+            })));
+
+            let mut attr = input(false, true);
+            attr.parsed_struct.scope = Some(Scope::new("archived = false").unwrap());
+            let generated = clean_tokens(list_all_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push("contact");
+                qb.push(" WHERE deleted_at IS NULL AND (archived = false) ");
+            })));
+        }
+
+        #[test]
+        fn test_generate_update_method() {
+            let db_ident = db_ident();
+            let generated = clean_tokens(update_fn(&input(false, false)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn update<'e, E>(&self, db: E) -> ::sqlx::Result<()>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
+                    qb.push("contact");
+                    qb.push(" SET ");
+
+                    let mut separated = qb.separated(", ");
+
+                    separated.push("created_at");
+                    separated.push_unseparated(" = ");
+                    separated.push_bind_unseparated(&self.created_at);
+
+                    separated.push("updated_at");
+                    separated.push_unseparated(" = ");
+                    separated.push_bind_unseparated(&self.updated_at);
+
+                    separated.push("last_name");
+                    separated.push_unseparated(" = ");
+                    separated.push_bind_unseparated(&self.last_name);
+
+                    qb.push(" WHERE ");
+                    qb.push("id");
+                    qb.push(" = ");
+                    qb.push_bind(&self.id);
+
+                    qb.build()
+                    .execute(db)
+                    .await
+                    .map(|_|())
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+        #[test]
+        fn test_generate_update_method_with_soft_deletion() {
+            let db_ident = db_ident();
+            let generated = clean_tokens(update_fn(&input(false, true)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn update<'e, E>(&self, db: E) -> ::sqlx::Result<()>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
+                    qb.push("contact");
+                    qb.push(" SET ");
+
+                    let mut separated = qb.separated(", ");
+
+                    separated.push("created_at");
+                    separated.push_unseparated(" = ");
+                    separated.push_bind_unseparated(&self.created_at);
+
+                    separated.push("updated_at");
+                    separated.push_unseparated(" = ");
+                    separated.push_bind_unseparated(&self.updated_at);
+
+                    separated.push("last_name");
+                    separated.push_unseparated(" = ");
+                    separated.push_bind_unseparated(&self.last_name);
+
+                    qb.push(" WHERE deleted_at IS NULL AND ");
+                    qb.push("id");
+                    qb.push(" = ");
+                    qb.push_bind(&self.id);
+
+                    qb.build()
+                    .execute(db)
+                    .await
+                    .map(|_|())
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_bulk_update_fns() {
+            let db_ident = db_ident();
+            let mut attr = input(false, false);
+            attr.columns[3].set_bulk_updatable();
+            let generated = clean_tokens(bulk_update_fns(&attr));
+
+            let expected = clean_tokens(quote! {
+                pub async fn update_last_name_by_ids<'e, E>(db: E, value: &String, ids: &[i64]) -> ::sqlx::Result<u64>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    if ids.is_empty() {
+                        return Ok(0);
+                    }
+                    let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
+                    qb.push("contact");
+                    qb.push(" SET ");
+                    qb.push("last_name");
+                    qb.push(" = ");
+                    qb.push_bind(value);
+
+                    qb.push(" WHERE ");
+                    qb.push("id");
+                    qb.push(" IN (");
+                    let mut separated = qb.separated(", ");
+                    for id in ids {
+                        separated.push_bind(id);
+                    }
+                    separated.push_unseparated(")");
+
+                    qb.build()
+                    .execute(db)
+                    .await
+                    .map(|result| result.rows_affected())
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_bulk_update_fns_with_soft_deletion() {
+            let mut attr = input(false, true);
+            attr.columns[3].set_bulk_updatable();
+            let generated = clean_tokens(bulk_update_fns(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" WHERE deleted_at IS NULL AND ");
+                qb.push("id");
+                qb.push(" IN (");
+            })));
+        }
+
+        #[test]
+        fn test_generate_no_bulk_update_fns_by_default() {
+            let generated = bulk_update_fns(&input(false, false));
+            assert!(generated.is_empty());
+        }
+
+        #[test]
+        fn test_generate_belongs_to_fns() {
+            let db_ident = db_ident();
+            let mut attr = input(false, false);
+            attr.columns
+                .push(Column::new("user_id", parse_quote!(Uuid)));
+            attr.parsed_struct.belongs_to = vec![BelongsTo {
+                target: format_ident!("User"),
+                foreign_key: format_ident!("user_id"),
+            }];
+            let generated = clean_tokens(belongs_to_fns(&attr));
+
+            let expected = clean_tokens(quote! {
+                pub async fn user<'e, E>(&self, db: E) -> ::sqlx::Result<Option<User>>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    User::get_by_id(db, &self.user_id).await
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_belongs_to_fns_with_optional_foreign_key() {
+            let mut attr = input(false, false);
+            attr.columns
+                .push(Column::new("team_id", parse_quote!(Option<i64>)));
+            attr.parsed_struct.belongs_to = vec![BelongsTo {
+                target: format_ident!("SportTeam"),
+                foreign_key: format_ident!("team_id"),
+            }];
+            let generated = clean_tokens(belongs_to_fns(&attr));
+            assert!(generated.contains("pubasyncfnsport_team<'e,E>"));
+            assert!(generated.contains(&clean_tokens(quote! {
+                match &self.team_id {
+                    Some(id) => SportTeam::get_by_id(db, id).await,
+                    None => Ok(None),
+                }
+            })));
+        }
+
+        #[test]
+        fn test_generate_belongs_to_fns_with_custom_error() {
+            let mut attr = input(false, false);
+            attr.columns
+                .push(Column::new("user_id", parse_quote!(Uuid)));
+            attr.parsed_struct.error = Some(parse_quote!(AppError));
+            attr.parsed_struct.belongs_to = vec![BelongsTo {
+                target: format_ident!("User"),
+                foreign_key: format_ident!("user_id"),
+            }];
+            let generated = clean_tokens(belongs_to_fns(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                User::get_by_id(db, &self.user_id).await.map_err(::core::convert::Into::into)
+            })));
+        }
+
+        #[test]
+        fn test_generate_get_by_id_with_fns() {
+            let database_ident = database::db_type().to_database_ident();
+            let mut attr = input(false, false);
+            attr.columns
+                .push(Column::new("user_id", parse_quote!(Uuid)));
+            attr.parsed_struct.belongs_to = vec![BelongsTo {
+                target: format_ident!("User"),
+                foreign_key: format_ident!("user_id"),
+            }];
+            let generated = clean_tokens(get_by_id_with_fns(&attr));
+
+            let expected = clean_tokens(quote! {
+                pub async fn get_by_id_with_user<'c, A>(db: A, id: impl ::std::borrow::Borrow<i64>) -> ::sqlx::Result<Option<(Self, Option<User>)>>
+                where
+                    A: ::sqlx::Acquire<'c, Database = ::sqlx::#database_ident>
+                {
+                    let mut conn = db.acquire().await?;
+
+                    let record = match Self::get_by_id(&mut *conn, id).await? {
+                        Some(record) => record,
+                        None => return Ok(None),
+                    };
+                    let related = record.user(&mut *conn).await?;
+
+                    Ok(Some((record, related)))
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_no_get_by_id_with_fns_with_a_return_object() {
+            let mut attr = input(false, false);
+            attr.parsed_struct.return_object = format_ident!("ContactSummary");
+            attr.parsed_struct.belongs_to = vec![BelongsTo {
+                target: format_ident!("User"),
+                foreign_key: format_ident!("id"),
+            }];
+            assert!(get_by_id_with_fns(&attr).is_empty());
+        }
+
+        #[test]
+        fn test_generate_has_many_fns() {
+            let db_ident = db_ident();
+            let mut attr = input(false, false);
+            attr.parsed_struct.has_many = vec![HasMany::new(
+                format_ident!("TodoItem"),
+                "contact_id",
+                None,
+                None,
+            )];
+            let generated = clean_tokens(has_many_fns(&attr));
+
+            let expected = clean_tokens(quote! {
+                pub async fn todo_items<'e, E>(&self, db: E) -> ::sqlx::Result<Vec<TodoItem>>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push("todo_item");
+                    qb.push(" WHERE ");
+                    qb.push("contact_id");
+                    qb.push(" = ");
+                    qb.push_bind(&self.id);
+
+                    qb.build_query_as()
+                    .fetch_all(db)
+                    .await
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_has_many_fns_with_child_soft_deletion() {
+            // The soft deletion of the parent does not apply to the children
+            let mut attr = input(false, true);
+            attr.parsed_struct.has_many = vec![
+                HasMany::new(
+                    format_ident!("Todo"),
+                    "contact_id",
+                    Some("tasks".to_string()),
+                    Some(SoftDeletion::new("archived_at")),
+                ),
+                HasMany::new(format_ident!("Note"), "contact_id", None, None),
+            ];
+            let generated = clean_tokens(has_many_fns(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push("tasks");
+                qb.push(" WHERE archived_at IS NULL AND ");
+                qb.push("contact_id");
+            })));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push("note");
+                qb.push(" WHERE ");
+                qb.push("contact_id");
+            })));
+        }
+
+        #[test]
+        fn test_generate_has_many_fns_with_child_fn() {
+            let mut attr = input(false, false);
+            attr.parsed_struct.has_many = vec![HasMany {
+                accessor: Some(format_ident!("categories")),
+                ..HasMany::new(format_ident!("Category"), "contact_id", None, None)
+            }];
+            let generated = clean_tokens(has_many_fns(&attr));
+            assert!(generated.contains("pubasyncfncategories<'e,E>"));
+            assert!(!generated.contains("categorys"));
+        }
+
+        #[test]
+        fn test_generate_delete_method_with_cascade_delete() {
+            let mut attr = input(false, true);
+            attr.parsed_struct.database = Some(DbType::Postgres);
+            let mut todos = HasMany::new(
+                format_ident!("Todo"),
+                "contact_id",
+                None,
+                Some(SoftDeletion::default()),
+            );
+            todos.cascade_delete = true;
+            let mut notes = HasMany::new(format_ident!("Note"), "contact_id", None, None);
+            notes.cascade_delete = true;
+            attr.parsed_struct.has_many = vec![
+                todos,
+                notes,
+                HasMany::new(format_ident!("Tag"), "contact_id", None, None),
+            ];
+            let generated = clean_tokens(delete_fn(&attr));
+
+            let expected = clean_tokens(quote! {
+                pub async fn delete<'c, A>(&self, db: A) -> ::sqlx::Result<()>
+                where
+                    A: ::sqlx::Acquire<'c, Database = ::sqlx::Postgres>
+                {
+                    let mut tx = db.begin().await?;
+                    let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
+                    qb.push("todo");
+                    qb.push(" SET deleted_at = NOW() WHERE deleted_at IS NULL AND contact_id = ");
+                    qb.push_bind(&self.id);
+                    qb.build().execute(&mut *tx).await?;
+                    let mut qb = ::sqlx::QueryBuilder::new("DELETE FROM ");
+                    qb.push("note");
+                    qb.push(" WHERE contact_id = ");
+                    qb.push_bind(&self.id);
+                    qb.build().execute(&mut *tx).await?;
+
+                    let result = {
+                        let db = &mut *tx;
+                        let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
+                        qb.push("contact");
+                        qb.push(" SET deleted_at = NOW(), updated_at = NOW() ");
+                        qb.push(" WHERE deleted_at IS NULL AND ");
+                        qb.push("id");
+                        qb.push(" = ");
+                        qb.push_bind(&self.id);
+
+                        qb.build()
+                        .execute(db)
+                        .await
+                        .map(|_| ())
+                    }?;
+                    tx.commit().await?;
+
+                    Ok(result)
+                }
+            });
+            assert_eq!(generated, expected);
+            // The children are deleted before the parent
+            let parent = generated.find("qb.push(\"contact\")").unwrap();
+            assert!(generated.find("qb.push(\"todo\")").unwrap() < parent);
+            assert!(generated.find("qb.push(\"note\")").unwrap() < parent);
+            assert!(!generated.contains("\"tag\""));
+        }
+
+        #[test]
+        fn test_no_tiny_table_impl_with_cascade_delete() {
+            let mut attr = input(false, false);
+            let mut notes = HasMany::new(format_ident!("Note"), "contact_id", None, None);
+            notes.cascade_delete = true;
+            attr.parsed_struct.has_many = vec![notes];
+            let generated = clean_tokens(crate::generate_impl(&attr).unwrap());
+            assert!(!generated.contains("TinyTable"));
+        }
+
+        #[test]
+        fn test_generate_create_method_with_uuid_primary_key() {
+            let db_ident = db_ident();
+            let mut attr = input(false, false);
+            let mut primary_key = Column::new("id", parse_quote!(Uuid));
+            primary_key.set_primary_key();
+            primary_key.set_uuid();
+            attr.columns = vec![
+                primary_key.clone(),
+                Column::new("last_name", parse_quote!(String)),
+            ];
+            attr.primary_key = Some(primary_key);
+            let generated = clean_tokens(create_fn(&attr));
+
+            let expected = clean_tokens(quote! {
+                pub async fn create<'e, E>(&self, db: E) -> ::sqlx::Result<Uuid>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let id = if self.id.is_nil() {
+                        ::tiny_orm::uuid::Uuid::new_v4()
+                    } else {
+                        self.id
+                    };
+                    let mut fields_str = Vec::new();
+                    fields_str.push("id");
+                    fields_str.push("last_name");
+
+                    let mut qb = ::sqlx::QueryBuilder::new("INSERT INTO ");
+                    qb.push("contact");
+                    qb.push(" (");
+                    qb.push(fields_str.join(", "));
+                    qb.push(") VALUES (");
+
+                    let mut separated = qb.separated(", ");
+                    separated.push_bind(&id);
+                    separated.push_bind(&self.last_name);
+                    separated.push_unseparated(")");
+
+                    qb.build().execute(db).await.map(|_| id)
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_create_returning_id_method() {
+            for (db_type, db_ident) in [
+                (DbType::Postgres, format_ident!("PgExecutor")),
+                (DbType::MySQL, format_ident!("MySqlExecutor")),
+                (DbType::Sqlite, format_ident!("SqliteExecutor")),
+            ] {
+                let mut attr = input(true, false);
+                attr.parsed_struct.database = Some(db_type);
+                let generated = clean_tokens(create_returning_id_fn(&attr));
+
+                let expected = clean_tokens(quote! {
+                    pub async fn create_returning_id<'e, E>(&self, db: E) -> ::sqlx::Result<i64>
+                    where
+                        E: ::sqlx::#db_ident<'e>
+                    {
+                        self.create(db).await
+                    }
+                });
+                assert_eq!(generated, expected);
+                assert!(clean_tokens(create_fn(&attr))
+                    .contains(&clean_tokens(quote! { -> ::sqlx::Result<i64> })));
+            }
+        }
+
+        #[test]
+        fn test_no_create_returning_id_method_without_primary_key() {
+            let mut attr = input(false, false);
+            attr.primary_key = None;
+            assert!(create_returning_id_fn(&attr).is_empty());
+        }
+
+        #[test]
+        fn test_generate_save_method() {
+            let db_ident = db_ident();
+            let generated = clean_tokens(save_fn(&input(true, false)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn save<'e, E>(&mut self, db: E) -> ::sqlx::Result<bool>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    if self.id == <i64 as ::core::default::Default>::default() {
+                        let id = self.create(db).await?;
+                        self.id = id;
+                        Ok(true)
+                    } else {
+                        self.update(db).await?;
+                        Ok(false)
+                    }
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_save_method_with_optional_primary_key() {
+            let mut attr = input(false, false);
+            let mut primary_key = Column::new("id", parse_quote!(Option<i64>));
+            primary_key.set_primary_key();
+            attr.columns[0] = primary_key.clone();
+            attr.primary_key = Some(primary_key);
+            let generated = clean_tokens(save_fn(&attr));
+
+            assert!(generated.contains(&clean_tokens(quote! {
+                if self.id.is_none() {
+                    let id = self.create(db).await?;
+                    self.id = Some(id);
+                    Ok(true)
+                }
+            })));
+        }
+
+        #[test]
+        fn test_no_save_method_with_a_provided_primary_key() {
+            assert!(save_fn(&input(false, false)).is_empty());
+        }
+
+        #[test]
+        fn test_generate_upsert_with_outcome_method() {
+            let mut attr = input(false, false);
+            attr.parsed_struct.database = Some(DbType::Postgres);
+            let generated = clean_tokens(upsert_with_outcome_fn(&attr));
+
+            let expected = clean_tokens(quote! {
+                pub async fn upsert_with_outcome<'e, E>(&self, db: E) -> ::sqlx::Result<::tiny_orm::UpsertOutcome<Self>>
+                where
+                    E: ::sqlx::PgExecutor<'e>
+                {
+                    let mut fields_str = Vec::new();
+                    fields_str.push("id");
+                    fields_str.push("created_at");
+                    fields_str.push("updated_at");
+                    fields_str.push("last_name");
+
+                    let mut qb = ::sqlx::QueryBuilder::new("INSERT INTO ");
+                    qb.push("contact");
+                    qb.push(" (");
+                    qb.push(fields_str.join(", "));
+                    qb.push(") VALUES (");
+
+                    let mut separated = qb.separated(", ");
+                    separated.push_bind(&self.id);
+                    separated.push_bind(&self.created_at);
+                    separated.push_bind(&self.updated_at);
+                    separated.push_bind(&self.last_name);
+                    separated.push_unseparated(")");
+
+                    qb.push(" ON CONFLICT (id) DO UPDATE SET ");
+                    let mut separated = qb.separated(", ");
+                    separated.push("id = EXCLUDED.id");
+                    separated.push("created_at = EXCLUDED.created_at");
+                    separated.push("updated_at = EXCLUDED.updated_at");
+                    separated.push("last_name = EXCLUDED.last_name");
+                    qb.push(" RETURNING *, (xmax = 0) AS inserted");
+
+                    let row = qb.build().fetch_one(db).await?;
+                    let inserted: bool = ::sqlx::Row::try_get(&row, "inserted")?;
+                    let record = <Self as ::sqlx::FromRow<_>>::from_row(&row)?;
+                    if inserted {
+                        Ok(::tiny_orm::UpsertOutcome::Inserted(record))
+                    } else {
+                        Ok(::tiny_orm::UpsertOutcome::Updated(record))
+                    }
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_upsert_with_outcome_method_with_explicit_returning() {
+            let mut attr = input(false, false);
+            attr.parsed_struct.database = Some(DbType::Postgres);
+            attr.parsed_struct.returning = Some(Returning::new("id, last_name").unwrap());
+            attr.columns[1].set_db_default();
+            let generated = clean_tokens(upsert_with_outcome_fn(&attr));
+            assert!(!generated.contains("created_at=EXCLUDED.created_at"));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" RETURNING id, last_name, (xmax = 0) AS inserted");
+            })));
+        }
+
+        #[test]
+        fn test_generate_upsert_with_outcome_method_with_partial_conflict_target() {
+            let mut attr = input(true, false);
+            attr.parsed_struct.database = Some(DbType::Postgres);
+            attr.parsed_struct.conflict_target = Some(
+                ConflictTarget::new("last_name")
+                    .unwrap()
+                    .with_predicate("deleted_at IS NULL")
+                    .unwrap(),
+            );
+            let generated = clean_tokens(upsert_with_outcome_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" ON CONFLICT (last_name) WHERE deleted_at IS NULL DO UPDATE SET ");
+                let mut separated = qb.separated(", ");
+                separated.push("last_name = EXCLUDED.last_name");
+                separated.push("created_at = EXCLUDED.created_at");
+                separated.push("updated_at = EXCLUDED.updated_at");
+                qb.push(" RETURNING *, (xmax = 0) AS inserted");
+            })));
+        }
+
+        #[test]
+        fn test_generate_no_upsert_with_outcome_method() {
+            let mut attr = input(true, false);
+            attr.parsed_struct.database = Some(DbType::Postgres);
+            assert!(upsert_with_outcome_fn(&attr).is_empty());
+        }
+
+        #[test]
+        fn test_generate_upsert_with_outcome_method_on_sqlite() {
+            let mut attr = input(false, false);
+            attr.parsed_struct.database = Some(DbType::Sqlite);
+            let generated = clean_tokens(upsert_with_outcome_fn(&attr));
+
+            let expected = clean_tokens(quote! {
+                pub async fn upsert_with_outcome<'c, A>(&self, db: A) -> ::sqlx::Result<::tiny_orm::UpsertOutcome<Self>>
+                where
+                    A: ::sqlx::Acquire<'c, Database = ::sqlx::Sqlite>
+                {
+                    let mut tx = db.begin().await?;
+
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT 1 FROM ");
+                    qb.push("contact");
+                    qb.push(" WHERE id = ");
+                    qb.push_bind(&self.id);
+                    let existed = qb.build().fetch_optional(&mut *tx).await?.is_some();
+
+                    let mut fields_str = Vec::new();
+                    fields_str.push("id");
+                    fields_str.push("created_at");
+                    fields_str.push("updated_at");
+                    fields_str.push("last_name");
+
+                    let mut qb = ::sqlx::QueryBuilder::new("INSERT INTO ");
+                    qb.push("contact");
+                    qb.push(" (");
+                    qb.push(fields_str.join(", "));
+                    qb.push(") VALUES (");
+
+                    let mut separated = qb.separated(", ");
+                    separated.push_bind(&self.id);
+                    separated.push_bind(&self.created_at);
+                    separated.push_bind(&self.updated_at);
+                    separated.push_bind(&self.last_name);
+                    separated.push_unseparated(")");
+
+                    qb.push(" ON CONFLICT (id) DO UPDATE SET ");
+                    let mut separated = qb.separated(", ");
+                    separated.push("id = EXCLUDED.id");
+                    separated.push("created_at = EXCLUDED.created_at");
+                    separated.push("updated_at = EXCLUDED.updated_at");
+                    separated.push("last_name = EXCLUDED.last_name");
+                    qb.push(" RETURNING *");
+                    let record: Self = qb.build_query_as().fetch_one(&mut *tx).await?;
+                    tx.commit().await?;
+
+                    if existed {
+                        Ok(::tiny_orm::UpsertOutcome::Updated(record))
+                    } else {
+                        Ok(::tiny_orm::UpsertOutcome::Inserted(record))
+                    }
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_upsert_with_outcome_method_on_mysql() {
+            let mut attr = input(true, false);
+            attr.parsed_struct.database = Some(DbType::MySQL);
+            attr.parsed_struct.conflict_target = Some(ConflictTarget::new("last_name").unwrap());
+            let generated = clean_tokens(upsert_with_outcome_fn(&attr));
+
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" WHERE last_name = ");
+                qb.push_bind(&self.last_name);
+                qb.push(" FOR UPDATE");
+                let existed = qb.build().fetch_optional(&mut *tx).await?.is_some();
+            })));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" ON DUPLICATE KEY UPDATE ");
+                let mut separated = qb.separated(", ");
+                separated.push("last_name = VALUES(last_name)");
+                separated.push("created_at = VALUES(created_at)");
+                separated.push("updated_at = VALUES(updated_at)");
+                qb.build().execute(&mut *tx).await?;
+
+                let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                qb.push("contact");
+                qb.push(" WHERE last_name = ");
+                qb.push_bind(&self.last_name);
+                let record: Self = qb.build_query_as().fetch_one(&mut *tx).await?;
+            })));
+        }
+
+        #[test]
+        fn test_generate_upsert_with_outcome_method_with_sqlite_predicate() {
+            let mut attr = input(true, false);
+            attr.parsed_struct.database = Some(DbType::Sqlite);
+            attr.parsed_struct.conflict_target = Some(
+                ConflictTarget::new("last_name")
+                    .unwrap()
+                    .with_predicate("deleted_at IS NULL")
+                    .unwrap(),
+            );
+            let generated = clean_tokens(upsert_with_outcome_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" AND (deleted_at IS NULL)");
+            })));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" ON CONFLICT (last_name) WHERE deleted_at IS NULL DO UPDATE SET ");
+            })));
+        }
+
+        #[test]
+        fn test_generate_create_many_method() {
+            let mut attr = input(true, false);
+            attr.parsed_struct.database = Some(DbType::Postgres);
+            let generated = clean_tokens(create_many_fn(&attr));
+
+            let expected = clean_tokens(quote! {
+                pub async fn create_many<'c, A>(db: A, items: &[Self]) -> ::sqlx::Result<Vec<i64>>
+                where
+                    A: ::sqlx::Acquire<'c, Database = ::sqlx::Postgres>
+                {
+                    // Each query stays under the bind parameter limit of the database
+                    const CHUNK_SIZE: usize = 21845usize;
+                    if items.is_empty() {
+                        return Ok(Vec::new());
+                    }
+
+                    let mut tx = db.begin().await?;
+                    let mut ids: Vec<i64> = Vec::with_capacity(items.len());
+                    for chunk in items.chunks(CHUNK_SIZE) {
+                        let mut qb = ::sqlx::QueryBuilder::new("INSERT INTO ");
+                        qb.push("contact");
+                        qb.push(" (created_at, updated_at, last_name) ");
+                        qb.push_values(chunk, |mut separated, item| {
+                            separated.push_bind(&item.created_at);
+                            separated.push_bind(&item.updated_at);
+                            separated.push_bind(&item.last_name);
+                        });
+                        qb.push(" RETURNING id");
+                        ids.extend(qb.build_query_scalar::<i64>().fetch_all(&mut *tx).await?);
+                    }
+                    tx.commit().await?;
+
+                    Ok(ids)
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[cfg(feature = "mysql")]
+        #[test]
+        fn test_generate_create_many_method_computes_the_mysql_keys() {
+            let generated = clean_tokens(create_many_fn(&input(true, false)));
+            assert!(generated.contains(&clean_tokens(quote! {
+                let mut tx = db.begin().await?;
+                let increment: u64 = ::sqlx::query_scalar("SELECT @@auto_increment_increment")
+                    .fetch_one(&mut *tx)
+                    .await?;
+            })));
+            assert!(generated.contains(&clean_tokens(quote! {
+                let result = qb.build().execute(&mut *tx).await?;
+                let first_id = result.last_insert_id();
+                ids.extend((0..result.rows_affected()).map(|index| (first_id + index * increment) as i64));
+            })));
+            assert!(!generated.contains("RETURNING"));
+        }
+
+        #[cfg(feature = "sqlite-no-returning")]
+        #[test]
+        fn test_generate_create_many_method_computes_the_sqlite_keys() {
+            let generated = clean_tokens(create_many_fn(&sqlite_no_returning_input(true)));
+            assert!(generated.contains(&clean_tokens(quote! {
+                let last_id = result.last_insert_rowid();
+                let first_id = last_id - result.rows_affected() as i64 + 1;
+                ids.extend((first_id..=last_id).map(|id| id as i64));
+            })));
+        }
+
+        #[test]
+        fn test_no_create_many_method_without_auto_primary_key() {
+            assert!(create_many_fn(&input(false, false)).is_empty());
+
+            let mut attr = input(true, false);
+            attr.primary_key = None;
+            assert!(create_many_fn(&attr).is_empty());
+
+            let mut attr = input(true, false);
+            attr.parsed_struct.return_nothing = true;
+            assert!(create_many_fn(&attr).is_empty());
+        }
+
+        #[test]
+        fn test_generate_upsert_many_method() {
+            let mut attr = input(false, false);
+            attr.parsed_struct.database = Some(DbType::Postgres);
+            let generated = clean_tokens(upsert_many_fn(&attr));
+
+            let expected = clean_tokens(quote! {
+                pub async fn upsert_many<'c, A>(db: A, items: &[Self]) -> ::sqlx::Result<u64>
+                where
+                    A: ::sqlx::Acquire<'c, Database = ::sqlx::Postgres>
+                {
+                    // Each query stays under the bind parameter limit of the database
+                    const CHUNK_SIZE: usize = 16383usize;
+                    if items.is_empty() {
+                        return Ok(0);
+                    }
+
+                    let mut tx = db.begin().await?;
+                    let mut rows_affected = 0;
+                    for chunk in items.chunks(CHUNK_SIZE) {
+                        let mut qb = ::sqlx::QueryBuilder::new("INSERT INTO ");
+                        qb.push("contact");
+                        qb.push(" (id, created_at, updated_at, last_name) ");
+                        qb.push_values(chunk, |mut separated, item| {
+                            separated.push_bind(&item.id);
+                            separated.push_bind(&item.created_at);
+                            separated.push_bind(&item.updated_at);
+                            separated.push_bind(&item.last_name);
+                        });
+                        qb.push(" ON CONFLICT (id) DO UPDATE SET created_at = EXCLUDED.created_at, updated_at = EXCLUDED.updated_at, last_name = EXCLUDED.last_name");
+                        rows_affected += qb.build().execute(&mut *tx).await?.rows_affected();
+                    }
+                    tx.commit().await?;
+
+                    Ok(rows_affected)
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_upsert_many_method_for_mysql() {
+            let mut attr = input(false, false);
+            attr.parsed_struct.database = Some(DbType::MySQL);
+            let generated = clean_tokens(upsert_many_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                A: ::sqlx::Acquire<'c, Database = ::sqlx::MySql>
+            })));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" ON DUPLICATE KEY UPDATE created_at = VALUES(created_at), updated_at = VALUES(updated_at), last_name = VALUES(last_name)");
+            })));
+        }
+
+        #[test]
+        fn test_generate_upsert_many_method_for_sqlite() {
+            let mut attr = input(false, false);
+            attr.parsed_struct.database = Some(DbType::Sqlite);
+            let generated = clean_tokens(upsert_many_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                A: ::sqlx::Acquire<'c, Database = ::sqlx::Sqlite>
+            })));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" ON CONFLICT (id) DO UPDATE SET created_at = EXCLUDED.created_at, updated_at = EXCLUDED.updated_at, last_name = EXCLUDED.last_name");
+            })));
+        }
+
+        #[test]
+        fn test_generate_upsert_many_method_chunk_size() {
+            for (db_type, chunk_size) in [
+                (DbType::Postgres, 16383usize),
+                (DbType::MySQL, 16383usize),
+                (DbType::Sqlite, 8191usize),
+            ] {
+                let mut attr = input(false, false);
+                attr.parsed_struct.database = Some(db_type);
+                assert!(
+                    clean_tokens(upsert_many_fn(&attr)).contains(&clean_tokens(quote! {
+                        const CHUNK_SIZE: usize = #chunk_size;
+                    }))
+                );
+            }
+
+            let mut attr = input(false, false);
+            attr.parsed_struct.database = Some(DbType::Sqlite);
+            attr.parsed_struct.sqlite_no_returning = true;
+            assert!(
+                clean_tokens(upsert_many_fn(&attr)).contains(&clean_tokens(quote! {
+                    const CHUNK_SIZE: usize = 249usize;
+                }))
+            );
+        }
+
+        #[test]
+        fn test_generate_upsert_many_method_with_conflict_target() {
+            let mut attr = input(true, false);
+            attr.parsed_struct.database = Some(DbType::Postgres);
+            attr.parsed_struct.conflict_target = Some(ConflictTarget::new("last_name").unwrap());
+            let generated = clean_tokens(upsert_many_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" (created_at, updated_at, last_name) ");
+            })));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" ON CONFLICT (last_name) DO UPDATE SET created_at = EXCLUDED.created_at, updated_at = EXCLUDED.updated_at");
+            })));
+        }
+
+        #[test]
+        fn test_generate_no_upsert_many_method() {
+            let mut attr = input(true, false);
+            attr.parsed_struct.database = Some(DbType::Postgres);
+            assert!(upsert_many_fn(&attr).is_empty());
+
+            let mut attr = input(false, false);
+            attr.parsed_struct.skip_defaults = true;
+            assert!(upsert_many_fn(&attr).is_empty());
+        }
+
+        #[test]
+        fn test_generate_existing_ids_method() {
+            let db_ident = db_ident();
+            let generated = clean_tokens(existing_ids_fn(&input(false, true)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn existing_ids<'e, E>(db: E, ids: &[i64]) -> ::sqlx::Result<Vec<i64>>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    if ids.is_empty() {
+                        return Ok(Vec::new());
+                    }
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT id FROM ");
+                    qb.push("contact");
+                    qb.push(" WHERE deleted_at IS NULL AND ");
+                    qb.push("id");
+                    qb.push(" IN (");
+                    let mut separated = qb.separated(", ");
+                    for id in ids {
+                        separated.push_bind(id);
+                    }
+                    separated.push_unseparated(")");
+
+                    qb.build_query_scalar().fetch_all(db).await
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_stream_by_ids_method() {
+            let db_ident = db_ident();
+            let generated = clean_tokens(stream_by_ids_fn(&input(false, false)));
+
+            let expected = clean_tokens(quote! {
+                pub fn stream_by_ids<'e, E>(db: E, ids: Vec<i64>, chunk_size: ::core::num::NonZeroUsize) -> impl ::tiny_orm::futures_util::Stream<Item = ::sqlx::Result<Self>> + 'e
+                where
+                    E: ::sqlx::#db_ident<'e> + Copy + 'e
+                {
+                    let mut chunks = Vec::new();
+                    let mut ids = ids;
+                    while !ids.is_empty() {
+                        let rest = ids.split_off(chunk_size.get().min(ids.len()));
+                        chunks.push(::std::mem::replace(&mut ids, rest));
+                    }
+
+                    let results = ::tiny_orm::futures_util::StreamExt::then(
+                        ::tiny_orm::futures_util::stream::iter(chunks),
+                        move |ids| async move {
+                            let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                            qb.push("contact");
+                            qb.push(" WHERE ");
+                            qb.push("id");
+                            qb.push(" IN (");
+                            let mut separated = qb.separated(", ");
+                            for id in ids {
+                                separated.push_bind(id);
+                            }
+                            separated.push_unseparated(")");
+
+                            qb.build_query_as::<Self>().fetch_all(db).await
+                        },
+                    );
+                    ::tiny_orm::futures_util::StreamExt::flat_map(results, |result| {
+                        let items: Vec<::sqlx::Result<Self> > = match result {
+                            Ok(items) => items.into_iter().map(Ok).collect(),
+                            Err(error) => vec![Err(error)],
+                        };
+                        ::tiny_orm::futures_util::stream::iter(items)
+                    })
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_stream_by_ids_method_with_custom_error() {
+            let mut attr = input(false, false);
+            attr.parsed_struct.error = Some(parse_quote!(AppError));
+            let generated = clean_tokens(stream_by_ids_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                Stream<Item = ::std::result::Result<Self, AppError> > + 'e
+            })));
+            assert!(generated.contains(&clean_tokens(quote! {
+                Err(error) => vec![Err(::core::convert::From::from(error))],
+            })));
+        }
+
+        #[test]
+        fn test_generate_get_by_ids_ordered_method_with_postgres() {
+            let mut attr = input(false, false);
+            attr.parsed_struct.database = Some(DbType::Postgres);
+            let generated = clean_tokens(get_by_ids_ordered_fn(&attr));
+
+            let expected = clean_tokens(quote! {
+                pub async fn get_by_ids_ordered<'e, E>(db: E, ids: &[i64]) -> ::sqlx::Result<Vec<Self>>
+                where
+                    E: ::sqlx::PgExecutor<'e>
+                {
+                    if ids.is_empty() {
+                        return Ok(Vec::new());
+                    }
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push("contact");
+                    qb.push(" WHERE ");
+                    qb.push("id");
+                    qb.push(" = ANY(");
+                    qb.push_bind(ids);
+                    qb.push(") ORDER BY array_position(");
+                    qb.push_bind(ids);
+                    qb.push(", id)");
+
+                    qb.build_query_as().fetch_all(db).await
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_get_by_ids_ordered_method_in_memory() {
+            for db_type in [DbType::Sqlite, DbType::MySQL] {
+                let mut attr = input(false, true);
+                attr.parsed_struct.database = Some(db_type);
+                let generated = clean_tokens(get_by_ids_ordered_fn(&attr));
+
+                assert!(generated.contains(&clean_tokens(quote! {
+                    qb.push(" WHERE deleted_at IS NULL AND ");
+                    qb.push("id");
+                    qb.push(" IN (");
+                })));
+                assert!(generated.contains(&clean_tokens(quote! {
+                    let items: Vec<Self> = qb.build_query_as().fetch_all(db).await?;
+                    let mut items: Vec<Option<Self>> = items.into_iter().map(Some).collect();
+                    Ok(ids
+                        .iter()
+                        .filter_map(|id| {
+                            items
+                                .iter_mut()
+                                .find(|item| item.as_ref().is_some_and(|item| &item.id == id))
+                                .and_then(Option::take)
+                        })
+                        .collect())
+                })));
+            }
+        }
+
+        #[test]
+        fn test_generate_no_get_by_ids_ordered_method_with_custom_return_object() {
+            let mut attr = input(false, false);
+            attr.parsed_struct.return_object = format_ident!("Summary");
+            assert!(get_by_ids_ordered_fn(&attr).is_empty());
+        }
+
+        #[test]
+        fn test_generate_find_one_by_fns() {
+            let db_ident = db_ident();
+            let mut attr = input(false, false);
+            attr.columns[3].set_unique();
+            let generated = clean_tokens(find_one_by_fns(&attr));
+
+            let expected = clean_tokens(quote! {
+                pub async fn find_one_by_last_name<'e, E>(db: E, value: &String) -> ::sqlx::Result<Option<Self>>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push("contact");
+                    qb.push(" WHERE ");
+                    qb.push("last_name");
+                    qb.push(" = ");
+                    qb.push_bind(value);
+                    qb.push(" LIMIT 1");
+
+                    qb.build_query_as()
+                    .fetch_optional(db)
+                    .await
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_find_one_by_fns_with_soft_deletion() {
+            let mut attr = input(false, true);
+            attr.columns[3].set_unique();
+            let generated = clean_tokens(find_one_by_fns(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" WHERE deleted_at IS NULL AND ");
+                qb.push("last_name");
+            })));
+        }
+
+        #[test]
+        fn test_generate_no_find_one_by_fns_by_default() {
+            let generated = find_one_by_fns(&input(false, false));
+            assert!(generated.is_empty());
+        }
+
+        #[test]
+        fn test_generate_delete_method() {
+            let db_ident = db_ident();
+            let generated = clean_tokens(delete_fn(&input(false, false)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn delete<'e, E>(&self, db: E) -> ::sqlx::Result<()>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let mut qb = ::sqlx::QueryBuilder::new("DELETE FROM ");
+                    qb.push("contact");
+                    qb.push(" WHERE ");
+                    qb.push("id");
+                    qb.push(" = ");
+                    qb.push_bind(&self.id);
+
+                    qb.build()
+                    .execute(db)
+                    .await
+                    .map(|_| ())
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+
+        #[cfg(not(feature = "mysql"))]
+        #[test]
+        fn test_generate_delete_method_with_delete_returning() {
+            let db_ident = db_ident();
+            let mut attr = input(false, false);
+            attr.parsed_struct.delete_returning = true;
+            let generated = clean_tokens(delete_fn(&attr));
+
+            let expected = clean_tokens(quote! {
+                pub async fn delete<'e, E>(&self, db: E) -> ::sqlx::Result<Option<Self>>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let mut qb = ::sqlx::QueryBuilder::new("DELETE FROM ");
+                    qb.push("contact");
+                    qb.push(" WHERE ");
+                    qb.push("id");
+                    qb.push(" = ");
+                    qb.push_bind(&self.id);
+
+                    qb.push(" RETURNING * ");
+
+                    qb.build_query_as()
+                    .fetch_optional(db)
+                    .await
+                }
+            });
 
-        #[test]
-        fn test_table_name() {
-            let generated = clean_tokens(get_table_name(&input(false, false)));
-            let expected = clean_tokens(quote! {
-                pub fn table_name<'a>() -> &'a str {
-                    "contact"
-                }
-            });
             assert_eq!(generated, expected);
         }
 
+        #[cfg(feature = "mysql")]
+        #[test]
+        fn test_generate_delete_method_with_delete_returning() {
+            let mut attr = input(false, false);
+            attr.parsed_struct.delete_returning = true;
+            let generated = clean_tokens(delete_fn(&attr));
+
+            assert_eq!(generated, clean_tokens(delete_fn(&input(false, false))));
+        }
+
+        #[cfg(feature = "sqlite")]
+        #[test]
+        fn test_generate_delete_method_with_soft_deletion_and_delete_returning() {
+            let mut attr = input(false, true);
+            attr.parsed_struct.delete_returning = true;
+            let generated = clean_tokens(delete_fn(&attr));
+
+            assert!(generated.contains(&clean_tokens(quote! {
+                let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
+                qb.push("contact");
+                qb.push(" SET deleted_at = DATETIME('now'), updated_at = DATETIME('now') ");
+                qb.push(" WHERE deleted_at IS NULL AND ");
+                qb.push("id");
+                qb.push(" = ");
+                qb.push_bind(&self.id);
+
+                qb.push(" RETURNING * ");
+            })));
+        }
+
         #[cfg(not(feature = "mysql"))]
         #[test]
-        fn test_generate_create_method() {
+        fn test_generate_tiny_table_impl_with_delete_returning() {
+            let mut attr = input(false, false);
+            attr.parsed_struct.delete_returning = true;
+            let generated = clean_tokens(tiny_table_impl(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                async move { Self::delete(self, db).await.map(|_| ()) }
+            })));
+        }
+        #[cfg(feature = "sqlite")]
+        #[test]
+        fn test_generate_delete_method_with_soft_deletion() {
+            let db_ident = db_ident();
+            let generated = clean_tokens(delete_fn(&input(false, true)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn delete<'e, E>(&self, db: E) -> ::sqlx::Result<()>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
+                    qb.push("contact");
+                    qb.push(" SET deleted_at = DATETIME('now'), updated_at = DATETIME('now') ");
+                    qb.push(" WHERE deleted_at IS NULL AND ");
+                    qb.push("id");
+                    qb.push(" = ");
+                    qb.push_bind(&self.id);
+                    qb.build()
+                    .execute(db)
+                    .await
+                    .map(|_| ())
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+        #[cfg(feature = "postgres")]
+        #[test]
+        fn test_generate_delete_method_with_soft_deletion() {
+            let db_ident = db_ident();
+            let generated = clean_tokens(delete_fn(&input(false, true)));
+
+            let expected = clean_tokens(quote! {
+                pub async fn delete<'e, E>(&self, db: E) -> ::sqlx::Result<()>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
+                    qb.push("contact");
+                    qb.push(" SET deleted_at = NOW(), updated_at = NOW() ");
+                    qb.push(" WHERE deleted_at IS NULL AND ");
+                    qb.push("id");
+                    qb.push(" = ");
+                    qb.push_bind(&self.id);
+                    qb.build()
+                    .execute(db)
+                    .await
+                    .map(|_| ())
+                }
+            });
+
+            assert_eq!(generated, expected);
+        }
+        #[cfg(feature = "mysql")]
+        #[test]
+        fn test_generate_delete_method_with_soft_deletion() {
             let db_ident = db_ident();
-            let generated = clean_tokens(create_fn(&input(false, false)));
+            let generated = clean_tokens(delete_fn(&input(false, true)));
 
             let expected = clean_tokens(quote! {
-                pub async fn create<'e, E>(&self, db: E) -> ::sqlx::Result<i64>
+                pub async fn delete<'e, E>(&self, db: E) -> ::sqlx::Result<()>
                 where
                     E: ::sqlx::#db_ident<'e>
                 {
-                    let mut fields_str = Vec::new();
-                    fields_str.push("id");
-                    fields_str.push("created_at");
-                    fields_str.push("updated_at");
-                    fields_str.push("last_name");
-
-                    let mut qb = ::sqlx::QueryBuilder::new("INSERT INTO ");
+                    let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
                     qb.push("contact");
-                    qb.push(" (");
-                    qb.push(fields_str.join(", "));
-                    qb.push(") VALUES (");
-
-                    let mut separated = qb.separated(", ");
-                    separated.push_bind(&self.id);
-                    separated.push_bind(&self.created_at);
-                    separated.push_bind(&self.updated_at);
-                    separated.push_bind(&self.last_name);
-                    separated.push_unseparated(")");
-
-                    qb.push(" RETURNING ");
+                    qb.push(" SET deleted_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP ");
+                    qb.push(" WHERE deleted_at IS NULL AND ");
                     qb.push("id");
-
+                    qb.push(" = ");
+                    qb.push_bind(&self.id);
                     qb.build()
-                    .fetch_one(db)
+                    .execute(db)
                     .await
-                    .map(|row|row.get(0))
+                    .map(|_| ())
                 }
             });
 
             assert_eq!(generated, expected);
         }
+        fn input_with_custom_soft_deletion() -> Attr {
+            let mut attr = input(false, true);
+            attr.soft_deletion = Some(SoftDeletion::new("archived_at"));
+            attr
+        }
 
-        #[cfg(feature = "mysql")]
         #[test]
-        fn test_generate_create_method() {
-            let generated = clean_tokens(create_fn(&input(false, false)));
+        fn test_generate_get_by_id_method_with_custom_soft_deletion_column() {
+            let db_ident = db_ident();
+            let generated = clean_tokens(get_by_id_fn(&input_with_custom_soft_deletion()));
 
             let expected = clean_tokens(quote! {
-                pub async fn create<'e, E>(&self, db: E) -> ::sqlx::Result<i64>
+                pub async fn get_by_id<'e, E>(db: E, id: impl ::std::borrow::Borrow<i64>) -> ::sqlx::Result<Option<Self>>
                 where
-                    E: ::sqlx::MySqlExecutor<'e>
+                    E: ::sqlx::#db_ident<'e>
                 {
-                    let mut fields_str = Vec::new();
-                    fields_str.push("id");
-                    fields_str.push("created_at");
-                    fields_str.push("updated_at");
-                    fields_str.push("last_name");
-
-                    let mut qb = ::sqlx::QueryBuilder::new("INSERT INTO ");
+                    let id = ::std::borrow::Borrow::<i64>::borrow(&id);
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
                     qb.push("contact");
-                    qb.push(" (");
-                    qb.push(fields_str.join(", "));
-                    qb.push(") VALUES (");
-
-                    let mut separated = qb.separated(", ");
-                    separated.push_bind(&self.id);
-                    separated.push_bind(&self.created_at);
-                    separated.push_bind(&self.updated_at);
-                    separated.push_bind(&self.last_name);
-                    separated.push_unseparated(")");
-
-                    qb.build()
-                    .execute(db)
-                    .await?;
+                    qb.push(" WHERE archived_at IS NULL AND ");
+                    qb.push("id");
+                    qb.push(" = ");
+                    qb.push_bind(id);
 
-                    Ok(self.id.clone())
+                    qb.build_query_as()
+                    .fetch_optional(db)
+                    .await
                 }
             });
 
             assert_eq!(generated, expected);
         }
 
-        #[cfg(not(feature = "mysql"))]
         #[test]
-        fn test_generate_create_method_with_auto_primary_key() {
+        fn test_generate_list_all_method_with_custom_soft_deletion_column() {
             let db_ident = db_ident();
-            let generated = clean_tokens(create_fn(&input(true, false)));
+            let generated = clean_tokens(list_all_fn(&input_with_custom_soft_deletion()));
 
             let expected = clean_tokens(quote! {
-                pub async fn create<'e, E>(&self, db: E) -> ::sqlx::Result<i64>
+                pub async fn list_all<'e, E>(db: E) -> ::sqlx::Result<Vec<Self>>
                 where
                     E: ::sqlx::#db_ident<'e>
                 {
-                    let mut fields_str = Vec::new();
-                    fields_str.push("created_at");
-                    fields_str.push("updated_at");
-                    fields_str.push("last_name");
-
-                    let mut qb = ::sqlx::QueryBuilder::new("INSERT INTO ");
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
                     qb.push("contact");
-                    qb.push(" (");
-                    qb.push(fields_str.join(", "));
-                    qb.push(") VALUES (");
-
-                    let mut separated = qb.separated(", ");
-                    separated.push_bind(&self.created_at);
-                    separated.push_bind(&self.updated_at);
-                    separated.push_bind(&self.last_name);
-                    separated.push_unseparated(")");
+                    qb.push(" WHERE archived_at IS NULL ");
 
-                    qb.push(" RETURNING ");
-                    qb.push("id");
-
-                    qb.build()
-                    .fetch_one(db)
+                    qb.build_query_as()
+                    .fetch_all(db)
                     .await
-                    .map(|row|row.get(0))
                 }
             });
 
             assert_eq!(generated, expected);
         }
 
-        #[cfg(feature = "mysql")]
         #[test]
-        fn test_generate_create_method_with_auto_primary_key() {
-            let generated = clean_tokens(create_fn(&input(true, false)));
+        fn test_generate_update_method_with_custom_soft_deletion_column() {
+            let generated = clean_tokens(update_fn(&input_with_custom_soft_deletion()));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" WHERE archived_at IS NULL AND ");
+            })));
+            assert!(!generated.contains("deleted_at"));
+        }
+
+        #[test]
+        fn test_generate_delete_method_with_soft_deletion_without_updated_at() {
+            let mut attr = input(false, true);
+            attr.columns.retain(|column| column.name != "updated_at");
+            let now = match database::db_type() {
+                DbType::Postgres => "NOW()",
+                DbType::MySQL => "CURRENT_TIMESTAMP",
+                DbType::Sqlite => "DATETIME('now')",
+            };
+            let set_str = format!(" SET deleted_at = {now} ");
+
+            let generated = clean_tokens(delete_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(#set_str);
+            })));
+            assert!(!generated.contains("updated_at"));
+        }
+
+        #[test]
+        fn test_generate_touch_method() {
+            for (db_type, db_ident, set_str) in [
+                (
+                    DbType::Postgres,
+                    format_ident!("PgExecutor"),
+                    " SET updated_at = NOW() ",
+                ),
+                (
+                    DbType::MySQL,
+                    format_ident!("MySqlExecutor"),
+                    " SET updated_at = CURRENT_TIMESTAMP ",
+                ),
+                (
+                    DbType::Sqlite,
+                    format_ident!("SqliteExecutor"),
+                    " SET updated_at = DATETIME('now') ",
+                ),
+            ] {
+                let mut attr = input(false, false);
+                attr.parsed_struct.database = Some(db_type);
+                let generated = clean_tokens(touch_fn(&attr));
+
+                let expected = clean_tokens(quote! {
+                    pub async fn touch<'e, E>(&self, db: E) -> ::sqlx::Result<()>
+                    where
+                        E: ::sqlx::#db_ident<'e>
+                    {
+                        let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
+                        qb.push("contact");
+                        qb.push(#set_str);
+                        qb.push(" WHERE ");
+                        qb.push("id");
+                        qb.push(" = ");
+                        qb.push_bind(&self.id);
+
+                        qb.build()
+                        .execute(db)
+                        .await
+                        .map(|_| ())
+                    }
+                });
+                assert_eq!(generated, expected);
+            }
+        }
+
+        #[test]
+        fn test_generate_touch_method_with_soft_deletion() {
+            let generated = clean_tokens(touch_fn(&input(false, true)));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" WHERE deleted_at IS NULL AND ");
+                qb.push("id");
+            })));
+        }
+
+        #[test]
+        fn test_generate_no_touch_method_without_updated_at() {
+            let mut attr = input(false, false);
+            attr.columns.retain(|column| column.ident != "updated_at");
+            assert!(touch_fn(&attr).is_empty());
+        }
+
+        #[test]
+        fn test_generate_delete_all_method() {
+            let db_ident = db_ident();
+            let generated = clean_tokens(delete_all_fn(&input(false, false)));
 
             let expected = clean_tokens(quote! {
-                pub async fn create<'e, E>(&self, db: E) -> ::sqlx::Result<i64>
+                pub async fn delete_all<'e, E>(db: E) -> ::sqlx::Result<u64>
                 where
-                    E: ::sqlx::MySqlExecutor<'e>
+                    E: ::sqlx::#db_ident<'e>
                 {
-                    let mut fields_str = Vec::new();
-                    fields_str.push("created_at");
-                    fields_str.push("updated_at");
-                    fields_str.push("last_name");
-
-                    let mut qb = ::sqlx::QueryBuilder::new("INSERT INTO ");
+                    let mut qb = ::sqlx::QueryBuilder::new("DELETE FROM ");
                     qb.push("contact");
-                    qb.push(" (");
-                    qb.push(fields_str.join(", "));
-                    qb.push(") VALUES (");
-
-                    let mut separated = qb.separated(", ");
-                    separated.push_bind(&self.created_at);
-                    separated.push_bind(&self.updated_at);
-                    separated.push_bind(&self.last_name);
-                    separated.push_unseparated(")");
 
                     qb.build()
                     .execute(db)
                     .await
-                    .map(|result|result.last_insert_id()as_)
+                    .map(|result| result.rows_affected())
                 }
             });
 
@@ -638,227 +6144,458 @@ mod tests {
         }
 
         #[test]
-        fn test_generate_get_by_id_method() {
+        fn test_generate_delete_by_fns() {
             let db_ident = db_ident();
-            let generated = clean_tokens(get_by_id_fn(&input(false, false)));
+            let mut attr = input(false, false);
+            attr.columns[3].set_filterable();
+            let generated = clean_tokens(delete_by_fns(&attr));
 
             let expected = clean_tokens(quote! {
-                pub async fn get_by_id<'e, E>(db: E, id: &i64) -> ::sqlx::Result<Option<Self>>
+                pub async fn delete_by_last_name<'e, E>(db: E, value: &String) -> ::sqlx::Result<u64>
                 where
                     E: ::sqlx::#db_ident<'e>
                 {
-                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    let mut qb = ::sqlx::QueryBuilder::new("DELETE FROM ");
                     qb.push("contact");
                     qb.push(" WHERE ");
-                    qb.push("id");
+                    qb.push("last_name");
                     qb.push(" = ");
-                    qb.push_bind(id);
+                    qb.push_bind(value);
 
-                    qb.build_query_as()
-                    .fetch_optional(db)
+                    qb.build()
+                    .execute(db)
                     .await
+                    .map(|result| result.rows_affected())
                 }
             });
-
             assert_eq!(generated, expected);
         }
 
         #[test]
-        fn test_generate_get_by_id_method_with_soft_deletion() {
+        fn test_generate_delete_by_fns_with_soft_deletion() {
+            let mut attr = input(false, true);
+            attr.columns[3].set_filterable();
+            let generated = clean_tokens(delete_by_fns(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
+            })));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" WHERE deleted_at IS NULL AND ");
+                qb.push("last_name");
+            })));
+        }
+
+        #[test]
+        fn test_generate_no_delete_by_fns_by_default() {
+            assert!(delete_by_fns(&input(false, false)).is_empty());
+        }
+
+        #[test]
+        fn test_custom_error_fns() {
             let db_ident = db_ident();
-            let generated = clean_tokens(get_by_id_fn(&input(false, true)));
+            let mut attr = input(true, false);
+            attr.parsed_struct.error = Some(parse_quote!(crate::AppError));
+            let generated = clean_tokens(custom_error_fns(&attr, delete_all_fn(&attr), false));
 
             let expected = clean_tokens(quote! {
-                pub async fn get_by_id<'e, E>(db: E, id: &i64) -> ::sqlx::Result<Option<Self>>
+                pub async fn delete_all<'e, E>(db: E) -> ::std::result::Result<u64, crate::AppError>
                 where
                     E: ::sqlx::#db_ident<'e>
                 {
-                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
-                    qb.push("contact");
-                    qb.push(" WHERE deleted_at IS NULL AND ");
-                    qb.push("id");
-                    qb.push(" = ");
-                    qb.push_bind(id);
-
-                    qb.build_query_as()
-                    .fetch_optional(db)
-                    .await
+                    let result: ::sqlx::Result<u64> = async move {
+                        let mut qb = ::sqlx::QueryBuilder::new("DELETE FROM ");
+                        qb.push("contact");
+
+                        qb.build()
+                        .execute(db)
+                        .await
+                        .map(|result| result.rows_affected())
+                    }.await;
+                    result.map_err(::core::convert::From::from)
                 }
             });
+            assert_eq!(generated, expected);
 
+            // The delegating methods only change their signature
+            let generated =
+                clean_tokens(custom_error_fns(&attr, create_returning_id_fn(&attr), true));
+            let expected = clean_tokens(quote! {
+                pub async fn create_returning_id<'e, E>(&self, db: E) -> ::std::result::Result<i64, crate::AppError>
+                where
+                    E: ::sqlx::#db_ident<'e>
+                {
+                    self.create(db).await
+                }
+            });
             assert_eq!(generated, expected);
         }
 
         #[test]
-        fn test_generate_list_all_method() {
+        fn test_custom_error_fns_without_error() {
+            let attr = input(false, false);
+            assert_eq!(
+                clean_tokens(custom_error_fns(&attr, delete_all_fn(&attr), false)),
+                clean_tokens(delete_all_fn(&attr))
+            );
+        }
+
+        #[test]
+        fn test_must_use_fns() {
             let db_ident = db_ident();
-            let generated = clean_tokens(list_all_fn(&input(false, false)));
+            let attr = input(false, false);
+            let columns_fn = get_columns(&attr);
+            let delete_all_fn = delete_all_fn(&attr);
+            let generated = clean_tokens(must_use_fns(
+                &attr,
+                quote! {
+                    #columns_fn
+                    #delete_all_fn
+                },
+            ));
 
             let expected = clean_tokens(quote! {
-                pub async fn list_all<'e, E>(db: E) -> ::sqlx::Result<Vec<Self>>
+                pub const COLUMNS: &'static [&'static str] = &["id", "created_at", "updated_at", "last_name"];
+
+                pub fn columns() -> &'static [&'static str] {
+                    Self::COLUMNS
+                }
+
+                #[must_use = "database result should be checked"]
+                pub async fn delete_all<'e, E>(db: E) -> ::sqlx::Result<u64>
                 where
                     E: ::sqlx::#db_ident<'e>
                 {
-                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    let mut qb = ::sqlx::QueryBuilder::new("DELETE FROM ");
                     qb.push("contact");
 
-                    qb.build_query_as()
-                    .fetch_all(db)
+                    qb.build()
+                    .execute(db)
                     .await
+                    .map(|result| result.rows_affected())
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_instrument_fns() {
+            let db_ident = db_ident();
+            let attr = input(false, false);
+            let columns_fn = get_columns(&attr);
+            let delete_all_fn = delete_all_fn(&attr);
+            let generated = clean_tokens(instrument_fns(
+                &attr,
+                quote! {
+                    impl Contact {
+                        #columns_fn
+                        #delete_all_fn
+                    }
+                },
+            ));
+
+            let expected = clean_tokens(quote! {
+                impl Contact {
+                    pub const COLUMNS: &'static [&'static str] = &["id", "created_at", "updated_at", "last_name"];
+
+                    pub fn columns() -> &'static [&'static str] {
+                        Self::COLUMNS
+                    }
+
+                    pub async fn delete_all<'e, E>(db: E) -> ::sqlx::Result<u64>
+                    where
+                        E: ::sqlx::#db_ident<'e>
+                    {
+                        let span = ::tiny_orm::tracing::debug_span!("tiny_orm.delete_all", table = "contact");
+                        ::tiny_orm::tracing::Instrument::instrument(async move {
+                            let mut qb = ::sqlx::QueryBuilder::new("DELETE FROM ");
+                            qb.push("contact");
+
+                            qb.build()
+                            .execute(db)
+                            .await
+                            .map(|result| result.rows_affected())
+                        }, span).await
+                    }
                 }
             });
 
             assert_eq!(generated, expected);
         }
+
         #[test]
-        fn test_generate_list_all_method_with_soft_deletion() {
+        fn test_generate_delete_all_method_with_soft_deletion() {
             let db_ident = db_ident();
-            let generated = clean_tokens(list_all_fn(&input(false, true)));
+            let now = match database::db_type() {
+                DbType::Postgres => "NOW()",
+                DbType::MySQL => "CURRENT_TIMESTAMP",
+                DbType::Sqlite => "DATETIME('now')",
+            };
+            let set_str = format!(" SET deleted_at = {now}, updated_at = {now} ");
+            let generated = clean_tokens(delete_all_fn(&input(false, true)));
 
             let expected = clean_tokens(quote! {
-                pub async fn list_all<'e, E>(db: E) -> ::sqlx::Result<Vec<Self>>
+                pub async fn delete_all<'e, E>(db: E) -> ::sqlx::Result<u64>
                 where
                     E: ::sqlx::#db_ident<'e>
                 {
-                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
                     qb.push("contact");
-                    qb.push("WHERE deleted_at IS NULL ");
+                    qb.push(#set_str);
+                    qb.push(" WHERE deleted_at IS NULL ");
 
-                    qb.build_query_as()
-                    .fetch_all(db)
+                    qb.build()
+                    .execute(db)
                     .await
+                    .map(|result| result.rows_affected())
                 }
             });
 
-            assert_eq!(generated, expected);
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_delete_method_with_custom_soft_delete_now() {
+            let mut attr = input(false, true);
+            attr.soft_deletion
+                .as_mut()
+                .unwrap()
+                .set_now("clock_timestamp()")
+                .unwrap();
+
+            let generated = clean_tokens(delete_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" SET deleted_at = clock_timestamp(), updated_at = clock_timestamp() ");
+            })));
+
+            let generated = clean_tokens(delete_all_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" SET deleted_at = clock_timestamp(), updated_at = clock_timestamp() ");
+            })));
+        }
+
+        #[test]
+        fn test_generate_delete_method_with_soft_deletion_and_string_updated_at() {
+            let mut attr = input(false, true);
+            attr.columns[2] = Column::new("updated_at", parse_quote!(String));
+            let now = match database::db_type() {
+                DbType::Postgres => "NOW()",
+                DbType::MySQL => "CURRENT_TIMESTAMP",
+                DbType::Sqlite => "DATETIME('now')",
+            };
+            let set_str = format!(" SET deleted_at = {now} ");
+
+            let generated = clean_tokens(delete_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(#set_str);
+            })));
+            assert!(!generated.contains("updated_at"));
+        }
+
+        /// The name of a renamed column in the SQL of the backend
+        fn sql_name(name: &str) -> String {
+            quote_identifier(name, &database::db_type())
+        }
+
+        fn renamed_input() -> Attr {
+            let mut attr = input(false, true);
+            for column in attr.columns.iter_mut() {
+                column.set_name(&RenameAll::Camel.apply(&column.name));
+            }
+            attr.columns[0].set_name("contact_id");
+            attr.primary_key = Some(attr.columns[0].clone());
+            attr
+        }
+
+        #[test]
+        fn test_generate_create_method_with_renamed_columns() {
+            let (created_at, updated_at, last_name) = (
+                sql_name("createdAt"),
+                sql_name("updatedAt"),
+                sql_name("lastName"),
+            );
+            let generated = clean_tokens(create_fn(&renamed_input()));
+            let fields_str = format!(
+                "{:?}",
+                format!("(contact_id,{created_at},{updated_at},{last_name})")
+            );
+            assert!(generated.contains(fields_str.trim_matches('"')));
+            assert!(generated.contains(".bind(&self.created_at)"));
+
+            let generated = clean_tokens(create_if_not_exists_fn(&renamed_input()));
+            assert!(generated.contains(&clean_tokens(quote! {
+                fields_str.push("contact_id");
+                fields_str.push(#created_at);
+                fields_str.push(#updated_at);
+                fields_str.push(#last_name);
+            })));
+            assert!(generated.contains(&clean_tokens(quote! {
+                separated.push_bind(&self.created_at);
+            })));
+        }
+
+        #[test]
+        fn test_generate_update_method_with_renamed_columns() {
+            let last_name = sql_name("lastName");
+            let generated = clean_tokens(update_fn(&renamed_input()));
+            assert!(generated.contains(&clean_tokens(quote! {
+                separated.push(#last_name);
+                separated.push_unseparated(" = ");
+                separated.push_bind_unseparated(&self.last_name);
+            })));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push("contact_id");
+                qb.push(" = ");
+                qb.push_bind(&self.id);
+            })));
+        }
+
+        #[cfg(feature = "postgres")]
+        #[test]
+        fn test_generate_renamed_columns_are_quoted_for_postgres() {
+            // Postgres lowercases the names which are not quoted, so `createdAt` would match `createdat`
+            let generated = create_fn(&renamed_input()).to_string();
+            assert!(generated.contains(
+                r#"INSERT INTO contact (contact_id, \"createdAt\", \"updatedAt\", \"lastName\") VALUES ($1, $2, $3, $4) RETURNING contact_id"#
+            ));
+
+            let generated = clean_tokens(update_fn(&renamed_input()));
+            assert!(generated.contains(&clean_tokens(quote! {
+                separated.push("\"lastName\"");
+                separated.push_unseparated(" = ");
+            })));
+
+            let generated = clean_tokens(get_by_fn(&renamed_input()));
+            assert!(generated.contains(&clean_tokens(quote! {
+                const SQL_COLUMNS: &[&str] = &["contact_id", "\"createdAt\"", "\"updatedAt\"", "\"lastName\""];
+            })));
+        }
+
+        #[test]
+        fn test_generate_kebab_case_columns_are_quoted() {
+            let mut attr = input(false, false);
+            for column in attr.columns.iter_mut() {
+                column.set_name(&RenameAll::Kebab.apply(&column.name));
+            }
+            attr.primary_key = Some(attr.columns[0].clone());
+            let last_name = sql_name("last-name");
+            assert_ne!(last_name, "last-name");
+
+            let generated = clean_tokens(update_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                separated.push(#last_name);
+                separated.push_unseparated(" = ");
+                separated.push_bind_unseparated(&self.last_name);
+            })));
+
+            let generated = clean_tokens(create_fn(&attr));
+            let fields_str = format!(
+                "{:?}",
+                format!(
+                    "(id,{},{},{})",
+                    sql_name("created-at"),
+                    sql_name("updated-at"),
+                    last_name
+                )
+            );
+            assert!(generated.contains(fields_str.trim_matches('"')));
+
+            // The row is still read by the name of the column, which is not quoted
+            let generated = clean_tokens(from_row_impl(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                last_name: ::sqlx::Row::try_get(row, "last-name")?
+            })));
         }
 
         #[test]
-        fn test_generate_update_method() {
+        fn test_generate_delete_method_with_renamed_updated_at() {
+            let now = match database::db_type() {
+                DbType::Postgres => "NOW()",
+                DbType::MySQL => "CURRENT_TIMESTAMP",
+                DbType::Sqlite => "DATETIME('now')",
+            };
+            let set_str = format!(
+                " SET deleted_at = {now}, {} = {now} ",
+                sql_name("updatedAt")
+            );
+
+            let generated = clean_tokens(delete_fn(&renamed_input()));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(#set_str);
+            })));
+        }
+
+        #[cfg(feature = "sqlite")]
+        #[test]
+        fn test_generate_delete_method_with_custom_soft_deletion_column() {
             let db_ident = db_ident();
-            let generated = clean_tokens(update_fn(&input(false, false)));
+            let generated = clean_tokens(delete_fn(&input_with_custom_soft_deletion()));
 
             let expected = clean_tokens(quote! {
-                pub async fn update<'e, E>(&self, db: E) -> ::sqlx::Result<()>
+                pub async fn delete<'e, E>(&self, db: E) -> ::sqlx::Result<()>
                 where
                     E: ::sqlx::#db_ident<'e>
                 {
                     let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
                     qb.push("contact");
-                    qb.push(" SET ");
-
-                    let mut first = true;
-
-                    if !first {
-                        qb.push(",");
-                    }
-                    qb.push("created_at");
-                    qb.push(" = ");
-                    qb.push_bind(&self.created_at);
-                    first = false;
-
-                    if !first {
-                        qb.push(",");
-                    }
-                    qb.push("updated_at");
-                    qb.push(" = ");
-                    qb.push_bind(&self.updated_at);
-                    first = false;
-
-                    if !first {
-                        qb.push(",");
-                    }
-                    qb.push("last_name");
-                    qb.push(" = ");
-                    qb.push_bind(&self.last_name);
-                    first = false;
-
-                    qb.push(" WHERE ");
+                    qb.push(" SET archived_at = DATETIME('now'), updated_at = DATETIME('now') ");
+                    qb.push(" WHERE archived_at IS NULL AND ");
                     qb.push("id");
                     qb.push(" = ");
                     qb.push_bind(&self.id);
-
                     qb.build()
                     .execute(db)
                     .await
-                    .map(|_|())
+                    .map(|_| ())
                 }
             });
 
             assert_eq!(generated, expected);
         }
+        #[cfg(feature = "postgres")]
         #[test]
-        fn test_generate_update_method_with_soft_deletion() {
+        fn test_generate_delete_method_with_custom_soft_deletion_column() {
             let db_ident = db_ident();
-            let generated = clean_tokens(update_fn(&input(false, true)));
+            let generated = clean_tokens(delete_fn(&input_with_custom_soft_deletion()));
 
             let expected = clean_tokens(quote! {
-                pub async fn update<'e, E>(&self, db: E) -> ::sqlx::Result<()>
+                pub async fn delete<'e, E>(&self, db: E) -> ::sqlx::Result<()>
                 where
                     E: ::sqlx::#db_ident<'e>
                 {
                     let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
                     qb.push("contact");
-                    qb.push(" SET ");
-
-                    let mut first = true;
-
-                    if !first {
-                        qb.push(",");
-                    }
-                    qb.push("created_at");
-                    qb.push(" = ");
-                    qb.push_bind(&self.created_at);
-                    first = false;
-
-                    if !first {
-                        qb.push(",");
-                    }
-                    qb.push("updated_at");
-                    qb.push(" = ");
-                    qb.push_bind(&self.updated_at);
-                    first = false;
-
-                    if !first {
-                        qb.push(",");
-                    }
-                    qb.push("last_name");
-                    qb.push(" = ");
-                    qb.push_bind(&self.last_name);
-                    first = false;
-
-                    qb.push(" WHERE deleted_at IS NULL AND ");
+                    qb.push(" SET archived_at = NOW(), updated_at = NOW() ");
+                    qb.push(" WHERE archived_at IS NULL AND ");
                     qb.push("id");
                     qb.push(" = ");
                     qb.push_bind(&self.id);
-
                     qb.build()
                     .execute(db)
                     .await
-                    .map(|_|())
+                    .map(|_| ())
                 }
             });
 
             assert_eq!(generated, expected);
         }
-
+        #[cfg(feature = "mysql")]
         #[test]
-        fn test_generate_delete_method() {
+        fn test_generate_delete_method_with_custom_soft_deletion_column() {
             let db_ident = db_ident();
-            let generated = clean_tokens(delete_fn(&input(false, false)));
+            let generated = clean_tokens(delete_fn(&input_with_custom_soft_deletion()));
 
             let expected = clean_tokens(quote! {
                 pub async fn delete<'e, E>(&self, db: E) -> ::sqlx::Result<()>
                 where
                     E: ::sqlx::#db_ident<'e>
                 {
-                    let mut qb = ::sqlx::QueryBuilder::new("DELETE FROM ");
+                    let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
                     qb.push("contact");
-                    qb.push(" WHERE ");
+                    qb.push(" SET archived_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP ");
+                    qb.push(" WHERE archived_at IS NULL AND ");
                     qb.push("id");
                     qb.push(" = ");
                     qb.push_bind(&self.id);
-
                     qb.build()
                     .execute(db)
                     .await
@@ -868,164 +6605,895 @@ mod tests {
 
             assert_eq!(generated, expected);
         }
-        #[cfg(feature = "sqlite")]
+        fn input_with_boolean_soft_deletion() -> Attr {
+            let mut attr = input(false, true);
+            attr.soft_deletion = Some(SoftDeletion::new_bool("is_deleted"));
+            attr.columns
+                .push(Column::new("is_deleted", parse_quote!(bool)));
+            attr
+        }
+
         #[test]
-        fn test_generate_delete_method_with_soft_deletion() {
+        fn test_generate_get_by_id_method_with_boolean_soft_deletion() {
             let db_ident = db_ident();
-            let generated = clean_tokens(delete_fn(&input(false, true)));
+            let generated = clean_tokens(get_by_id_fn(&input_with_boolean_soft_deletion()));
 
             let expected = clean_tokens(quote! {
-                pub async fn delete<'e, E>(&self, db: E) -> ::sqlx::Result<()>
+                pub async fn get_by_id<'e, E>(db: E, id: impl ::std::borrow::Borrow<i64>) -> ::sqlx::Result<Option<Self>>
                 where
                     E: ::sqlx::#db_ident<'e>
                 {
-                    let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
+                    let id = ::std::borrow::Borrow::<i64>::borrow(&id);
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
                     qb.push("contact");
-                    qb.push(" SET deleted_at = DATETIME('now') ");
-                    qb.push(" WHERE deleted_at IS NULL AND ");
+                    qb.push(" WHERE is_deleted = false AND ");
                     qb.push("id");
                     qb.push(" = ");
-                    qb.push_bind(&self.id);
-                    qb.build()
-                    .execute(db)
+                    qb.push_bind(id);
+
+                    qb.build_query_as()
+                    .fetch_optional(db)
                     .await
-                    .map(|_| ())
                 }
             });
 
             assert_eq!(generated, expected);
         }
-        #[cfg(feature = "postgres")]
+
         #[test]
-        fn test_generate_delete_method_with_soft_deletion() {
-            let db_ident = db_ident();
-            let generated = clean_tokens(delete_fn(&input(false, true)));
+        fn test_generate_list_all_method_with_boolean_soft_deletion() {
+            let generated = clean_tokens(list_all_fn(&input_with_boolean_soft_deletion()));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" WHERE is_deleted = false ");
+            })));
+            assert!(!generated.contains("ISNULL"));
+        }
+
+        #[test]
+        fn test_generate_update_method_with_boolean_soft_deletion() {
+            let generated = clean_tokens(update_fn(&input_with_boolean_soft_deletion()));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" WHERE is_deleted = false AND ");
+            })));
+        }
 
+        #[test]
+        fn test_generate_is_deleted_method_with_boolean_soft_deletion() {
+            let generated = clean_tokens(is_deleted_fn(&input_with_boolean_soft_deletion()));
             let expected = clean_tokens(quote! {
-                pub async fn delete<'e, E>(&self, db: E) -> ::sqlx::Result<()>
-                where
-                    E: ::sqlx::#db_ident<'e>
-                {
+                pub fn is_deleted(&self) -> bool {
+                    self.is_deleted
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_delete_method_with_boolean_soft_deletion() {
+            for (db_type, now) in [
+                (DbType::Postgres, "NOW()"),
+                (DbType::MySQL, "CURRENT_TIMESTAMP"),
+                (DbType::Sqlite, "DATETIME('now')"),
+            ] {
+                let mut attr = input_with_boolean_soft_deletion();
+                attr.parsed_struct.database = Some(db_type);
+                let set_str = format!(" SET is_deleted = true, updated_at = {now} ");
+
+                let generated = clean_tokens(delete_fn(&attr));
+                assert!(generated.contains(&clean_tokens(quote! {
                     let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
                     qb.push("contact");
-                    qb.push(" SET deleted_at = NOW() ");
-                    qb.push(" WHERE deleted_at IS NULL AND ");
+                    qb.push(#set_str);
+                    qb.push(" WHERE is_deleted = false AND ");
+                })));
+            }
+
+            // Without `updated_at`, no timestamp is needed at all
+            let mut attr = input_with_boolean_soft_deletion();
+            attr.columns.retain(|column| column.ident != "updated_at");
+            let generated = clean_tokens(delete_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" SET is_deleted = true ");
+            })));
+        }
+
+        #[test]
+        fn test_generate_delete_all_method_with_boolean_soft_deletion() {
+            let generated = clean_tokens(delete_all_fn(&input_with_boolean_soft_deletion()));
+            assert!(generated.contains("SETis_deleted=true"));
+            assert!(generated.contains("WHEREis_deleted=false"));
+        }
+    }
+
+    mod custom {
+        use super::*;
+        use crate::attr::Attr;
+        use crate::types::*;
+        use syn::parse_quote;
+
+        fn newtype_pk_input(auto_increment: bool) -> Attr {
+            let parsed_struct = ParsedStruct::new(&format_ident!("Todo"), None, None);
+            let mut primary_key = Column::new("id", parse_quote!(TodoId));
+            primary_key.set_primary_key();
+            if auto_increment {
+                primary_key.set_auto_increment();
+            }
+            Attr {
+                parsed_struct,
+                primary_key: Some(primary_key.clone()),
+                lookup_key: None,
+                cursor: None,
+                columns: vec![
+                    primary_key,
+                    Column::new("description", parse_quote!(String)),
+                ],
+                operations: Operation::all(),
+                soft_deletion: None,
+            }
+        }
+
+        #[test]
+        fn test_newtype_primary_key_get_by_id() {
+            let db_ident = db_ident();
+            let generated = clean_tokens(get_by_id_fn(&newtype_pk_input(false)));
+            assert!(generated.starts_with(&clean_tokens(quote! {
+                pub async fn get_by_id<'e, E>(db: E, id: impl ::std::borrow::Borrow<TodoId>) -> ::sqlx::Result<Option<Self>>
+                where
+                    E: ::sqlx::#db_ident<'e>
+            })));
+        }
+
+        #[cfg(not(feature = "mysql"))]
+        #[test]
+        fn test_newtype_primary_key_create() {
+            let generated = clean_tokens(create_fn(&newtype_pk_input(true)));
+            assert!(generated.contains(&clean_tokens(quote! {
+                -> ::sqlx::Result<TodoId>
+            })));
+            assert!(generated.contains(&clean_tokens(quote! {
+                .map(|row| ::sqlx::Row::get(&row, 0))
+            })));
+        }
+
+        #[cfg(not(feature = "mysql"))]
+        #[test]
+        fn test_newtype_primary_key_is_never_cloned() {
+            for auto_increment in [true, false] {
+                let generated =
+                    clean_tokens(crate::generate_impl(&newtype_pk_input(auto_increment)).unwrap());
+                assert!(!generated.contains(".clone()"));
+            }
+        }
+
+        fn optional_pk_input() -> Attr {
+            let parsed_struct = ParsedStruct::new(&format_ident!("Todo"), None, None);
+            let mut primary_key = Column::new("id", parse_quote!(Option<Uuid>));
+            primary_key.set_primary_key();
+            Attr {
+                parsed_struct,
+                primary_key: Some(primary_key.clone()),
+                lookup_key: None,
+                cursor: None,
+                columns: vec![
+                    primary_key,
+                    Column::new("description", parse_quote!(String)),
+                ],
+                operations: Operation::all(),
+                soft_deletion: None,
+            }
+        }
+
+        #[test]
+        fn test_optional_primary_key_lookups_use_the_inner_type() {
+            let generated = clean_tokens(get_by_id_fn(&optional_pk_input()));
+            assert!(generated.contains(&clean_tokens(quote! {
+                id: impl ::std::borrow::Borrow<Uuid>
+            })));
+
+            let generated = clean_tokens(tiny_table_impl(&optional_pk_input()));
+            assert!(generated.contains(&clean_tokens(quote! {
+                type PrimaryKey = Uuid;
+            })));
+        }
+
+        #[test]
+        fn test_optional_primary_key_create() {
+            let generated = clean_tokens(create_fn(&optional_pk_input()));
+            assert!(generated.contains(&clean_tokens(quote! {
+                if id_value.is_some() {
+                    fields_str.push("id");
+                }
+                fields_str.push("description");
+            })));
+            assert!(generated.contains(&clean_tokens(quote! {
+                if let Some(v) = id_value {
+                    separated.push_bind(v);
+                }
+                separated.push_bind(&self.description);
+            })));
+            assert!(generated.contains(&clean_tokens(quote! {
+                -> ::sqlx::Result<Uuid>
+            })));
+        }
+
+        #[test]
+        fn test_optional_primary_key_update_and_delete() {
+            let expected = clean_tokens(quote! {
+                match &self.id {
+                    Some(id) => qb.push_bind(id),
+                    None => return Err(::sqlx::Error::RowNotFound),
+                };
+            });
+            for generated in [
+                update_fn(&optional_pk_input()),
+                delete_fn(&optional_pk_input()),
+            ] {
+                assert!(clean_tokens(generated).contains(&expected));
+            }
+        }
+
+        fn set_option_input() -> Attr {
+            let parsed_struct = ParsedStruct::new(&format_ident!("UpdateTodo"), None, None);
+            let mut primary_key = Column::new("id", parse_quote!(i64));
+            primary_key.set_primary_key();
+            Attr {
+                parsed_struct,
+                primary_key: Some(primary_key.clone()),
+                lookup_key: None,
+                cursor: None,
+                columns: vec![
+                    primary_key,
+                    Column::new("description", parse_quote!(SetOption<String>)),
+                    Column::new("done", parse_quote!(tiny_orm::SetOption<bool>)),
+                ],
+                operations: vec![Operation::Update],
+                soft_deletion: None,
+            }
+        }
+
+        #[test]
+        fn test_generate_set_option_builder_fns() {
+            let generated = clean_tokens(set_option_builder_fns(&set_option_input()));
+            let expected = clean_tokens(quote! {
+                pub fn new(id: i64) -> Self {
+                    Self {
+                        id,
+                        description: ::tiny_orm::SetOption::NotSet,
+                        done: ::tiny_orm::SetOption::NotSet
+                    }
+                }
+                pub fn with_description(mut self, value: impl Into<String>) -> Self {
+                    self.description = ::tiny_orm::SetOption::Set(value.into());
+                    self
+                }
+                pub fn with_done(mut self, value: impl Into<bool>) -> Self {
+                    self.done = ::tiny_orm::SetOption::Set(value.into());
+                    self
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_generate_set_option_builder_fns_with_skipped_fields() {
+            let mut attr = set_option_input();
+            attr.parsed_struct.skipped_fields = vec![format_ident!("cache")];
+            let generated = clean_tokens(set_option_builder_fns(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                Self {
+                    id,
+                    description: ::tiny_orm::SetOption::NotSet,
+                    done: ::tiny_orm::SetOption::NotSet,
+                    cache: ::core::default::Default::default()
+                }
+            })));
+        }
+
+        #[test]
+        fn test_generate_set_option_builder_fns_without_new() {
+            let mut attr = set_option_input();
+            attr.columns
+                .push(Column::new("updated_at", parse_quote!(DateTime<Utc>)));
+            let generated = clean_tokens(set_option_builder_fns(&attr));
+            assert!(!generated.contains("fnnew"));
+            assert!(generated.contains("fnwith_description"));
+        }
+
+        #[test]
+        fn test_generate_set_option_builder_fns_without_set_option_columns() {
+            let generated = set_option_builder_fns(&newtype_pk_input(false));
+            assert!(generated.is_empty());
+        }
+
+        #[test]
+        fn test_update_without_any_set_option_set() {
+            let mut attr = set_option_input();
+            attr.parsed_struct.database = Some(DbType::Sqlite);
+            let generated = clean_tokens(update_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                if !self.description.is_set() && !self.done.is_set() {
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
+                    qb.push("todo");
+                    qb.push(" WHERE ");
                     qb.push("id");
                     qb.push(" = ");
                     qb.push_bind(&self.id);
-                    qb.build()
-                    .execute(db)
-                    .await
-                    .map(|_| ())
+
+                    return qb.build_query_as()
+                        .fetch_one(db)
+                        .await;
                 }
-            });
+            })));
 
-            assert_eq!(generated, expected);
+            attr.parsed_struct.database = Some(DbType::MySQL);
+            let generated = clean_tokens(update_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                if !self.description.is_set() && !self.done.is_set() {
+                    return Ok(());
+                }
+            })));
+
+            // A column which is not a `SetOption` is always part of the `SET` clause
+            attr.columns
+                .push(Column::new("title", parse_quote!(String)));
+            let generated = clean_tokens(update_fn(&attr));
+            assert!(!generated.contains("return Ok(());"));
+        }
+
+        #[test]
+        fn test_update_set_option_primary_key() {
+            let mut attr = set_option_input();
+            attr.columns[0] = Column::new("id", parse_quote!(SetOption<i64>));
+            let mut primary_key = attr.columns[0].clone();
+            primary_key.set_primary_key();
+
+            // With and without the primary_key flag on the column itself
+            for columns_primary_key in [false, true] {
+                if columns_primary_key {
+                    attr.columns[0] = primary_key.clone();
+                }
+                attr.primary_key = Some(primary_key.clone());
+
+                let generated = clean_tokens(update_fn(&attr));
+                assert!(!generated.contains(&clean_tokens(quote! {
+                    separated.push("id");
+                    separated.push_unseparated(" = ");
+                    separated.push_bind_unseparated(&self.id);
+                })));
+                assert!(!generated.contains("self.id.is_set()"));
+                assert!(generated.contains(&clean_tokens(quote! {
+                    qb.push(" WHERE ");
+                    qb.push("id");
+                    qb.push(" = ");
+                    qb.push_bind(&self.id);
+                })));
+
+                let generated = clean_tokens(sql_fns(&attr));
+                assert!(!generated.contains(&clean_tokens(quote! {
+                    separated.push("id");
+                })));
+            }
         }
+
+        #[test]
+        fn test_set_option_primary_key_with_create_is_rejected() {
+            let mut primary_key = Column::new("id", parse_quote!(SetOption<i64>));
+            primary_key.set_primary_key();
+            let mut attr = set_option_input();
+            attr.columns[0] = primary_key.clone();
+            attr.primary_key = Some(primary_key);
+            assert!(crate::generate_impl(&attr).is_ok());
+
+            attr.operations.push(Operation::Create);
+            assert!(crate::generate_impl(&attr).is_err());
+        }
+
+        #[test]
+        fn test_structs_targeting_different_databases() {
+            let mut postgres_attr = newtype_pk_input(false);
+            postgres_attr.parsed_struct.database = Some(DbType::Postgres);
+            let mut mysql_attr = newtype_pk_input(false);
+            mysql_attr.parsed_struct.database = Some(DbType::MySQL);
+
+            let postgres_generated = clean_tokens(crate::generate_impl(&postgres_attr).unwrap());
+            assert!(postgres_generated.contains("::sqlx::PgExecutor<'e>"));
+            assert!(postgres_generated.contains("::sqlx::Postgres"));
+            assert!(!postgres_generated.contains("MySql"));
+            assert!(postgres_generated.contains(&clean_tokens(quote! {
+                qb.push(" RETURNING ");
+                qb.push("id");
+            })));
+
+            let mysql_generated = clean_tokens(crate::generate_impl(&mysql_attr).unwrap());
+            assert!(mysql_generated.contains("::sqlx::MySqlExecutor<'e>"));
+            assert!(mysql_generated.contains("::sqlx::MySql"));
+            assert!(!mysql_generated.contains("Pg"));
+            assert!(!mysql_generated.contains("RETURNING"));
+            assert!(mysql_generated.contains(&clean_tokens(quote! {
+                Ok(self.id.clone())
+            })));
+        }
+
         #[cfg(feature = "mysql")]
         #[test]
-        fn test_generate_delete_method_with_soft_deletion() {
+        fn test_newtype_primary_key_create() {
+            let generated = clean_tokens(create_fn(&newtype_pk_input(false)));
+            assert!(generated.contains(&clean_tokens(quote! {
+                -> ::sqlx::Result<TodoId>
+            })));
+            assert!(generated.contains(&clean_tokens(quote! {
+                Ok(self.id.clone())
+            })));
+        }
+
+        #[cfg(feature = "mysql")]
+        #[test]
+        fn test_newtype_auto_increment_primary_key_create() {
+            let error = crate::generate_impl(&newtype_pk_input(true)).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "MySQL only supports auto increment primary keys with an integer type (eg: i64, u32, ...)"
+            );
+        }
+
+        #[cfg(not(feature = "mysql"))]
+        #[test]
+        fn test_custom_output_create() {
             let db_ident = db_ident();
-            let generated = clean_tokens(delete_fn(&input(false, true)));
+            let parsed_struct = ParsedStruct::new(&format_ident!("NewContact"), None, None);
+            let input = Attr {
+                parsed_struct,
+                primary_key: None,
+                lookup_key: None,
+                cursor: None,
+                columns: vec![
+                    Column::new("first_name", parse_quote!(String)),
+                    Column::new("last_name", parse_quote!(String)),
+                    Column::new("email", parse_quote!(String)),
+                ],
+                operations: vec![Operation::Create],
+                soft_deletion: None,
+            };
+
+            let generated = clean_tokens(create_fn(&input));
+            let sql = format!(
+                "INSERT INTO contact (first_name, last_name, email) VALUES ({}) RETURNING *",
+                values_str(3)
+            );
 
             let expected = clean_tokens(quote! {
-                pub async fn delete<'e, E>(&self, db: E) -> ::sqlx::Result<()>
+                pub async fn create<'e, E>(&self, db: E) -> ::sqlx::Result<Contact>
                 where
                     E: ::sqlx::#db_ident<'e>
                 {
-                    let mut qb = ::sqlx::QueryBuilder::new("UPDATE ");
-                    qb.push("contact");
-                    qb.push(" SET deleted_at = CURRENT_TIMESTAMP ");
-                    qb.push(" WHERE deleted_at IS NULL AND ");
-                    qb.push("id");
-                    qb.push(" = ");
-                    qb.push_bind(&self.id);
-                    qb.build()
-                    .execute(db)
+                    const SQL: &str = #sql;
+
+                    ::sqlx::query_as(SQL)
+                    .bind(&self.first_name)
+                    .bind(&self.last_name)
+                    .bind(&self.email)
+                    .fetch_one(db)
                     .await
-                    .map(|_| ())
                 }
             });
-
             assert_eq!(generated, expected);
         }
-    }
-
-    mod custom {
-        use super::*;
-        use crate::attr::Attr;
-        use crate::types::*;
-        use syn::parse_quote;
 
-        #[cfg(not(feature = "mysql"))]
+        #[cfg(feature = "mysql")]
         #[test]
         fn test_custom_output_create() {
-            let db_ident = db_ident();
             let parsed_struct = ParsedStruct::new(&format_ident!("NewContact"), None, None);
             let input = Attr {
                 parsed_struct,
                 primary_key: None,
+                lookup_key: None,
+                cursor: None,
                 columns: vec![
                     Column::new("first_name", parse_quote!(String)),
                     Column::new("last_name", parse_quote!(String)),
                     Column::new("email", parse_quote!(String)),
                 ],
                 operations: vec![Operation::Create],
-                soft_deletion: false,
+                soft_deletion: None,
             };
 
+            // The record created is read back by its `last_insert_id()` on the same connection
             let generated = clean_tokens(create_fn(&input));
-
             let expected = clean_tokens(quote! {
-                pub async fn create<'e, E>(&self, db: E) -> ::sqlx::Result<Contact>
+                pub async fn create<'c, A>(&self, db: A) -> ::sqlx::Result<Contact>
                 where
-                    E: ::sqlx::#db_ident<'e>
+                    A: ::sqlx::Acquire<'c, Database = ::sqlx::MySql>
                 {
-                    let mut fields_str = Vec::new();
-                    fields_str.push("first_name");
-                    fields_str.push("last_name");
-                    fields_str.push("email");
+                    const SQL: &str = "INSERT INTO contact (first_name, last_name, email) VALUES (?, ?, ?)";
+
+                    let mut conn = db.acquire().await?;
+                    let result = ::sqlx::query(SQL)
+                    .bind(&self.first_name)
+                    .bind(&self.last_name)
+                    .bind(&self.email)
+                    .execute(&mut *conn)
+                    .await?;
 
-                    let mut qb = ::sqlx::QueryBuilder::new("INSERT INTO ");
+                    let mut qb = ::sqlx::QueryBuilder::new("SELECT * FROM ");
                     qb.push("contact");
-                    qb.push(" (");
-                    qb.push(fields_str.join(", "));
-                    qb.push(") VALUES (");
-
-                    let mut separated = qb.separated(", ");
-                    separated.push_bind(&self.first_name);
-                    separated.push_bind(&self.last_name);
-                    separated.push_bind(&self.email);
-                    separated.push_unseparated(")");
-
-                    qb.push(" RETURNING * ");
-
+                    let id = result.last_insert_id();
+                    if id == 0 {
+                        return Err(::sqlx::Error::Protocol(
+                            "MySQL can only read back a record created with an auto increment key".to_string(),
+                        ));
+                    }
+                    qb.push(" WHERE id = ");
+                    qb.push_bind(id);
                     qb.build_query_as()
-                    .fetch_one(db)
+                    .fetch_one(&mut *conn)
                     .await
                 }
             });
             assert_eq!(generated, expected);
+
+            let mut input = input;
+            input.parsed_struct.returning = Some(Returning::new("id, email").unwrap());
+            input.columns = vec![Column::new("email", parse_quote!(SetOption<String>))];
+            let generated = clean_tokens(create_fn(&input));
+            assert!(!generated.contains("RETURNING"));
+            assert!(generated.contains(&clean_tokens(quote! {
+                let result = qb.build()
+                .execute(&mut *conn)
+                .await?;
+
+                let mut qb = ::sqlx::QueryBuilder::new("SELECT id, email FROM ");
+            })));
         }
 
         #[cfg(feature = "mysql")]
         #[test]
-        #[should_panic]
-        fn test_custom_output_create() {
-            let parsed_struct = ParsedStruct::new(&format_ident!("NewContact"), None, None);
+        fn test_custom_output_create_reads_back_by_the_renamed_key() {
+            let mut parsed_struct = ParsedStruct::new(&format_ident!("NewContact"), None, None);
+            parsed_struct.created_key = "ID".to_string();
+            let input = Attr {
+                parsed_struct,
+                primary_key: None,
+                lookup_key: None,
+                cursor: None,
+                columns: vec![Column::new("email", parse_quote!(String))],
+                operations: vec![Operation::Create],
+                soft_deletion: None,
+            };
+
+            let generated = clean_tokens(create_fn(&input));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" WHERE `ID` = ");
+                qb.push_bind(id);
+            })));
+        }
+
+        #[cfg(not(feature = "mysql"))]
+        #[test]
+        fn test_custom_output_create_with_explicit_returning() {
+            let mut parsed_struct = ParsedStruct::new(&format_ident!("NewContact"), None, None);
+            parsed_struct.returning = Some(Returning::new("id, email").unwrap());
+            let input = Attr {
+                parsed_struct,
+                primary_key: None,
+                lookup_key: None,
+                cursor: None,
+                columns: vec![Column::new("email", parse_quote!(String))],
+                operations: vec![Operation::Create],
+                soft_deletion: None,
+            };
+
+            let generated = clean_tokens(create_fn(&input));
+            assert!(generated.contains("RETURNINGid,email\";"));
+            assert!(generated.contains("::sqlx::query_as(SQL)"));
+            assert!(!generated.contains("RETURNING*"));
+
+            let mut input = input;
+            input.columns = vec![Column::new("email", parse_quote!(SetOption<String>))];
+            let generated = clean_tokens(create_fn(&input));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" RETURNING id, email ");
+            })));
+        }
+
+        #[cfg(not(feature = "mysql"))]
+        #[test]
+        fn test_custom_output_update_with_explicit_returning() {
+            let mut parsed_struct = ParsedStruct::new(&format_ident!("UpdateContact"), None, None);
+            parsed_struct.returning = Some(Returning::new("id,email").unwrap());
+            let mut primary_key = Column::new("id", parse_quote!(i64));
+            primary_key.set_primary_key();
+            let input = Attr {
+                parsed_struct,
+                primary_key: Some(primary_key.clone()),
+                lookup_key: None,
+                cursor: None,
+                columns: vec![primary_key, Column::new("email", parse_quote!(String))],
+                operations: vec![Operation::Update],
+                soft_deletion: None,
+            };
+
+            let generated = clean_tokens(update_fn(&input));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" RETURNING id, email ");
+
+                qb.build_query_as()
+                .fetch_one(db)
+                .await
+            })));
+            assert!(!generated.contains("RETURNING*"));
+        }
+
+        #[cfg(not(feature = "mysql"))]
+        #[test]
+        fn test_delete_returning_with_explicit_returning() {
+            let mut parsed_struct = ParsedStruct::new(
+                &format_ident!("Contact"),
+                None,
+                Some(format_ident!("Summary")),
+            );
+            parsed_struct.returning = Some(Returning::new("id, email").unwrap());
+            parsed_struct.delete_returning = true;
+            let mut primary_key = Column::new("id", parse_quote!(i64));
+            primary_key.set_primary_key();
+            let input = Attr {
+                parsed_struct,
+                primary_key: Some(primary_key.clone()),
+                lookup_key: None,
+                cursor: None,
+                columns: vec![primary_key, Column::new("email", parse_quote!(String))],
+                operations: vec![Operation::Delete],
+                soft_deletion: None,
+            };
+
+            let generated = clean_tokens(delete_fn(&input));
+            assert!(generated.contains("::sqlx::Result<Option<Summary>>"));
+            assert!(generated.contains(&clean_tokens(quote! {
+                qb.push(" RETURNING id, email ");
+            })));
+        }
+
+        #[test]
+        fn test_primary_key_output_ignores_explicit_returning() {
+            let mut parsed_struct = ParsedStruct::new(&format_ident!("NewContact"), None, None);
+            parsed_struct.returning = Some(Returning::new("id, email").unwrap());
+            let mut primary_key = Column::new("id", parse_quote!(i64));
+            primary_key.set_primary_key();
+            let input = Attr {
+                parsed_struct,
+                primary_key: Some(primary_key.clone()),
+                lookup_key: None,
+                cursor: None,
+                columns: vec![primary_key, Column::new("email", parse_quote!(String))],
+                operations: vec![Operation::Create],
+                soft_deletion: None,
+            };
+
+            let generated = clean_tokens(create_fn(&input));
+            assert!(!generated.contains("RETURNINGid,email"));
+        }
+
+        #[cfg(not(feature = "mysql"))]
+        #[test]
+        fn test_columns_are_bound_by_reference() {
+            let mut attr = set_option_input();
+            attr.operations = vec![Operation::Create, Operation::Update, Operation::Delete];
+            for generated in [
+                create_fn(&attr),
+                create_if_not_exists_fn(&attr),
+                update_fn(&attr),
+                delete_fn(&attr),
+            ] {
+                let generated = clean_tokens(generated);
+                assert!(!generated.contains("clone()"));
+                assert!(!generated.contains("to_owned()"));
+            }
+        }
+
+        #[test]
+        fn test_json_columns_are_wrapped() {
+            let parsed_struct = ParsedStruct::new(&format_ident!("Settings"), None, None);
+            let mut primary_key = Column::new("id", parse_quote!(i64));
+            primary_key.set_primary_key();
+            let mut metadata = Column::new("metadata", parse_quote!(SetOption<serde_json::Value>));
+            metadata.set_json();
+            let mut extra =
+                Column::new("extra", parse_quote!(SetOption<Option<serde_json::Value>>));
+            extra.set_json();
+            let mut tags = Column::new("tags", parse_quote!(Vec<String>));
+            tags.set_json();
+            let attr = Attr {
+                parsed_struct,
+                primary_key: Some(primary_key.clone()),
+                lookup_key: None,
+                cursor: None,
+                columns: vec![primary_key, metadata, extra, tags],
+                operations: vec![Operation::Create, Operation::Update],
+                soft_deletion: None,
+            };
+
+            let generated = clean_tokens(insert_statement(&attr, "INSERT INTO "));
+            assert!(generated.contains(&clean_tokens(quote! {
+                separated.push_bind(&self.id);
+                if let Some(v) = metadata_value {
+                    separated.push_bind(::sqlx::types::Json(v));
+                }
+                if let Some(v) = extra_value {
+                    separated.push_bind(v.as_ref().map(::sqlx::types::Json));
+                }
+                separated.push_bind(::sqlx::types::Json(&self.tags));
+            })));
+
+            let generated = clean_tokens(update_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                if let SetOption::Set(v) = &self.metadata {
+                    separated.push("metadata");
+                    separated.push_unseparated(" = ");
+                    separated.push_bind_unseparated(::sqlx::types::Json(v));
+                }
+                if let SetOption::Set(v) = &self.extra {
+                    separated.push("extra");
+                    separated.push_unseparated(" = ");
+                    separated.push_bind_unseparated(v.as_ref().map(::sqlx::types::Json));
+                }
+                separated.push("tags");
+                separated.push_unseparated(" = ");
+                separated.push_bind_unseparated(::sqlx::types::Json(&self.tags));
+            })));
+        }
+
+        #[test]
+        fn test_generate_from_row_impl() {
+            let mut parsed_struct = ParsedStruct::new(&format_ident!("Patient"), None, None);
+            parsed_struct.database = Some(DbType::Postgres);
+            parsed_struct.skipped_fields = vec![format_ident!("cache")];
+            let mut primary_key = Column::new("id", parse_quote!(i64));
+            primary_key.set_primary_key();
+            let mut full_name = Column::new("full_name", parse_quote!(String));
+            full_name.set_name("name");
+            let mut ssn = Column::new("ssn", parse_quote!(Option<String>));
+            ssn.set_transform(parse_quote!(crate::crypto));
+            let mut metadata = Column::new("metadata", parse_quote!(Vec<String>));
+            metadata.set_json();
+            let attr = Attr {
+                parsed_struct,
+                primary_key: Some(primary_key.clone()),
+                lookup_key: None,
+                cursor: None,
+                columns: vec![primary_key, full_name, ssn, metadata],
+                operations: vec![Operation::Get],
+                soft_deletion: None,
+            };
+
+            let generated = clean_tokens(from_row_impl(&attr));
+            let expected = clean_tokens(quote! {
+                impl<'r> ::sqlx::FromRow<'r, <::sqlx::Postgres as ::sqlx::Database>::Row> for Patient {
+                    fn from_row(row: &'r <::sqlx::Postgres as ::sqlx::Database>::Row) -> ::sqlx::Result<Self> {
+                        Ok(Self {
+                            id: ::sqlx::Row::try_get(row, "id")?,
+                            full_name: ::sqlx::Row::try_get(row, "name")?,
+                            ssn: ::sqlx::Row::try_get::<Option<_>, _>(row, "ssn")?.map(crate::crypto::decode),
+                            metadata: ::sqlx::Row::try_get::<::sqlx::types::Json<_>, _>(row, "metadata")?.0,
+                            cache: ::core::default::Default::default()
+                        })
+                    }
+                }
+            });
+            assert_eq!(generated, expected);
+        }
+
+        #[test]
+        fn test_transform_columns_are_encoded() {
+            let parsed_struct = ParsedStruct::new(&format_ident!("Patient"), None, None);
+            let mut primary_key = Column::new("id", parse_quote!(i64));
+            primary_key.set_primary_key();
+            let mut ssn = Column::new("ssn", parse_quote!(String));
+            ssn.set_transform(parse_quote!(crate::crypto));
+            let mut notes = Column::new("notes", parse_quote!(SetOption<Option<String>>));
+            notes.set_transform(parse_quote!(crate::crypto));
+            let attr = Attr {
+                parsed_struct,
+                primary_key: Some(primary_key.clone()),
+                lookup_key: None,
+                cursor: None,
+                columns: vec![primary_key, ssn, notes],
+                operations: vec![Operation::Create, Operation::Update],
+                soft_deletion: None,
+            };
+
+            let generated = clean_tokens(insert_statement(&attr, "INSERT INTO "));
+            assert!(generated.contains(&clean_tokens(quote! {
+                separated.push_bind(&self.id);
+                separated.push_bind(crate::crypto::encode(&self.ssn));
+                if let Some(v) = notes_value {
+                    separated.push_bind(v.as_ref().map(crate::crypto::encode));
+                }
+            })));
+
+            let generated = clean_tokens(update_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                separated.push("ssn");
+                separated.push_unseparated(" = ");
+                separated.push_bind_unseparated(crate::crypto::encode(&self.ssn));
+                if let SetOption::Set(v) = &self.notes {
+                    separated.push("notes");
+                    separated.push_unseparated(" = ");
+                    separated.push_bind_unseparated(v.as_ref().map(crate::crypto::encode));
+                }
+            })));
+        }
+
+        #[test]
+        fn test_update_optional_columns() {
+            let parsed_struct = ParsedStruct::new(&format_ident!("UpdateProfile"), None, None);
+            let mut primary_key = Column::new("id", parse_quote!(i64));
+            primary_key.set_primary_key();
+            let attr = Attr {
+                parsed_struct,
+                primary_key: Some(primary_key.clone()),
+                lookup_key: None,
+                cursor: None,
+                columns: vec![
+                    primary_key,
+                    Column::new("bio", parse_quote!(Option<String>)),
+                    Column::new("nickname", parse_quote!(SetOption<String>)),
+                    Column::new("website", parse_quote!(SetOption<Option<String>>)),
+                ],
+                operations: vec![Operation::Update],
+                soft_deletion: None,
+            };
+
+            // An `Option` is always bound (`None` writes a NULL), a `SetOption` is skipped when `NotSet`
+            // and a `SetOption<Option>` writes a NULL with `Set(None)`, since it is bound as is
+            let generated = clean_tokens(update_fn(&attr));
+            assert!(generated.contains(&clean_tokens(quote! {
+                let mut separated = qb.separated(", ");
+                separated.push("bio");
+                separated.push_unseparated(" = ");
+                separated.push_bind_unseparated(&self.bio);
+                if self.nickname.is_set() {
+                    separated.push("nickname");
+                    separated.push_unseparated(" = ");
+                    separated.push_bind_unseparated(&self.nickname);
+                }
+                if self.website.is_set() {
+                    separated.push("website");
+                    separated.push_unseparated(" = ");
+                    separated.push_bind_unseparated(&self.website);
+                }
+                qb.push(" WHERE ");
+            })));
+            // The `Option` is always written, so the update is never empty
+            assert!(!generated.contains("is_set() &&"));
+        }
+
+        #[test]
+        fn test_setoption_create_returning_nothing() {
+            let mut parsed_struct = ParsedStruct::new(&format_ident!("NewContact"), None, None);
+            parsed_struct.return_nothing = true;
             let input = Attr {
                 parsed_struct,
                 primary_key: None,
+                lookup_key: None,
+                cursor: None,
                 columns: vec![
-                    Column::new("first_name", parse_quote!(String)),
-                    Column::new("last_name", parse_quote!(String)),
+                    Column::new("first_name", parse_quote!(SetOption<String>)),
                     Column::new("email", parse_quote!(String)),
                 ],
                 operations: vec![Operation::Create],
-                soft_deletion: false,
+                soft_deletion: None,
             };
 
-            create_fn(&input);
+            let generated = clean_tokens(create_fn(&input));
+            assert!(generated.contains(&clean_tokens(quote! { -> ::sqlx::Result<()> })));
+            assert!(!generated.contains("RETURNING"));
+            assert!(generated.contains(&clean_tokens(quote! {
+                separated.push_unseparated(")");
+
+                qb.build()
+                .execute(db)
+                .await
+                .map(|_| ())
+            })));
         }
 
         #[cfg(not(feature = "mysql"))]
@@ -1036,13 +7504,15 @@ mod tests {
             let input = Attr {
                 parsed_struct,
                 primary_key: None,
+                lookup_key: None,
+                cursor: None,
                 columns: vec![
                     Column::new("first_name", parse_quote!(SetOption<String>)),
                     Column::new("last_name", parse_quote!(SetOption<String>)),
                     Column::new("email", parse_quote!(String)),
                 ],
                 operations: vec![Operation::Create],
-                soft_deletion: false,
+                soft_deletion: None,
             };
 
             let generated = clean_tokens(create_fn(&input));
@@ -1052,11 +7522,13 @@ mod tests {
                 where
                     E: ::sqlx::#db_ident<'e>
                 {
+                    let first_name_value = self.first_name.value_ref().ok();
+                    let last_name_value = self.last_name.value_ref().ok();
                     let mut fields_str = Vec::new();
-                    if self.first_name.is_set() {
+                    if first_name_value.is_some() {
                         fields_str.push("first_name");
                     }
-                    if self.last_name.is_set() {
+                    if last_name_value.is_some() {
                         fields_str.push("last_name");
                     }
                     fields_str.push("email");
@@ -1068,10 +7540,10 @@ mod tests {
                     qb.push(") VALUES (");
 
                     let mut separated = qb.separated(", ");
-                    if let SetOption::Set(v) = &self.first_name {
+                    if let Some(v) = first_name_value {
                         separated.push_bind(v);
                     }
-                    if let SetOption::Set(v) = &self.last_name {
+                    if let Some(v) = last_name_value {
                         separated.push_bind(v);
                     }
                     separated.push_bind(&self.email);
@@ -1087,6 +7559,143 @@ mod tests {
             assert_eq!(generated, expected);
         }
 
+        #[test]
+        fn test_create_pushes_the_values_in_the_order_of_the_columns() {
+            let parsed_struct = ParsedStruct::new(&format_ident!("Profile"), None, None);
+            let mut primary_key = Column::new("id", parse_quote!(Option<i64>));
+            primary_key.set_primary_key();
+            let mut metadata = Column::new("metadata", parse_quote!(SetOption<Value>));
+            metadata.set_json();
+            let input = Attr {
+                parsed_struct,
+                primary_key: Some(primary_key.clone()),
+                lookup_key: None,
+                cursor: None,
+                columns: vec![
+                    primary_key,
+                    Column::new("first_name", parse_quote!(SetOption<String>)),
+                    Column::new("email", parse_quote!(String)),
+                    metadata,
+                    Column::new("nickname", parse_quote!(SetOption<Option<String>>)),
+                    Column::new("age", parse_quote!(i32)),
+                ],
+                operations: vec![Operation::Create],
+                soft_deletion: None,
+            };
+            let generated = clean_tokens(insert_statement(&input, "INSERT INTO "));
+
+            // Each column is either always pushed or guarded by its own `<column>_value`
+            let column_regex = regex::Regex::new(
+                r#"(?:if(\w+)_value\.is_some\(\)\{)?fields_str\.push\("(\w+)"\);"#,
+            )
+            .unwrap();
+            let bind_regex = regex::Regex::new(
+                r#"(?:ifletSome\(v\)=(\w+)_value\{)?separated\.push_bind\((?:&self\.(\w+)|[^;]*v[^;]*)\);"#,
+            )
+            .unwrap();
+            let columns: Vec<(Option<&str>, &str)> = column_regex
+                .captures_iter(&generated)
+                .map(|captures| {
+                    let guard = captures.get(1).map(|guard| guard.as_str());
+                    (guard, captures.get(2).unwrap().as_str())
+                })
+                .collect();
+            let binds: Vec<(Option<&str>, &str)> = bind_regex
+                .captures_iter(&generated)
+                .map(|captures| {
+                    let guard = captures.get(1).map(|guard| guard.as_str());
+                    let field = guard.or(captures.get(2).map(|field| field.as_str()));
+                    (guard, field.unwrap())
+                })
+                .collect();
+
+            assert_eq!(
+                columns,
+                vec![
+                    (Some("id"), "id"),
+                    (Some("first_name"), "first_name"),
+                    (None, "email"),
+                    (Some("metadata"), "metadata"),
+                    (Some("nickname"), "nickname"),
+                    (None, "age"),
+                ]
+            );
+            assert_eq!(binds, columns);
+        }
+
+        #[test]
+        fn test_create_with_nested_optional_columns() {
+            let parsed_struct = ParsedStruct::new(&format_ident!("Profile"), None, None);
+            let input = Attr {
+                parsed_struct,
+                primary_key: None,
+                lookup_key: None,
+                cursor: None,
+                columns: vec![
+                    Column::new("nickname", parse_quote!(SetOption<Option<String>>)),
+                    Column::new("bio", parse_quote!(Option<SetOption<String>>)),
+                ],
+                operations: vec![Operation::Create],
+                soft_deletion: None,
+            };
+
+            let generated = clean_tokens(insert_statement(&input, "INSERT INTO "));
+            assert!(generated.contains(&clean_tokens(quote! {
+                if nickname_value.is_some() {
+                    fields_str.push("nickname");
+                }
+                fields_str.push("bio");
+            })));
+            assert!(generated.contains(&clean_tokens(quote! {
+                if let Some(v) = nickname_value {
+                    separated.push_bind(v);
+                }
+                separated.push_bind(&self.bio);
+            })));
+        }
+
+        #[test]
+        fn test_create_with_skip_defaults() {
+            let mut parsed_struct = ParsedStruct::new(&format_ident!("NewSettings"), None, None);
+            parsed_struct.skip_defaults = true;
+            let mut primary_key = Column::new("id", parse_quote!(i64));
+            primary_key.set_primary_key();
+            let input = Attr {
+                parsed_struct,
+                primary_key: Some(primary_key.clone()),
+                lookup_key: None,
+                cursor: None,
+                columns: vec![
+                    primary_key,
+                    Column::new("theme", parse_quote!(String)),
+                    Column::new("retries", parse_quote!(i32)),
+                ],
+                operations: vec![Operation::Create],
+                soft_deletion: None,
+            };
+
+            let generated = clean_tokens(insert_statement(&input, "INSERT INTO "));
+            assert!(generated.contains(&clean_tokens(quote! {
+                fields_str.push("id");
+                if theme_value.is_some() {
+                    fields_str.push("theme");
+                }
+                if retries_value.is_some() {
+                    fields_str.push("retries");
+                }
+            })));
+            assert!(generated.contains(&clean_tokens(quote! {
+                separated.push_bind(&self.id);
+                if let Some(v) = theme_value {
+                    separated.push_bind(v);
+                }
+                if let Some(v) = retries_value {
+                    separated.push_bind(v);
+                }
+            })));
+            assert!(!has_static_create_query(&input));
+        }
+
         #[cfg(not(feature = "mysql"))]
         #[test]
         fn test_setoption_create_auto_generated_pk() {
@@ -1098,6 +7707,8 @@ mod tests {
             let input = Attr {
                 parsed_struct,
                 primary_key: Some(primary_key.clone()),
+                lookup_key: None,
+                cursor: None,
                 columns: vec![
                     primary_key,
                     Column::new("first_name", parse_quote!(SetOption<String>)),
@@ -1105,7 +7716,7 @@ mod tests {
                     Column::new("email", parse_quote!(String)),
                 ],
                 operations: vec![Operation::Create],
-                soft_deletion: false,
+                soft_deletion: None,
             };
 
             let generated = clean_tokens(create_fn(&input));
@@ -1115,11 +7726,13 @@ mod tests {
                 where
                     E: ::sqlx::#db_ident<'e>
                 {
+                    let first_name_value = self.first_name.value_ref().ok();
+                    let last_name_value = self.last_name.value_ref().ok();
                     let mut fields_str = Vec::new();
-                    if self.first_name.is_set() {
+                    if first_name_value.is_some() {
                         fields_str.push("first_name");
                     }
-                    if self.last_name.is_set() {
+                    if last_name_value.is_some() {
                         fields_str.push("last_name");
                     }
                     fields_str.push("email");
@@ -1131,10 +7744,10 @@ mod tests {
                     qb.push(") VALUES (");
 
                     let mut separated = qb.separated(", ");
-                    if let SetOption::Set(v) = &self.first_name {
+                    if let Some(v) = first_name_value {
                         separated.push_bind(v);
                     }
-                    if let SetOption::Set(v) = &self.last_name {
+                    if let Some(v) = last_name_value {
                         separated.push_bind(v);
                     }
                     separated.push_bind(&self.email);
@@ -1146,7 +7759,7 @@ mod tests {
                     qb.build()
                     .fetch_one(db)
                     .await
-                    .map(|row|row.get(0))
+                    .map(|row| ::sqlx::Row::get(&row, 0))
                 }
             });
             assert_eq!(generated, expected);
@@ -1162,6 +7775,8 @@ mod tests {
             let input = Attr {
                 parsed_struct,
                 primary_key: Some(primary_key.clone()),
+                lookup_key: None,
+                cursor: None,
                 columns: vec![
                     primary_key,
                     Column::new("first_name", parse_quote!(SetOption<String>)),
@@ -1169,7 +7784,7 @@ mod tests {
                     Column::new("email", parse_quote!(String)),
                 ],
                 operations: vec![Operation::Create],
-                soft_deletion: false,
+                soft_deletion: None,
             };
 
             let generated = clean_tokens(create_fn(&input));
@@ -1179,12 +7794,14 @@ mod tests {
                 where
                     E: ::sqlx::#db_ident<'e>
                 {
+                    let first_name_value = self.first_name.value_ref().ok();
+                    let last_name_value = self.last_name.value_ref().ok();
                     let mut fields_str = Vec::new();
                     fields_str.push("uuid");
-                    if self.first_name.is_set() {
+                    if first_name_value.is_some() {
                         fields_str.push("first_name");
                     }
-                    if self.last_name.is_set() {
+                    if last_name_value.is_some() {
                         fields_str.push("last_name");
                     }
                     fields_str.push("email");
@@ -1197,10 +7814,10 @@ mod tests {
 
                     let mut separated = qb.separated(", ");
                     separated.push_bind(&self.uuid);
-                    if let SetOption::Set(v) = &self.first_name {
+                    if let Some(v) = first_name_value {
                         separated.push_bind(v);
                     }
-                    if let SetOption::Set(v) = &self.last_name {
+                    if let Some(v) = last_name_value {
                         separated.push_bind(v);
                     }
                     separated.push_bind(&self.email);
@@ -1212,7 +7829,7 @@ mod tests {
                     qb.build()
                     .fetch_one(db)
                     .await
-                    .map(|row|row.get(0))
+                    .map(|row| ::sqlx::Row::get(&row, 0))
                 }
             });
             assert_eq!(generated, expected);
@@ -1229,9 +7846,11 @@ mod tests {
             let input = Attr {
                 parsed_struct,
                 primary_key: Some(primary_key.clone()),
+                lookup_key: None,
+                cursor: None,
                 columns: vec![primary_key, Column::new("first_name", parse_quote!(String))],
                 operations: vec![Operation::Update],
-                soft_deletion: false,
+                soft_deletion: None,
             };
 
             let generated = clean_tokens(update_fn(&input));
@@ -1245,15 +7864,11 @@ mod tests {
                     qb.push("contact");
                     qb.push(" SET ");
 
-                    let mut first = true;
+                    let mut separated = qb.separated(", ");
 
-                    if !first {
-                        qb.push(",");
-                    }
-                    qb.push("first_name");
-                    qb.push(" = ");
-                    qb.push_bind(&self.first_name);
-                    first = false;
+                    separated.push("first_name");
+                    separated.push_unseparated(" = ");
+                    separated.push_bind_unseparated(&self.first_name);
 
                     qb.push(" WHERE ");
                     qb.push("custom_id");
@@ -1281,9 +7896,11 @@ mod tests {
             let input = Attr {
                 parsed_struct,
                 primary_key: Some(primary_key.clone()),
+                lookup_key: None,
+                cursor: None,
                 columns: vec![primary_key, Column::new("first_name", parse_quote!(String))],
                 operations: vec![Operation::Update],
-                soft_deletion: false,
+                soft_deletion: None,
             };
 
             let generated = clean_tokens(update_fn(&input));
@@ -1297,15 +7914,11 @@ mod tests {
                     qb.push("contact");
                     qb.push(" SET ");
 
-                    let mut first = true;
+                    let mut separated = qb.separated(", ");
 
-                    if !first {
-                        qb.push(",");
-                    }
-                    qb.push("first_name");
-                    qb.push(" = ");
-                    qb.push_bind(&self.first_name);
-                    first = false;
+                    separated.push("first_name");
+                    separated.push_unseparated(" = ");
+                    separated.push_bind_unseparated(&self.first_name);
 
                     qb.push(" WHERE ");
                     qb.push("custom_id");
@@ -1333,13 +7946,15 @@ mod tests {
             let input = Attr {
                 parsed_struct,
                 primary_key: Some(primary_key.clone()),
+                lookup_key: None,
+                cursor: None,
                 columns: vec![
                     primary_key,
                     Column::new("first_name", parse_quote!(SetOption<String>)),
                     Column::new("last_name", parse_quote!(String)),
                 ],
                 operations: vec![Operation::Update],
-                soft_deletion: false,
+                soft_deletion: None,
             };
 
             let generated = clean_tokens(update_fn(&input));
@@ -1353,24 +7968,16 @@ mod tests {
                     qb.push("contact");
                     qb.push(" SET ");
 
-                    let mut first = true;
+                    let mut separated = qb.separated(", ");
 
                     if self.first_name.is_set() {
-                        if !first {
-                            qb.push(",");
-                        }
-                        qb.push("first_name");
-                        qb.push(" = ");
-                        qb.push_bind(&self.first_name);
-                        first = false;
-                    }
-                    if !first {
-                        qb.push(",");
+                        separated.push("first_name");
+                        separated.push_unseparated(" = ");
+                        separated.push_bind_unseparated(&self.first_name);
                     }
-                    qb.push("last_name");
-                    qb.push(" = ");
-                    qb.push_bind(&self.last_name);
-                    first = false;
+                    separated.push("last_name");
+                    separated.push_unseparated(" = ");
+                    separated.push_bind_unseparated(&self.last_name);
 
                     qb.push(" WHERE ");
                     qb.push("custom_id");
@@ -1398,13 +8005,15 @@ mod tests {
             let input = Attr {
                 parsed_struct,
                 primary_key: Some(primary_key.clone()),
+                lookup_key: None,
+                cursor: None,
                 columns: vec![
                     primary_key,
                     Column::new("first_name", parse_quote!(SetOption<String>)),
                     Column::new("last_name", parse_quote!(String)),
                 ],
                 operations: vec![Operation::Update],
-                soft_deletion: false,
+                soft_deletion: None,
             };
 
             let generated = clean_tokens(update_fn(&input));
@@ -1418,24 +8027,16 @@ mod tests {
                     qb.push("contact");
                     qb.push(" SET ");
 
-                    let mut first = true;
+                    let mut separated = qb.separated(", ");
 
                     if self.first_name.is_set() {
-                        if !first {
-                            qb.push(",");
-                        }
-                        qb.push("first_name");
-                        qb.push(" = ");
-                        qb.push_bind(&self.first_name);
-                        first = false;
-                    }
-                    if !first {
-                        qb.push(",");
+                        separated.push("first_name");
+                        separated.push_unseparated(" = ");
+                        separated.push_bind_unseparated(&self.first_name);
                     }
-                    qb.push("last_name");
-                    qb.push(" = ");
-                    qb.push_bind(&self.last_name);
-                    first = false;
+                    separated.push("last_name");
+                    separated.push_unseparated(" = ");
+                    separated.push_bind_unseparated(&self.last_name);
 
                     qb.push(" WHERE ");
                     qb.push("custom_id");
@@ -3,10 +3,51 @@ use quote::{format_ident, ToTokens};
 use regex::Regex;
 use std::sync::LazyLock;
 use std::{fmt, str::FromStr};
-use syn::{Ident, Type};
+use syn::{GenericArgument, Generics, Ident, LitStr, Path, PathArguments, Type, Visibility};
+
+use crate::database::DbType;
 
 static FIND_SET_OPTION_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^(?:tiny_orm\s*::\s*)*SetOption\s*<").unwrap());
+static FUNCTION_CALL_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z_][A-Za-z0-9_]*\([A-Za-z0-9_', ]*\)$").unwrap());
+
+const TIMESTAMP_TYPES: [&str; 5] = [
+    "DateTime",
+    "NaiveDateTime",
+    "OffsetDateTime",
+    "PrimitiveDateTime",
+    "UtcDateTime",
+];
+
+const INTEGER_TYPES: [&str; 12] = [
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+];
+
+/// The quote of the identifiers of the backend
+fn identifier_quote(db_type: &DbType) -> char {
+    match db_type {
+        DbType::MySQL => '`',
+        DbType::Postgres | DbType::Sqlite => '"',
+    }
+}
+
+/// A name which is not a plain lowercase identifier (eg: `firstName` or `first-name` with `rename_all`)
+/// is quoted, otherwise Postgres would lowercase it and no backend accepts a `-` in it
+pub fn quote_identifier(name: &str, db_type: &DbType) -> String {
+    let is_plain = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if is_plain {
+        return name.to_string();
+    }
+    let quote = identifier_quote(db_type);
+    format!("{quote}{name}{quote}")
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum StructType {
@@ -45,9 +86,39 @@ impl From<&str> for StructType {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParsedStruct {
     pub name: StructName,
+    pub vis: Visibility,
+    pub generics: Generics,
+    /// The fields with `#[sqlx(skip)]`, which are not columns
+    pub skipped_fields: Vec<Ident>,
     pub table_name: TableName,
+    /// The name of the generated `table_name()` function
+    pub table_name_fn: Option<Ident>,
     pub struct_type: StructType,
     pub return_object: ReturnObject,
+    pub return_nothing: bool,
+    pub sqlite_no_returning: bool,
+    pub returning: Option<Returning>,
+    pub inject: Option<Inject>,
+    pub scope: Option<Scope>,
+    pub order_by: Option<OrderBy>,
+    pub conflict_target: Option<ConflictTarget>,
+    pub delete_returning: bool,
+    pub returning_generated: bool,
+    /// The environment variables read by `env!("VAR")` values
+    pub env_vars: Vec<LitStr>,
+    /// The auto increment key of the table by which MySQL reads back a record created without `RETURNING`,
+    /// `id` renamed with `rename_all`
+    pub created_key: String,
+    pub skip_defaults: bool,
+    pub from_row: bool,
+    pub error: Option<Path>,
+    pub database: Option<DbType>,
+    pub belongs_to: Vec<BelongsTo>,
+    pub has_many: Vec<HasMany>,
+    pub projections: Vec<Projection>,
+    /// The column telling the variants of an enum apart, `None` for a struct
+    pub discriminator: Option<String>,
+    pub variants: Vec<Variant>,
 }
 impl ParsedStruct {
     pub fn new(
@@ -71,13 +142,299 @@ impl ParsedStruct {
 
         Self {
             name: struct_name.clone(),
+            vis: Visibility::Inherited,
+            generics: Generics::default(),
+            skipped_fields: Vec::new(),
             table_name: TableName::new(&table_name),
+            table_name_fn: None,
             struct_type,
             return_object,
+            return_nothing: false,
+            sqlite_no_returning: false,
+            returning: None,
+            inject: None,
+            scope: None,
+            order_by: None,
+            conflict_target: None,
+            delete_returning: false,
+            returning_generated: false,
+            env_vars: Vec::new(),
+            created_key: "id".to_string(),
+            skip_defaults: false,
+            from_row: false,
+            error: None,
+            database: None,
+            belongs_to: Vec::new(),
+            has_many: Vec::new(),
+            projections: Vec::new(),
+            discriminator: None,
+            variants: Vec::new(),
+        }
+    }
+}
+
+/// A variant of an enum mapped to a single table, built from the records whose discriminator
+/// column is the snake_case name of the variant (eg: `circle` for `Circle`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variant {
+    pub ident: Ident,
+    pub discriminator: String,
+    pub columns: Vec<Column>,
+    pub skipped_fields: Vec<Ident>,
+}
+impl Variant {
+    pub fn new(ident: &Ident, columns: Vec<Column>, skipped_fields: Vec<Ident>) -> Self {
+        Self {
+            ident: ident.clone(),
+            discriminator: ident.to_string().to_case(Case::Snake),
+            columns,
+            skipped_fields,
+        }
+    }
+}
+
+/// The struct refers to a `target` record with its `foreign_key` field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BelongsTo {
+    pub target: Ident,
+    pub foreign_key: Ident,
+}
+impl BelongsTo {
+    /// Name of the accessor, eg: `user()` for a `User` target
+    pub fn fn_name(&self) -> Ident {
+        format_ident!("{}", self.target.to_string().to_case(Case::Snake))
+    }
+}
+
+/// The `target` records refer to the struct with their `foreign_key` column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HasMany {
+    pub target: Ident,
+    pub foreign_key: String,
+    pub table_name: TableName,
+    pub soft_deletion: Option<SoftDeletion>,
+    /// The records are (soft) deleted along with the struct by `delete()`
+    pub cascade_delete: bool,
+    /// The name of the accessor given by `child_fn`, when the plural is not just an `s` (eg: `categories`)
+    pub accessor: Option<Ident>,
+}
+impl HasMany {
+    pub fn new(
+        target: Ident,
+        foreign_key: &str,
+        table_name: Option<String>,
+        soft_deletion: Option<SoftDeletion>,
+    ) -> Self {
+        let table_name = match table_name {
+            Some(value) => TableName::new(&value),
+            None => TableName::new(&target.to_string()),
+        };
+        Self {
+            target,
+            foreign_key: foreign_key.trim().to_string(),
+            table_name,
+            soft_deletion,
+            cascade_delete: false,
+            accessor: None,
+        }
+    }
+    /// Name of the accessor, eg: `todos()` for a `Todo` target
+    pub fn fn_name(&self) -> Ident {
+        match self.accessor {
+            Some(ref accessor) => accessor.clone(),
+            None => format_ident!("{}s", self.target.to_string().to_case(Case::Snake)),
+        }
+    }
+}
+
+/// A lighter struct read by `list_all_<name>()` from a few columns only, eg: `TodoSummary = "id, description"`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Projection {
+    pub target: Ident,
+    pub columns: Vec<String>,
+}
+impl Projection {
+    pub fn new(target: Ident, input: &str) -> Result<Self, String> {
+        Ok(Self {
+            target,
+            columns: parse_columns(input, "projections")?,
+        })
+    }
+    /// The columns in the SQL, quoted when needed
+    pub fn sql(&self, db_type: &DbType) -> String {
+        quote_identifiers(&self.columns, db_type)
+    }
+    /// Name of the method, eg: `list_all_summary()` for a `TodoSummary` target of `Todo`
+    pub fn fn_name(&self, struct_name: &Ident) -> Ident {
+        let target = self.target.to_string();
+        let name = target
+            .strip_prefix(&struct_name.to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or(&target);
+        format_ident!("list_all_{}", name.to_case(Case::Snake))
+    }
+}
+
+/// Explicit list of columns used in the `RETURNING` statement instead of `*`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Returning(pub Vec<String>);
+impl Returning {
+    pub fn new(input: &str) -> Result<Self, String> {
+        parse_columns(input, "returning").map(Self)
+    }
+}
+impl Returning {
+    /// The columns in the SQL, quoted when needed
+    pub fn sql(&self, db_type: &DbType) -> String {
+        quote_identifiers(&self.0, db_type)
+    }
+}
+
+/// Column bound from the `tenant` argument of `create_for()` instead of a field of the struct, eg: `tenant_id`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inject(pub String);
+impl Inject {
+    pub fn new(input: &str) -> Result<Self, String> {
+        match parse_columns(input, "inject")?.as_slice() {
+            [column] => Ok(Self(column.clone())),
+            _ => Err("The 'inject' keyword takes a single column".to_string()),
+        }
+    }
+    /// The column of the generic `T` type of the `tenant` argument
+    pub fn column(&self) -> Column {
+        let mut column = Column::new(&self.0, syn::parse_quote!(T));
+        column.injected = true;
+        column
+    }
+}
+
+/// Static condition added to the `WHERE` clause of every `SELECT` query.
+/// It is trusted since it comes from the code, only obvious mistakes are rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scope(pub String);
+impl Scope {
+    pub fn new(input: &str) -> Result<Self, String> {
+        parse_condition(input, "scope").map(Self)
+    }
+}
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Default order of the `list` queries, eg: `created_at DESC, id`. The direction is `ASC` unless given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderBy(pub Vec<(String, String)>);
+impl OrderBy {
+    pub fn new(input: &str) -> Result<Self, String> {
+        input
+            .split(',')
+            .map(|item| {
+                let mut words = item.split_whitespace();
+                let column = words.next().unwrap_or_default();
+                let direction = words.next().map(str::to_uppercase);
+                match (direction.as_deref(), words.next()) {
+                    (None | Some("ASC") | Some("DESC"), None) => Ok(()),
+                    _ => Err(format!(
+                        "'{}' is not a valid order for the 'order_by' keyword",
+                        item.trim()
+                    )),
+                }?;
+                let column = parse_columns(column, "order_by")?.remove(0);
+                Ok((column, direction.unwrap_or_else(|| "ASC".to_string())))
+            })
+            .collect::<Result<Vec<(String, String)>, String>>()
+            .map(Self)
+    }
+}
+impl fmt::Display for OrderBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let items: Vec<String> = self
+            .0
+            .iter()
+            .map(|(column, direction)| format!("{column} {direction}"))
+            .collect();
+        write!(f, "{}", items.join(", "))
+    }
+}
+
+/// Columns of the unique index used by the `ON CONFLICT` clause of the upsert instead of the primary key.
+/// The predicate targets a partial unique index (eg: `WHERE deleted_at IS NULL`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictTarget {
+    pub columns: Vec<String>,
+    pub predicate: Option<String>,
+}
+impl ConflictTarget {
+    pub fn new(input: &str) -> Result<Self, String> {
+        Ok(Self {
+            columns: parse_columns(input, "conflict_target")?,
+            predicate: None,
+        })
+    }
+
+    pub fn with_predicate(self, input: &str) -> Result<Self, String> {
+        Ok(Self {
+            predicate: Some(parse_condition(input, "conflict_where")?),
+            ..self
+        })
+    }
+}
+impl ConflictTarget {
+    /// The target in the SQL, its columns being quoted when needed
+    pub fn sql(&self, db_type: &DbType) -> String {
+        let columns = quote_identifiers(&self.columns, db_type);
+        match self.predicate {
+            Some(ref predicate) => format!("({columns}) WHERE {predicate}"),
+            None => format!("({columns})"),
         }
     }
 }
 
+/// The columns of a list given in an attribute, quoted when needed and separated by commas
+fn quote_identifiers(columns: &[String], db_type: &DbType) -> String {
+    columns
+        .iter()
+        .map(|column| quote_identifier(column, db_type))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn parse_columns(input: &str, keyword: &str) -> Result<Vec<String>, String> {
+    let columns: Vec<String> = input.split(',').map(|s| s.trim().to_string()).collect();
+    for column in columns.iter() {
+        let is_identifier = column
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && column
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_identifier {
+            return Err(format!(
+                "'{column}' is not a valid column name for the '{keyword}' keyword"
+            ));
+        }
+    }
+    Ok(columns)
+}
+
+fn parse_condition(input: &str, keyword: &str) -> Result<String, String> {
+    let condition = input.trim();
+    let is_condition = !condition.is_empty()
+        && !condition.contains(';')
+        && !condition.contains("--")
+        && !condition.contains("/*")
+        && condition.matches('\'').count() % 2 == 0;
+    if !is_condition {
+        return Err(format!(
+            "'{condition}' is not a valid condition for the '{keyword}' keyword"
+        ));
+    }
+    Ok(condition.to_string())
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Operation {
     Get,
@@ -99,6 +456,19 @@ impl Operation {
     }
 }
 
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operation = match self {
+            Operation::Get => "get",
+            Operation::List => "list",
+            Operation::Create => "create",
+            Operation::Update => "update",
+            Operation::Delete => "delete",
+        };
+        write!(f, "{operation}")
+    }
+}
+
 impl FromStr for Operation {
     type Err = ();
 
@@ -114,13 +484,44 @@ impl FromStr for Operation {
     }
 }
 
+/// The `T` of a `wrapper<T>` type (eg: `Option<T>`)
+fn inner_type<'a>(_type: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    let Type::Path(type_path) = _type else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != wrapper {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) if arguments.args.len() == 1 => {
+            match arguments.args.first() {
+                Some(GenericArgument::Type(inner_type)) => Some(inner_type),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Column {
     pub name: String,
     pub ident: Ident,
     pub _type: Type,
     pub auto_increment: bool,
+    pub uuid: bool,
     pub primary_key: bool,
+    pub db_default: bool,
+    pub bulk_updatable: bool,
+    pub readonly: bool,
+    pub unique: bool,
+    pub filterable: bool,
+    pub json: bool,
+    pub transform: Option<Path>,
+    pub sequence: Option<String>,
+    /// Bound from the `tenant` argument of `create_for()`
+    pub injected: bool,
 }
 impl Column {
     pub fn new(name: &str, _type: Type) -> Self {
@@ -129,25 +530,276 @@ impl Column {
             ident: format_ident!("{}", name),
             _type,
             auto_increment: false,
+            uuid: false,
             primary_key: false,
+            db_default: false,
+            bulk_updatable: false,
+            readonly: false,
+            unique: false,
+            filterable: false,
+            json: false,
+            transform: None,
+            sequence: None,
+            injected: false,
         }
     }
     pub fn set_auto_increment(&mut self) {
         self.auto_increment = true;
     }
+    pub fn set_uuid(&mut self) {
+        self.uuid = true;
+    }
     pub fn set_primary_key(&mut self) {
         self.primary_key = true;
     }
+    pub fn set_db_default(&mut self) {
+        self.db_default = true;
+    }
+    pub fn set_bulk_updatable(&mut self) {
+        self.bulk_updatable = true;
+    }
+    pub fn set_readonly(&mut self) {
+        self.readonly = true;
+    }
+    pub fn set_unique(&mut self) {
+        self.unique = true;
+    }
+    pub fn set_filterable(&mut self) {
+        self.filterable = true;
+    }
+    pub fn set_json(&mut self) {
+        self.json = true;
+    }
+    pub fn set_transform(&mut self, transform: Path) {
+        self.transform = Some(transform);
+    }
+    pub fn set_name(&mut self, name: &str) {
+        self.name = name.to_string();
+    }
+    /// The name of the column in the SQL, quoted when needed
+    pub fn sql_name(&self, db_type: &DbType) -> String {
+        quote_identifier(&self.name, db_type)
+    }
+    /// The key is still generated by the database, but from the Postgres sequence given
+    pub fn set_sequence(&mut self, sequence: &str) -> Result<(), String> {
+        let is_identifier = |segment: &str| {
+            segment
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        };
+        if !sequence.split('.').all(is_identifier) {
+            return Err(format!("'{sequence}' is not a valid sequence name"));
+        }
+        self.auto_increment = true;
+        self.sequence = Some(sequence.to_string());
+        Ok(())
+    }
+    /// A column written by `create`, a key drawn from a sequence being inserted with `nextval()`
+    pub fn is_inserted(&self) -> bool {
+        (!self.auto_increment || self.sequence.is_some()) && !self.db_default && !self.readonly
+    }
+    /// The SQL expression inserted in place of a bound value, for a key drawn from a sequence
+    pub fn nextval(&self) -> Option<String> {
+        self.sequence
+            .as_ref()
+            .map(|sequence| format!("nextval('{sequence}')"))
+    }
+    /// The `T` of an `Option<T>` column
+    pub fn option_inner_type(&self) -> Option<&Type> {
+        inner_type(&self._type, "Option")
+    }
+    /// The `T` of a `SetOption<T>` column
+    pub fn set_option_inner_type(&self) -> Option<&Type> {
+        inner_type(&self._type, "SetOption")
+    }
+    /// The `T` of a `T`, `Option<T>`, `SetOption<T>` or `SetOption<Option<T>>` column
+    pub fn value_type(&self) -> &Type {
+        let _type = self.set_option_inner_type().unwrap_or(&self._type);
+        inner_type(_type, "Option").unwrap_or(_type)
+    }
+    /// An `Option<T>` or a `SetOption<Option<T>>` column
+    pub fn is_nullable(&self) -> bool {
+        let _type = self.set_option_inner_type().unwrap_or(&self._type);
+        inner_type(_type, "Option").is_some()
+    }
+    /// The type used to look up a record, `T` for an `Option<T>` primary key
+    pub fn unwrapped_type(&self) -> &Type {
+        match self.primary_key {
+            true => self.option_inner_type().unwrap_or(&self._type),
+            false => &self._type,
+        }
+    }
+    pub fn is_optional_primary_key(&self) -> bool {
+        self.primary_key && self.option_inner_type().is_some()
+    }
+    pub fn is_integer(&self) -> bool {
+        match self.unwrapped_type() {
+            Type::Path(type_path) => type_path
+                .path
+                .get_ident()
+                .is_some_and(|ident| INTEGER_TYPES.contains(&ident.to_string().as_str())),
+            _ => false,
+        }
+    }
+    /// A chrono or time timestamp, optionally wrapped in an `Option` or a `SetOption`
+    pub fn is_timestamp(&self) -> bool {
+        let _type = self
+            .option_inner_type()
+            .or(self.set_option_inner_type())
+            .unwrap_or(&self._type);
+        match _type {
+            Type::Path(type_path) => type_path.path.segments.last().is_some_and(|segment| {
+                TIMESTAMP_TYPES.contains(&segment.ident.to_string().as_str())
+            }),
+            _ => false,
+        }
+    }
+    /// The value is not bound as is, but wrapped by `json` or encoded by a `transform`
+    pub fn wraps_bind(&self) -> bool {
+        self.json || self.transform.is_some()
+    }
     pub fn use_set_options(&self) -> bool {
         FIND_SET_OPTION_REGEX.is_match(&self._type.to_token_stream().to_string())
     }
 }
 
+/// Same renaming rules as the `#[sqlx(rename_all = "...")]` attribute of `FromRow`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenameAll {
+    Lower,
+    Upper,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    Camel,
+    Pascal,
+}
+impl RenameAll {
+    pub fn apply(&self, input: &str) -> String {
+        match self {
+            RenameAll::Lower => input.to_lowercase(),
+            RenameAll::Upper => input.to_uppercase(),
+            RenameAll::Snake => input.to_case(Case::Snake),
+            RenameAll::ScreamingSnake => input.to_case(Case::UpperSnake),
+            RenameAll::Kebab => input.to_case(Case::Kebab),
+            RenameAll::Camel => input.to_case(Case::Camel),
+            RenameAll::Pascal => input.to_case(Case::Pascal),
+        }
+    }
+}
+impl FromStr for RenameAll {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<RenameAll, Self::Err> {
+        match input {
+            "lowercase" => Ok(RenameAll::Lower),
+            "UPPERCASE" => Ok(RenameAll::Upper),
+            "snake_case" => Ok(RenameAll::Snake),
+            "SCREAMING_SNAKE_CASE" => Ok(RenameAll::ScreamingSnake),
+            "kebab-case" => Ok(RenameAll::Kebab),
+            "camelCase" => Ok(RenameAll::Camel),
+            "PascalCase" => Ok(RenameAll::Pascal),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SoftDeletion {
+    pub column: String,
+    /// Expression used instead of the default current timestamp of the backend
+    pub now: Option<String>,
+    /// A boolean column (eg: `is_deleted`) set to `true` instead of a timestamp
+    pub boolean: bool,
+}
+impl SoftDeletion {
+    pub fn new(column: &str) -> Self {
+        Self {
+            column: column.trim().to_string(),
+            now: None,
+            boolean: false,
+        }
+    }
+    pub fn new_bool(column: &str) -> Self {
+        Self {
+            boolean: true,
+            ..Self::new(column)
+        }
+    }
+    /// The condition of the records which are not deleted
+    pub fn condition(&self) -> String {
+        match self.boolean {
+            true => format!("{} = false", self.column),
+            false => format!("{} IS NULL", self.column),
+        }
+    }
+    pub fn set_now(&mut self, now: &str) -> Result<(), String> {
+        if self.boolean {
+            return Err(
+                "The 'soft_delete_now' keyword cannot be used with a boolean 'soft_deletion'"
+                    .to_string(),
+            );
+        }
+        let now = now.trim();
+        if !FUNCTION_CALL_REGEX.is_match(now) {
+            return Err(format!(
+                "'{now}' is not a valid function call for the 'soft_delete_now' keyword"
+            ));
+        }
+        self.now = Some(now.to_string());
+        Ok(())
+    }
+}
+impl Default for SoftDeletion {
+    fn default() -> Self {
+        Self::new("deleted_at")
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TableName(pub String);
 impl TableName {
+    /// Each segment of a qualified name (eg: `billing.invoice`) is snake cased on its own
     pub fn new(input: &str) -> Self {
-        Self(input.to_case(Case::Snake))
+        Self(
+            input
+                .split('.')
+                .map(|segment| segment.trim().to_case(Case::Snake))
+                .collect::<Vec<_>>()
+                .join("."),
+        )
+    }
+    /// The prefix goes before the table itself, after its schema
+    pub fn with_prefix(&self, prefix: &str) -> Self {
+        match self.qualifier() {
+            Some(qualifier) => Self(format!("{qualifier}.{prefix}{}", self.table())),
+            None => Self(format!("{prefix}{}", self.0)),
+        }
+    }
+    /// The schema (or the attached database for SQLite) of a qualified name
+    pub fn qualifier(&self) -> Option<&str> {
+        self.0.rsplit_once('.').map(|(qualifier, _)| qualifier)
+    }
+    /// The name without its qualifier
+    pub fn table(&self) -> &str {
+        self.0.rsplit_once('.').map_or(&self.0, |(_, table)| table)
+    }
+    /// The name used in the SQL, each segment of a qualified name is quoted for the backend
+    /// (eg: `"billing"."invoice"` or `` `billing`.`invoice` `` with MySQL)
+    pub fn quoted(&self, db_type: &DbType) -> String {
+        if self.qualifier().is_none() {
+            return self.0.clone();
+        }
+        let quote = identifier_quote(db_type);
+        self.0
+            .split('.')
+            .map(|segment| format!("{quote}{segment}{quote}"))
+            .collect::<Vec<_>>()
+            .join(".")
     }
 }
 
@@ -168,6 +820,286 @@ mod tests {
 
     use super::*;
 
+    mod returning {
+        use super::*;
+
+        #[test]
+        fn test_new_trims_columns() {
+            let returning = Returning::new(" id,created_at ,  _rev ").unwrap();
+            assert_eq!(
+                returning.0,
+                vec![
+                    "id".to_string(),
+                    "created_at".to_string(),
+                    "_rev".to_string()
+                ]
+            );
+            assert_eq!(returning.sql(&DbType::Postgres), "id, created_at, _rev");
+        }
+
+        #[test]
+        fn test_new_rejects_non_identifiers() {
+            assert_eq!(
+                Returning::new("id; DROP TABLE contact"),
+                Err(
+                    "'id; DROP TABLE contact' is not a valid column name for the 'returning' keyword"
+                        .to_string()
+                )
+            );
+        }
+
+        #[test]
+        fn test_new_rejects_empty_column() {
+            assert!(Returning::new("id,,created_at").is_err());
+        }
+    }
+
+    mod projection {
+        use super::*;
+
+        #[test]
+        fn test_fn_name_removes_the_struct_name() {
+            let struct_name = format_ident!("Todo");
+            let summary = Projection::new(format_ident!("TodoSummary"), "id, description").unwrap();
+            assert_eq!(
+                summary.columns,
+                vec!["id".to_string(), "description".to_string()]
+            );
+            assert_eq!(summary.fn_name(&struct_name), "list_all_summary");
+
+            let card = Projection::new(format_ident!("ContactCard"), "id").unwrap();
+            assert_eq!(card.fn_name(&struct_name), "list_all_contact_card");
+            let todo = Projection::new(format_ident!("Todo"), "id").unwrap();
+            assert_eq!(todo.fn_name(&struct_name), "list_all_todo");
+        }
+
+        #[test]
+        fn test_new_rejects_non_identifiers() {
+            assert_eq!(
+                Projection::new(format_ident!("TodoSummary"), "id, * FROM todo --"),
+                Err(
+                    "'* FROM todo --' is not a valid column name for the 'projections' keyword"
+                        .to_string()
+                )
+            );
+        }
+    }
+
+    mod inject {
+        use super::*;
+
+        #[test]
+        fn test_new_takes_a_single_column() {
+            assert_eq!(Inject::new(" tenant_id ").unwrap().0, "tenant_id");
+            assert_eq!(
+                Inject::new("tenant_id, account_id"),
+                Err("The 'inject' keyword takes a single column".to_string())
+            );
+            assert!(Inject::new("tenant_id; --").is_err());
+        }
+    }
+
+    mod scope {
+        use super::*;
+
+        #[test]
+        fn test_new_trims_condition() {
+            let scope = Scope::new(" archived = false ").unwrap();
+            assert_eq!(scope.to_string(), "archived = false");
+            assert!(Scope::new("status = 'open' AND tenant_id = 1").is_ok());
+        }
+
+        #[test]
+        fn test_new_rejects_invalid_conditions() {
+            assert_eq!(
+                Scope::new("archived = false; DROP TABLE contact"),
+                Err(
+                    "'archived = false; DROP TABLE contact' is not a valid condition for the 'scope' keyword"
+                        .to_string()
+                )
+            );
+            assert!(Scope::new("  ").is_err());
+            assert!(Scope::new("archived = false --").is_err());
+            assert!(Scope::new("status = 'open").is_err());
+        }
+    }
+
+    mod order_by {
+        use super::*;
+
+        #[test]
+        fn test_new_defaults_to_ascending() {
+            let order_by = OrderBy::new(" created_at desc,id ").unwrap();
+            assert_eq!(
+                order_by.0,
+                vec![
+                    ("created_at".to_string(), "DESC".to_string()),
+                    ("id".to_string(), "ASC".to_string())
+                ]
+            );
+            assert_eq!(order_by.to_string(), "created_at DESC, id ASC");
+        }
+
+        #[test]
+        fn test_new_rejects_invalid_orders() {
+            assert_eq!(
+                OrderBy::new("created_at DESC NULLS LAST"),
+                Err(
+                    "'created_at DESC NULLS LAST' is not a valid order for the 'order_by' keyword"
+                        .to_string()
+                )
+            );
+            assert!(OrderBy::new("created_at UP").is_err());
+            assert!(OrderBy::new("id; DROP TABLE contact").is_err());
+            assert!(OrderBy::new("id,").is_err());
+        }
+    }
+
+    mod conflict_target {
+        use super::*;
+
+        #[test]
+        fn test_sql() {
+            let conflict_target = ConflictTarget::new("tenant_id, email").unwrap();
+            assert_eq!(conflict_target.sql(&DbType::Postgres), "(tenant_id, email)");
+
+            let conflict_target = conflict_target
+                .with_predicate(" deleted_at IS NULL ")
+                .unwrap();
+            assert_eq!(
+                conflict_target.sql(&DbType::Postgres),
+                "(tenant_id, email) WHERE deleted_at IS NULL"
+            );
+
+            let conflict_target = ConflictTarget::new("tenantId").unwrap();
+            assert_eq!(conflict_target.sql(&DbType::MySQL), "(`tenantId`)");
+        }
+
+        #[test]
+        fn test_new_rejects_invalid_inputs() {
+            assert_eq!(
+                ConflictTarget::new("lower(email)"),
+                Err(
+                    "'lower(email)' is not a valid column name for the 'conflict_target' keyword"
+                        .to_string()
+                )
+            );
+            assert_eq!(
+                ConflictTarget::new("email")
+                    .unwrap()
+                    .with_predicate("deleted_at IS NULL; --"),
+                Err(
+                    "'deleted_at IS NULL; --' is not a valid condition for the 'conflict_where' keyword"
+                        .to_string()
+                )
+            );
+        }
+    }
+
+    mod quote_identifier {
+        use super::*;
+
+        #[test]
+        fn test_only_quotes_names_which_are_not_plain_identifiers() {
+            assert_eq!(
+                quote_identifier("first_name", &DbType::Postgres),
+                "first_name"
+            );
+            assert_eq!(quote_identifier("_rev2", &DbType::Postgres), "_rev2");
+            assert_eq!(
+                quote_identifier("firstName", &DbType::Postgres),
+                r#""firstName""#
+            );
+            assert_eq!(
+                quote_identifier("first-name", &DbType::Sqlite),
+                r#""first-name""#
+            );
+            assert_eq!(
+                quote_identifier("FIRST_NAME", &DbType::MySQL),
+                "`FIRST_NAME`"
+            );
+        }
+
+        #[test]
+        fn test_column_sql_name() {
+            let mut column = Column::new("first_name", parse_quote!(String));
+            assert_eq!(column.sql_name(&DbType::Postgres), "first_name");
+            column.set_name(&RenameAll::Kebab.apply("first_name"));
+            assert_eq!(column.sql_name(&DbType::Postgres), r#""first-name""#);
+            assert_eq!(column.sql_name(&DbType::MySQL), "`first-name`");
+        }
+    }
+
+    mod table_name {
+        use super::*;
+
+        #[test]
+        fn test_new_snake_cases_each_segment() {
+            let table_name = TableName::new("Billing.InvoiceItem");
+            assert_eq!(table_name.to_string(), "billing.invoice_item");
+            assert_eq!(table_name.qualifier(), Some("billing"));
+            assert_eq!(table_name.table(), "invoice_item");
+
+            let table_name = TableName::new("InvoiceItem");
+            assert_eq!(table_name.to_string(), "invoice_item");
+            assert_eq!(table_name.qualifier(), None);
+            assert_eq!(table_name.table(), "invoice_item");
+        }
+
+        #[test]
+        fn test_quoted_quotes_each_segment_of_a_qualified_name() {
+            let table_name = TableName::new("billing.invoice");
+            assert_eq!(
+                table_name.quoted(&DbType::Postgres),
+                r#""billing"."invoice""#
+            );
+            assert_eq!(table_name.quoted(&DbType::Sqlite), r#""billing"."invoice""#);
+            assert_eq!(table_name.quoted(&DbType::MySQL), "`billing`.`invoice`");
+
+            let table_name = TableName::new("invoice");
+            assert_eq!(table_name.quoted(&DbType::Postgres), "invoice");
+            assert_eq!(table_name.quoted(&DbType::MySQL), "invoice");
+        }
+
+        #[test]
+        fn test_with_prefix_keeps_the_qualifier_first() {
+            assert_eq!(
+                TableName::new("billing.invoice")
+                    .with_prefix("tenant_")
+                    .to_string(),
+                "billing.tenant_invoice"
+            );
+            assert_eq!(
+                TableName::new("invoice").with_prefix("tenant_").to_string(),
+                "tenant_invoice"
+            );
+        }
+    }
+
+    mod rename_all {
+        use super::*;
+
+        #[test]
+        fn test_from_str() {
+            assert_eq!(RenameAll::from_str("camelCase"), Ok(RenameAll::Camel));
+            assert_eq!(RenameAll::from_str("PascalCase"), Ok(RenameAll::Pascal));
+            assert_eq!(RenameAll::from_str("snake_case"), Ok(RenameAll::Snake));
+            assert_eq!(RenameAll::from_str("kebab-case"), Ok(RenameAll::Kebab));
+            assert_eq!(RenameAll::from_str("camel_case"), Err(()));
+        }
+
+        #[test]
+        fn test_apply() {
+            assert_eq!(RenameAll::Camel.apply("created_at"), "createdAt");
+            assert_eq!(RenameAll::Pascal.apply("created_at"), "CreatedAt");
+            assert_eq!(RenameAll::Snake.apply("created_at"), "created_at");
+            assert_eq!(RenameAll::Kebab.apply("created_at"), "created-at");
+            assert_eq!(RenameAll::Lower.apply("created_at"), "created_at");
+            assert_eq!(RenameAll::Upper.apply("created_at"), "CREATED_AT");
+            assert_eq!(RenameAll::ScreamingSnake.apply("created_at"), "CREATED_AT");
+        }
+    }
+
     mod column {
         use super::*;
 
@@ -187,6 +1119,101 @@ mod tests {
             assert!(column.primary_key);
         }
 
+        #[test]
+        fn test_set_name_keeps_ident() {
+            let mut column = Column::new("col_name", parse_quote!(i32));
+            column.set_name("colName");
+            assert_eq!(column.name, "colName".to_string());
+            assert_eq!(column.ident, format_ident!("col_name"));
+        }
+
+        #[test]
+        fn test_is_integer() {
+            assert!(Column::new("id", parse_quote!(i64)).is_integer());
+            assert!(Column::new("id", parse_quote!(u32)).is_integer());
+            assert!(!Column::new("id", parse_quote!(TodoId)).is_integer());
+            assert!(!Column::new("id", parse_quote!(Uuid)).is_integer());
+            assert!(!Column::new("id", parse_quote!(std::primitive::i64)).is_integer());
+
+            let mut primary_key = Column::new("id", parse_quote!(Option<i64>));
+            assert!(!primary_key.is_integer());
+            primary_key.set_primary_key();
+            assert!(primary_key.is_integer());
+        }
+
+        #[test]
+        fn test_is_timestamp() {
+            for _type in [
+                parse_quote!(DateTime<Utc>),
+                parse_quote!(chrono::DateTime<chrono::Utc>),
+                parse_quote!(NaiveDateTime),
+                parse_quote!(time::OffsetDateTime),
+                parse_quote!(sqlx::types::time::PrimitiveDateTime),
+                parse_quote!(Option<DateTime<Utc>>),
+                parse_quote!(SetOption<NaiveDateTime>),
+            ] {
+                assert!(Column::new("updated_at", _type).is_timestamp());
+            }
+            for _type in [
+                parse_quote!(String),
+                parse_quote!(i64),
+                parse_quote!(Option<String>),
+            ] {
+                assert!(!Column::new("updated_at", _type).is_timestamp());
+            }
+        }
+
+        #[test]
+        fn test_option_inner_type() {
+            let inner_type: Type = parse_quote!(Uuid);
+            assert_eq!(
+                Column::new("id", parse_quote!(Option<Uuid>)).option_inner_type(),
+                Some(&inner_type)
+            );
+            assert_eq!(
+                Column::new("id", parse_quote!(std::option::Option<Uuid>)).option_inner_type(),
+                Some(&inner_type)
+            );
+            assert_eq!(
+                Column::new("id", parse_quote!(Uuid)).option_inner_type(),
+                None
+            );
+            assert_eq!(
+                Column::new("id", parse_quote!(SetOption<Uuid>)).option_inner_type(),
+                None
+            );
+        }
+
+        #[test]
+        fn test_set_option_inner_type() {
+            let inner_type: Type = parse_quote!(Option<String>);
+            assert_eq!(
+                Column::new(
+                    "col_name",
+                    parse_quote!(tiny_orm::SetOption<Option<String>>)
+                )
+                .set_option_inner_type(),
+                Some(&inner_type)
+            );
+            assert_eq!(
+                Column::new("col_name", parse_quote!(Option<String>)).set_option_inner_type(),
+                None
+            );
+        }
+
+        #[test]
+        fn test_unwrapped_type() {
+            let mut primary_key = Column::new("id", parse_quote!(Option<Uuid>));
+            let option_type: Type = parse_quote!(Option<Uuid>);
+            assert_eq!(primary_key.unwrapped_type(), &option_type);
+            assert!(!primary_key.is_optional_primary_key());
+
+            primary_key.set_primary_key();
+            let inner_type: Type = parse_quote!(Uuid);
+            assert_eq!(primary_key.unwrapped_type(), &inner_type);
+            assert!(primary_key.is_optional_primary_key());
+        }
+
         #[test]
         fn test_use_set_options_true() {
             let col_name = "col_name";
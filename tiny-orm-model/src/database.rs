@@ -0,0 +1,28 @@
+#[cfg(any(
+    all(feature = "postgres", feature = "mysql"),
+    all(feature = "postgres", feature = "sqlite"),
+    all(feature = "mysql", feature = "sqlite"),
+))]
+compile_error!("Only one of the 'postgres', 'mysql' or 'sqlite' features can be enabled at a time");
+
+/// tiny_orm::Db is the sqlx database of the backend selected with the `postgres`, `mysql` or `sqlite` feature.
+#[cfg(feature = "postgres")]
+pub type Db = sqlx::Postgres;
+/// tiny_orm::Db is the sqlx database of the backend selected with the `postgres`, `mysql` or `sqlite` feature.
+#[cfg(feature = "mysql")]
+pub type Db = sqlx::MySql;
+/// tiny_orm::Db is the sqlx database of the backend selected with the `postgres`, `mysql` or `sqlite` feature.
+#[cfg(feature = "sqlite")]
+pub type Db = sqlx::Sqlite;
+
+/// tiny_orm::DbPool is the sqlx connection pool of the selected backend.
+/// It makes the code portable across backends.
+/// ```rust
+/// # use tiny_orm_model::DbPool;
+/// async fn ping(pool: &DbPool) -> sqlx::Result<()> {
+///     let _ = pool.acquire().await?;
+///     Ok(())
+/// }
+/// ```
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+pub type DbPool = sqlx::Pool<Db>;
@@ -1,5 +1,16 @@
+mod database;
 mod errors;
+mod order;
+mod page;
+mod tiny_table;
+mod upsert_outcome;
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+pub use crate::database::{Db, DbPool};
 pub use crate::errors::TinyOrmError;
+pub use crate::order::Order;
+pub use crate::page::Page;
+pub use crate::tiny_table::TinyTable;
+pub use crate::upsert_outcome::UpsertOutcome;
 
 #[cfg(feature = "set-option")]
 mod set_option;
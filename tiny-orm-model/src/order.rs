@@ -0,0 +1,30 @@
+/// tiny_orm::Order is the direction of a column in the `ORDER BY` of the `list_all_ordered()` method.
+/// ```rust
+/// # use tiny_orm_model::Order;
+/// assert_eq!(Order::default(), Order::Asc);
+/// assert_eq!(Order::Desc.as_sql(), "DESC");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Order {
+    /// From the lowest to the highest value.
+    #[default]
+    Asc,
+    /// From the highest to the lowest value.
+    Desc,
+}
+
+impl Order {
+    /// `as_sql()` returns the SQL keyword of the direction.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use tiny_orm_model::Order;
+    /// assert_eq!(Order::Asc.as_sql(), "ASC");
+    /// ```
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            Order::Asc => "ASC",
+            Order::Desc => "DESC",
+        }
+    }
+}
@@ -0,0 +1,66 @@
+/// tiny_orm::Page is a page of records returned by the `list_page()` method.
+/// On top of the records, it holds the total number of records so that it is possible to paginate.
+/// ```rust
+/// # use tiny_orm_model::Page;
+/// let page = Page::new(vec!["a", "b"], 5, 2, 0);
+/// assert_eq!(page.items, vec!["a", "b"]);
+/// assert_eq!(page.total, 5);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Page<T> {
+    /// The records of the page.
+    pub items: Vec<T>,
+    /// The total number of records, regardless of the `limit` and the `offset`.
+    pub total: i64,
+    /// The maximum number of records in the page.
+    pub limit: i64,
+    /// The number of records skipped before the page.
+    pub offset: i64,
+}
+
+impl<T> Page<T> {
+    pub fn new(items: Vec<T>, total: i64, limit: i64, offset: i64) -> Self {
+        Self {
+            items,
+            total,
+            limit,
+            offset,
+        }
+    }
+
+    /// `has_next()` returns true when there are records after the current page.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use tiny_orm_model::Page;
+    /// let page = Page::new(vec![1, 2], 5, 2, 2);
+    /// assert!(page.has_next());
+    /// ```
+    ///
+    /// ```rust
+    /// # use tiny_orm_model::Page;
+    /// let page = Page::new(vec![5], 5, 2, 4);
+    /// assert!(!page.has_next());
+    /// ```
+    pub fn has_next(&self) -> bool {
+        self.offset + (self.items.len() as i64) < self.total
+    }
+
+    /// `has_previous()` returns true when there are records before the current page.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use tiny_orm_model::Page;
+    /// let page = Page::new(vec![1, 2], 5, 2, 0);
+    /// assert!(!page.has_previous());
+    /// ```
+    ///
+    /// ```rust
+    /// # use tiny_orm_model::Page;
+    /// let page = Page::new(vec![3, 4], 5, 2, 2);
+    /// assert!(page.has_previous());
+    /// ```
+    pub fn has_previous(&self) -> bool {
+        self.offset > 0
+    }
+}
@@ -0,0 +1,57 @@
+use std::future::Future;
+
+/// `TinyTable` is implemented by the `Table` macro so that generic code can run the CRUD operations across models.
+/// The trait is only implemented when the `get`, `create`, `update` and `delete` operations are all available for the struct.
+/// Each method simply calls the inherent method generated by the macro.
+///
+/// # Examples
+/// ```rust,ignore
+/// use tiny_orm::TinyTable;
+///
+/// async fn create_then_get<T>(item: &T, pool: &sqlx::SqlitePool) -> sqlx::Result<Option<T::ReturnObject>>
+/// where
+///     T: TinyTable<Database = sqlx::Sqlite>,
+///     T::Created: Into<T::PrimaryKey>,
+/// {
+///     let id = item.create(pool).await?.into();
+///     T::get_by_id(pool, &id).await
+/// }
+/// ```
+pub trait TinyTable: Sized {
+    /// The database the struct is mapped to (eg: `sqlx::Postgres`).
+    type Database: sqlx::Database;
+    /// The type of the primary key.
+    type PrimaryKey;
+    /// The object returned when reading records. Default being `Self`.
+    type ReturnObject;
+    /// The value returned by `create()`. Either the primary key or the `ReturnObject`.
+    type Created;
+    /// The value returned by `update()`. Either `()` or the `ReturnObject`.
+    type Updated;
+
+    /// The name of the table in the database.
+    fn table_name() -> &'static str;
+
+    /// Get a specific record for a given primary key.
+    fn get_by_id<'e, E>(
+        db: E,
+        id: &Self::PrimaryKey,
+    ) -> impl Future<Output = sqlx::Result<Option<Self::ReturnObject>>> + Send
+    where
+        E: sqlx::Executor<'e, Database = Self::Database>;
+
+    /// Create the object as a record in the database.
+    fn create<'e, E>(&self, db: E) -> impl Future<Output = sqlx::Result<Self::Created>> + Send
+    where
+        E: sqlx::Executor<'e, Database = Self::Database>;
+
+    /// Update the record in the database with the values of the object.
+    fn update<'e, E>(&self, db: E) -> impl Future<Output = sqlx::Result<Self::Updated>> + Send
+    where
+        E: sqlx::Executor<'e, Database = Self::Database>;
+
+    /// Delete the record in the database.
+    fn delete<'e, E>(&self, db: E) -> impl Future<Output = sqlx::Result<()>> + Send
+    where
+        E: sqlx::Executor<'e, Database = Self::Database>;
+}
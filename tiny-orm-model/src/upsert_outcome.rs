@@ -0,0 +1,54 @@
+/// tiny_orm::UpsertOutcome is returned by the `upsert_with_outcome()` method.
+/// It tells whether the record has been inserted or an existing record has been updated.
+/// ```rust
+/// # use tiny_orm_model::UpsertOutcome;
+/// let outcome = UpsertOutcome::Inserted("a");
+/// assert!(outcome.is_inserted());
+/// assert_eq!(outcome.into_inner(), "a");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum UpsertOutcome<T> {
+    /// No record had the same primary key so a new one has been inserted.
+    Inserted(T),
+    /// A record with the same primary key already existed and has been updated.
+    Updated(T),
+}
+
+impl<T> UpsertOutcome<T> {
+    /// `is_inserted()` returns true when a new record has been inserted.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use tiny_orm_model::UpsertOutcome;
+    /// assert!(UpsertOutcome::Inserted(1).is_inserted());
+    /// assert!(!UpsertOutcome::Updated(1).is_inserted());
+    /// ```
+    pub fn is_inserted(&self) -> bool {
+        matches!(self, UpsertOutcome::Inserted(_))
+    }
+
+    /// `is_updated()` returns true when an existing record has been updated.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use tiny_orm_model::UpsertOutcome;
+    /// assert!(UpsertOutcome::Updated(1).is_updated());
+    /// assert!(!UpsertOutcome::Inserted(1).is_updated());
+    /// ```
+    pub fn is_updated(&self) -> bool {
+        matches!(self, UpsertOutcome::Updated(_))
+    }
+
+    /// `into_inner()` returns the record, regardless of the outcome.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use tiny_orm_model::UpsertOutcome;
+    /// assert_eq!(UpsertOutcome::Updated(1).into_inner(), 1);
+    /// ```
+    pub fn into_inner(self) -> T {
+        match self {
+            UpsertOutcome::Inserted(value) | UpsertOutcome::Updated(value) => value,
+        }
+    }
+}